@@ -120,3 +120,70 @@ def generate_concat_file_content(slice_paths: list[Path]) -> str:
 
 def build_video_cleaned_output_path(video_path: Path) -> Path:
     return video_path.parent / "video_cleaned" / video_path.name
+
+
+def build_enable_expression(ranges_seconds: list[tuple[float, float]]) -> str:
+    return "+".join(f"between(t,{start:.3f},{end:.3f})" for start, end in ranges_seconds)
+
+
+def build_ffmpeg_mute_command(
+    ffmpeg_path: str,
+    video_path: Path,
+    output_path: Path,
+    ranges_seconds: list[tuple[float, float]],
+) -> list[str]:
+    enable = build_enable_expression(ranges_seconds)
+    return [
+        ffmpeg_path,
+        "-y",
+        "-i",
+        str(video_path),
+        "-af",
+        f"volume=enable='{enable}':volume=0",
+        "-c:v",
+        "copy",
+        "-c:a",
+        "aac",
+        str(output_path),
+    ]
+
+
+def build_ffmpeg_bleep_command(
+    ffmpeg_path: str,
+    video_path: Path,
+    output_path: Path,
+    ranges_seconds: list[tuple[float, float]],
+    tone_frequency: int = 1000,
+) -> list[str]:
+    enable = build_enable_expression(ranges_seconds)
+    filter_graph = (
+        f"[0:a]volume=enable='{enable}':volume=0[speech];"
+        f"[1:a]volume=0.3,volume=enable='not({enable})':volume=0[tone];"
+        "[speech][tone]amix=inputs=2:duration=first:normalize=0[audio]"
+    )
+    return [
+        ffmpeg_path,
+        "-y",
+        "-i",
+        str(video_path),
+        "-f",
+        "lavfi",
+        "-i",
+        f"sine=frequency={tone_frequency}:sample_rate=48000",
+        "-filter_complex",
+        filter_graph,
+        "-map",
+        "0:v:0",
+        "-map",
+        "[audio]",
+        "-c:v",
+        "copy",
+        "-c:a",
+        "aac",
+        str(output_path),
+    ]
+
+
+def build_audio_redacted_output_path(video_path: Path, output_mode: str) -> Path:
+    folder = "video_muted" if output_mode == "mute" else "video_bleeped"
+    return video_path.parent / folder / video_path.name
@@ -17,11 +17,19 @@ class StartTranscriptionBatchCommand:
     yap_mode: str
 
 
+@dataclass(slots=True)
+class ProfileRef:
+    id: str
+    name: str
+
+
 @dataclass(slots=True)
 class StartFlagBatchCommand:
     task_id: str
     input_paths: list[str]
     settings: dict[str, Any]
+    # Moderation profile, recorded in the analysis sidecars.
+    profile: ProfileRef | None = None
 
 
 @dataclass(slots=True)
@@ -38,6 +46,39 @@ class StartCutJobCommand:
     output_mode: str
 
 
+@dataclass(slots=True)
+class PlannedCutJob:
+    video_path: str
+    # Ranges for the cut job in the batch's or cut stage's output mode.
+    ranges: list[CutRange]
+    removed_ranges: list[CutRange]
+    # Set when the host could not plan the video; its job fails with it.
+    error: str | None = None
+
+
+@dataclass(slots=True)
+class StartCutBatchCommand:
+    task_id: str
+    jobs: list[PlannedCutJob]
+    output_mode: str
+
+
+@dataclass(slots=True)
+class PipelineStage:
+    stage: str
+    options: dict[str, Any]
+
+
+@dataclass(slots=True)
+class StartPipelineCommand:
+    task_id: str
+    input_paths: list[str]
+    stages: list[PipelineStage]
+    settings: dict[str, Any]
+    # Moderation profile the flag stage records in the analysis sidecars.
+    profile: ProfileRef | None = None
+
+
 @dataclass(slots=True)
 class CancelBatchCommand:
     batch_id: str
@@ -50,11 +91,52 @@ class CancelTaskCommand:
     mode: str
 
 
+@dataclass(slots=True)
+class PauseBatchCommand:
+    batch_id: str
+
+
+@dataclass(slots=True)
+class ResumeBatchCommand:
+    batch_id: str
+
+
+@dataclass(slots=True)
+class PauseTaskCommand:
+    task_id: str
+
+
+@dataclass(slots=True)
+class ResumeTaskCommand:
+    task_id: str
+
+
+@dataclass(slots=True)
+class ProvideCutPlanCommand:
+    """The host's answer to a pipeline's `cut_plan_needed` event."""
+
+    task_id: str
+    job: PlannedCutJob
+
+
+@dataclass(slots=True)
+class PingCommand:
+    ping_id: str
+
+
 WorkerCommand = (
     StartBatchCommand
     | StartTranscriptionBatchCommand
     | StartFlagBatchCommand
     | StartCutJobCommand
+    | StartCutBatchCommand
+    | StartPipelineCommand
     | CancelBatchCommand
     | CancelTaskCommand
+    | PauseBatchCommand
+    | ResumeBatchCommand
+    | PauseTaskCommand
+    | ResumeTaskCommand
+    | ProvideCutPlanCommand
+    | PingCommand
 )
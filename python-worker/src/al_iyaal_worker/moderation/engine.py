@@ -1,5 +1,10 @@
+"""The blacklist engine for pipeline flag stages, which flag the subtitles the
+worker transcribed earlier in the same run. Standalone flag tasks run the app's
+copy (`blacklist.rs`); the `tests/fixtures/blacklist_parity*.json` fixtures
+keep the two producing identical output."""
+
 from collections import Counter
-from dataclasses import dataclass
+from dataclasses import dataclass, field
 import re
 from typing import Any, Literal
 
@@ -17,8 +22,10 @@ except Exception:  # pragma: no cover - fallback for dev environments without ru
     profanity = _FallbackProfanity()
 
 from ..subtitles import SubtitleEntry
+from .normalization import TextNormalization, normalize_text
 
 Priority = Literal["high", "medium", "low"]
+Pattern = str | re.Pattern[str]
 
 
 @dataclass(slots=True)
@@ -27,7 +34,17 @@ class ModerationRule:
     category: str
     priority: Priority
     reason: str
-    patterns: list[str]
+    patterns: list[Pattern]
+    exclusions: list[Pattern] = field(default_factory=list)
+
+    def matches(self, normalized_text: str) -> bool:
+        """Whether a pattern matches somewhere no exclusion match overlaps."""
+        excluded = [span for exclusion in self.exclusions for span in _pattern_spans(exclusion, normalized_text)]
+        return any(
+            not any(start < excluded_end and excluded_start < end for excluded_start, excluded_end in excluded)
+            for pattern in self.patterns
+            for start, end in _pattern_spans(pattern, normalized_text)
+        )
 
 
 def default_rules() -> list[ModerationRule]:
@@ -77,7 +94,51 @@ def _to_priority(value: str) -> Priority:
     return "medium"
 
 
-def _normalize_rules(raw_rules: Any) -> list[ModerationRule]:
+def _compile_pattern(raw_pattern: Any, normalization: TextNormalization) -> Pattern | None:
+    """Mirrors the Rust host's pattern matching. Plain strings are substring
+    patterns from before match types existed; invalid regexes match nothing.
+    Non-regex patterns are normalized like the text they are matched against."""
+    if isinstance(raw_pattern, dict):
+        value = str(raw_pattern.get("value", "")).strip()
+        match_type = str(raw_pattern.get("matchType", "substring"))
+    else:
+        value = str(raw_pattern).strip()
+        match_type = "substring"
+    if not value:
+        return None
+
+    normalized = normalize_text(value, normalization)
+    if match_type == "whole_word":
+        expression = rf"\b{re.escape(normalized)}\b"
+    elif match_type == "phrase":
+        expression = r"\b" + r"\W+".join(re.escape(word) for word in normalized.split()) + r"\b"
+    elif match_type == "stem":
+        expression = rf"\b{re.escape(normalized)}"
+    elif match_type == "regex":
+        expression = value
+    else:
+        return normalized
+
+    try:
+        return re.compile(expression, re.IGNORECASE if normalization.fold_case else 0)
+    except re.error:
+        return None
+
+
+def _compile_patterns(raw_patterns: Any, normalization: TextNormalization) -> list[Pattern]:
+    if not isinstance(raw_patterns, list):
+        return []
+    compiled = (_compile_pattern(raw_pattern, normalization) for raw_pattern in raw_patterns)
+    return [pattern for pattern in compiled if pattern is not None]
+
+
+def _pattern_spans(pattern: Pattern, normalized_text: str) -> list[tuple[int, int]]:
+    if isinstance(pattern, str):
+        return [(match.start(), match.end()) for match in re.finditer(re.escape(pattern), normalized_text)]
+    return [match.span() for match in pattern.finditer(normalized_text)]
+
+
+def _normalize_rules(raw_rules: Any, normalization: TextNormalization) -> list[ModerationRule]:
     if not isinstance(raw_rules, list):
         return default_rules()
 
@@ -95,25 +156,26 @@ def _normalize_rules(raw_rules: Any) -> list[ModerationRule]:
                 category=str(raw_rule.get("category", "custom")),
                 priority=_to_priority(str(raw_rule.get("priority", "medium"))),
                 reason=str(raw_rule.get("reason", "Matched moderation rule.")),
-                patterns=[str(pattern).strip().lower() for pattern in patterns if str(pattern).strip()],
+                patterns=_compile_patterns(patterns, normalization),
+                exclusions=_compile_patterns(raw_rule.get("exclusions", []), normalization),
             )
         )
 
     return normalized if normalized else default_rules()
 
 
-def _extract_custom_profanity_words(settings: dict[str, Any]) -> set[str]:
+def _extract_custom_profanity_words(settings: dict[str, Any], normalization: TextNormalization) -> set[str]:
     raw_words = settings.get("profanityWords", [])
     if not isinstance(raw_words, list):
         return set()
-    return {str(word).strip().lower() for word in raw_words if str(word).strip()}
+    return {normalize_text(str(word).strip(), normalization) for word in raw_words if str(word).strip()}
 
 
 def _contains_custom_word(text: str, custom_words: set[str]) -> bool:
     if not custom_words:
         return False
 
-    words = re.findall(r"[a-zA-Z']+", text.lower())
+    words = re.findall(r"[\w']+", text)
     return any(word in custom_words for word in words)
 
 
@@ -129,16 +191,17 @@ def analyze_subtitles(
     subtitles: list[SubtitleEntry],
     settings: dict[str, Any],
 ) -> tuple[list[dict[str, Any]], str]:
-    rules = _normalize_rules(settings.get("rules"))
-    custom_profanity_words = _extract_custom_profanity_words(settings)
+    normalization = TextNormalization.from_settings(settings)
+    rules = _normalize_rules(settings.get("rules"), normalization)
+    custom_profanity_words = _extract_custom_profanity_words(settings, normalization)
 
     flagged: list[dict[str, Any]] = []
     seen: set[tuple[int, str]] = set()
     for entry in subtitles:
-        lowered_text = entry.text.lower()
+        normalized_text = normalize_text(entry.text, normalization)
 
-        if profanity.contains_profanity(lowered_text) or _contains_custom_word(
-            lowered_text, custom_profanity_words
+        if profanity.contains_profanity(normalized_text) or _contains_custom_word(
+            normalized_text, custom_profanity_words
         ):
             key = (int(entry.start_time * 1000), "profanity")
             if key not in seen:
@@ -158,7 +221,7 @@ def analyze_subtitles(
         for rule in rules:
             if not rule.patterns:
                 continue
-            if not any(pattern in lowered_text for pattern in rule.patterns):
+            if not rule.matches(normalized_text):
                 continue
 
             key = (int(entry.start_time * 1000), rule.rule_id)
@@ -0,0 +1,73 @@
+"""Text normalization for blacklist matching; mirrors `text_normalization.rs`
+in the Rust host, which runs blacklist flag tasks natively."""
+
+from dataclasses import dataclass
+import re
+from typing import Any
+
+# Tanween, harakat, shadda, sukun, superscript alef, Quranic marks, tatweel and
+# combining accents of decomposed Latin text.
+_DIACRITICS_RE = re.compile("[\u064b-\u065f\u0670\u06d6-\u06ed\u0640\u0300-\u036f]")
+_ARABIC_LETTERS = str.maketrans(
+    {
+        # Alef with hamza above or below, alef with madda, alef wasla.
+        "\u0623": "\u0627",
+        "\u0625": "\u0627",
+        "\u0622": "\u0627",
+        "\u0671": "\u0627",
+        # Alef maqsura, Farsi/Urdu yeh, yeh with hamza above.
+        "\u0649": "\u064a",
+        "\u06cc": "\u064a",
+        "\u0626": "\u064a",
+        # Taa marbuta, Urdu taa marbuta goal, Urdu heh goal.
+        "\u0629": "\u0647",
+        "\u06c3": "\u0647",
+        "\u06c1": "\u0647",
+        # Keheh.
+        "\u06a9": "\u0643",
+    }
+)
+_DIGITS = str.maketrans(
+    {chr(0x0660 + digit): str(digit) for digit in range(10)}
+    | {chr(0x06F0 + digit): str(digit) for digit in range(10)}
+)
+_LEETSPEAK = str.maketrans({"0": "o", "1": "i", "3": "e", "4": "a", "@": "a", "5": "s", "$": "s", "7": "t"})
+
+
+@dataclass(slots=True, frozen=True)
+class TextNormalization:
+    fold_case: bool = True
+    strip_diacritics: bool = False
+    unify_arabic_letters: bool = False
+    fold_digits: bool = False
+    fold_leetspeak: bool = False
+
+    @classmethod
+    def from_settings(cls, settings: dict[str, Any]) -> "TextNormalization":
+        raw = settings.get("normalization")
+        if not isinstance(raw, dict):
+            return cls()
+        return cls(
+            fold_case=bool(raw.get("foldCase", True)),
+            strip_diacritics=bool(raw.get("stripDiacritics", False)),
+            unify_arabic_letters=bool(raw.get("unifyArabicLetters", False)),
+            fold_digits=bool(raw.get("foldDigits", False)),
+            fold_leetspeak=bool(raw.get("foldLeetspeak", False)),
+        )
+
+
+def normalize_text(text: str, options: TextNormalization) -> str:
+    def normalize_word(match: re.Match[str]) -> str:
+        word = match.group()
+        fold_leetspeak = options.fold_leetspeak and any(character.isalpha() for character in word)
+        if options.strip_diacritics:
+            word = _DIACRITICS_RE.sub("", word)
+        if options.fold_digits:
+            word = word.translate(_DIGITS)
+        if options.unify_arabic_letters:
+            word = word.translate(_ARABIC_LETTERS)
+        if fold_leetspeak:
+            word = word.translate(_LEETSPEAK)
+        return word.lower() if options.fold_case else word
+
+    return re.sub(r"\S+", normalize_word, text)
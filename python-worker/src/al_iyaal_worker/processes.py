@@ -0,0 +1,84 @@
+from collections.abc import Callable, Iterator
+from contextlib import contextmanager
+import subprocess
+import threading
+import time
+from typing import Any, TypeVar
+
+T = TypeVar("T")
+
+_active_processes: set[subprocess.Popen[str]] = set()
+_active_processes_lock = threading.Lock()
+
+
+class OperationAborted(Exception):
+    """Raised when an immediate cancellation interrupts the running job."""
+
+
+@contextmanager
+def tracked_popen(command: list[str], **kwargs: Any) -> Iterator[subprocess.Popen[str]]:
+    process = subprocess.Popen(command, **kwargs)  # noqa: S603
+    with _active_processes_lock:
+        _active_processes.add(process)
+    try:
+        yield process
+    finally:
+        with _active_processes_lock:
+            _active_processes.discard(process)
+
+
+def run_tracked(command: list[str]) -> subprocess.CompletedProcess[str]:
+    with tracked_popen(
+        command,
+        stdout=subprocess.PIPE,
+        stderr=subprocess.PIPE,
+        text=True,
+    ) as process:
+        stdout, stderr = process.communicate()
+    return subprocess.CompletedProcess(command, process.returncode, stdout, stderr)
+
+
+def terminate_active_processes(grace_seconds: float = 5.0) -> int:
+    with _active_processes_lock:
+        processes = list(_active_processes)
+
+    for process in processes:
+        if process.poll() is None:
+            process.terminate()
+
+    deadline = time.monotonic() + grace_seconds
+    for process in processes:
+        remaining = max(0.0, deadline - time.monotonic())
+        try:
+            process.wait(timeout=remaining)
+        except subprocess.TimeoutExpired:
+            process.kill()
+
+    return len(processes)
+
+
+def run_until_aborted(
+    work: Callable[[], T],
+    should_abort: Callable[[], bool],
+    poll_interval: float = 0.2,
+) -> T:
+    """Runs blocking work (e.g. an LLM request) on a helper thread so an
+    immediate cancellation can abandon it instead of waiting for it to return."""
+    outcome: dict[str, Any] = {}
+
+    def target() -> None:
+        try:
+            outcome["value"] = work()
+        except BaseException as error:  # noqa: BLE001 - re-raised on the caller thread
+            outcome["error"] = error
+
+    thread = threading.Thread(target=target, daemon=True, name="abortable-work")
+    thread.start()
+    while thread.is_alive():
+        if should_abort():
+            raise OperationAborted()
+        thread.join(poll_interval)
+
+    if "error" in outcome:
+        raise outcome["error"]
+    return outcome["value"]
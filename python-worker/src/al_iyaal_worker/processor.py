@@ -9,16 +9,18 @@ from .commands import build_demucs_command, build_ffmpeg_command, expected_vocal
 from .errors import map_process_failure
 from .filesystem import to_job_id
 from .models import StartBatchCommand
+from .processes import run_tracked, tracked_popen
 from .runtime import resolve_compute_device
 
 EmitEvent = Callable[[dict[str, object]], None]
 RunCommand = Callable[[list[str]], subprocess.CompletedProcess[str]]
 ShouldCancel = Callable[[], bool]
+WaitIfPaused = Callable[[], None]
 PROGRESS_PERCENT_RE = re.compile(r"(\d{1,3})%\|")
 
 
 def run_command(command: list[str]) -> subprocess.CompletedProcess[str]:
-    return subprocess.run(command, check=False, capture_output=True, text=True)
+    return run_tracked(command)
 
 
 def run_demucs_command_with_progress(
@@ -27,13 +29,22 @@ def run_demucs_command_with_progress(
     batch_id: str,
     job_id: str,
 ) -> subprocess.CompletedProcess[str]:
-    process = subprocess.Popen(  # noqa: S603
+    with tracked_popen(
         command,
         stdout=subprocess.DEVNULL,
         stderr=subprocess.PIPE,
         text=True,
-    )
+    ) as process:
+        return _stream_demucs_progress(command, process, emit, batch_id, job_id)
+
 
+def _stream_demucs_progress(
+    command: list[str],
+    process: subprocess.Popen[str],
+    emit: EmitEvent,
+    batch_id: str,
+    job_id: str,
+) -> subprocess.CompletedProcess[str]:
     stderr_pipe = process.stderr
     if stderr_pipe is None:
         return subprocess.CompletedProcess(command, 1, "", "demucs stderr stream unavailable")
@@ -87,11 +98,23 @@ def run_demucs_command_with_progress(
     return subprocess.CompletedProcess(command, return_code, "", stderr_text)
 
 
+def _emit_job_cancelled(emit: EmitEvent, batch_id: str, job_id: str) -> None:
+    emit(
+        {
+            "type": "job_cancelled",
+            "batchId": batch_id,
+            "jobId": job_id,
+        }
+    )
+
+
 def process_batch(
     command: StartBatchCommand,
     emit: EmitEvent,
     should_cancel: ShouldCancel,
     command_runner: RunCommand = run_command,
+    should_abort: ShouldCancel = lambda: False,
+    wait_if_paused: WaitIfPaused = lambda: None,
 ) -> None:
     output_dir = Path(command.output_dir)
     output_dir.mkdir(parents=True, exist_ok=True)
@@ -105,6 +128,7 @@ def process_batch(
     cancelled_count = 0
 
     for index, raw_input_path in enumerate(command.input_paths):
+        wait_if_paused()
         if should_cancel():
             cancelled_count = len(command.input_paths) - index
             break
@@ -139,6 +163,11 @@ def process_batch(
             )
             cleanup_demucs_artifacts(stem_dir, model_root_dir)
             continue
+        if should_abort():
+            cancelled_count = len(command.input_paths) - index
+            _emit_job_cancelled(emit, command.batch_id, job_id)
+            cleanup_demucs_artifacts(stem_dir, model_root_dir)
+            break
         if demucs_result.returncode != 0:
             failed_count += 1
             emit(
@@ -207,6 +236,13 @@ def process_batch(
             cleanup_demucs_artifacts(stem_dir, model_root_dir)
             continue
 
+        if should_abort():
+            cancelled_count = len(command.input_paths) - index
+            _emit_job_cancelled(emit, command.batch_id, job_id)
+            output_path.unlink(missing_ok=True)
+            cleanup_demucs_artifacts(stem_dir, model_root_dir)
+            break
+
         if ffmpeg_result.returncode != 0:
             failed_count += 1
             if ffmpeg_result.stderr:
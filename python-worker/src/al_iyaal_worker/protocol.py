@@ -7,13 +7,93 @@ from .models import (
     CancelBatchCommand,
     CancelTaskCommand,
     CutRange,
+    PauseBatchCommand,
+    PauseTaskCommand,
+    PingCommand,
+    PipelineStage,
+    PlannedCutJob,
+    ProfileRef,
+    ProvideCutPlanCommand,
+    ResumeBatchCommand,
+    ResumeTaskCommand,
     StartBatchCommand,
+    StartCutBatchCommand,
     StartCutJobCommand,
     StartFlagBatchCommand,
+    StartPipelineCommand,
     StartTranscriptionBatchCommand,
     WorkerCommand,
 )
 
+# Must match WORKER_PROTOCOL_VERSION in src-tauri/src/protocol.rs.
+PROTOCOL_VERSION = 1
+WORKER_VERSION = "0.1.0"
+SUPPORTED_COMMANDS = (
+    "start_batch",
+    "start_transcription_batch",
+    "start_flag_batch",
+    "start_cut_job",
+    "start_cut_batch",
+    "start_pipeline",
+    "cancel_batch",
+    "cancel_task",
+    "pause_batch",
+    "resume_batch",
+    "pause_task",
+    "resume_task",
+    "provide_cut_plan",
+    "ping",
+)
+SUPPORTED_FEATURES = ("cancel_immediate", "audio_redaction", "flag_profiles")
+PIPELINE_STAGES = ("remove_music", "transcribe", "flag", "cut")
+
+
+def build_hello_event() -> dict[str, Any]:
+    return {
+        "type": "hello",
+        "protocolVersion": PROTOCOL_VERSION,
+        "workerVersion": WORKER_VERSION,
+        "capabilities": [*SUPPORTED_COMMANDS, *SUPPORTED_FEATURES],
+    }
+
+
+def _parse_profile(raw_profile: Any) -> ProfileRef | None:
+    if raw_profile is None:
+        return None
+    if not isinstance(raw_profile, dict):
+        raise ValueError("profile must be an object")
+    return ProfileRef(id=str(raw_profile["id"]), name=str(raw_profile["name"]))
+
+
+def _parse_ranges(raw_ranges: Any) -> list[CutRange]:
+    if not isinstance(raw_ranges, list):
+        raise ValueError("ranges must be an array")
+
+    ranges: list[CutRange] = []
+    for item in raw_ranges:
+        if not isinstance(item, dict):
+            raise ValueError("range must be an object")
+        ranges.append(
+            CutRange(
+                start=str(item["start"]),
+                end=str(item["end"]),
+            )
+        )
+    return ranges
+
+
+def _parse_planned_cut_job(raw_job: Any) -> PlannedCutJob:
+    if not isinstance(raw_job, dict):
+        raise ValueError("job must be an object")
+
+    error = raw_job.get("error")
+    return PlannedCutJob(
+        video_path=str(raw_job["videoPath"]),
+        ranges=_parse_ranges(raw_job.get("ranges", [])),
+        removed_ranges=_parse_ranges(raw_job.get("removedRanges", [])),
+        error=str(error) if error is not None else None,
+    )
+
 
 def parse_worker_command(raw_line: str) -> WorkerCommand:
     payload = json.loads(raw_line)
@@ -43,29 +123,59 @@ def parse_worker_command(raw_line: str) -> WorkerCommand:
             task_id=str(payload["taskId"]),
             input_paths=[str(path) for path in payload["inputPaths"]],
             settings=settings,
+            profile=_parse_profile(payload.get("profile")),
         )
 
     if command_type == "start_cut_job":
-        raw_ranges = payload.get("ranges", [])
-        if not isinstance(raw_ranges, list):
-            raise ValueError("ranges must be an array")
+        return StartCutJobCommand(
+            task_id=str(payload["taskId"]),
+            video_path=str(payload["videoPath"]),
+            ranges=_parse_ranges(payload.get("ranges", [])),
+            output_mode=str(payload.get("outputMode", "video_cleaned_default")),
+        )
+
+    if command_type == "start_cut_batch":
+        raw_jobs = payload.get("jobs", [])
+        if not isinstance(raw_jobs, list):
+            raise ValueError("jobs must be an array")
+
+        jobs = [_parse_planned_cut_job(item) for item in raw_jobs]
+
+        return StartCutBatchCommand(
+            task_id=str(payload["taskId"]),
+            jobs=jobs,
+            output_mode=str(payload.get("outputMode", "video_cleaned_default")),
+        )
 
-        ranges: list[CutRange] = []
-        for item in raw_ranges:
+    if command_type == "start_pipeline":
+        raw_stages = payload.get("stages", [])
+        if not isinstance(raw_stages, list) or not raw_stages:
+            raise ValueError("stages must be a non-empty array")
+
+        stages: list[PipelineStage] = []
+        for item in raw_stages:
             if not isinstance(item, dict):
-                raise ValueError("range must be an object")
-            ranges.append(
-                CutRange(
-                    start=str(item["start"]),
-                    end=str(item["end"]),
+                raise ValueError("stage must be an object")
+            stage = str(item.get("stage"))
+            if stage not in PIPELINE_STAGES:
+                raise ValueError(f"Unsupported pipeline stage: {stage}")
+            stages.append(
+                PipelineStage(
+                    stage=stage,
+                    options={key: value for key, value in item.items() if key != "stage"},
                 )
             )
 
-        return StartCutJobCommand(
+        settings = payload.get("settings", {})
+        if not isinstance(settings, dict):
+            raise ValueError("settings must be an object")
+
+        return StartPipelineCommand(
             task_id=str(payload["taskId"]),
-            video_path=str(payload["videoPath"]),
-            ranges=ranges,
-            output_mode=str(payload.get("outputMode", "video_cleaned_default")),
+            input_paths=[str(path) for path in payload["inputPaths"]],
+            stages=stages,
+            settings=settings,
+            profile=_parse_profile(payload.get("profile")),
         )
 
     if command_type == "cancel_batch":
@@ -80,6 +190,27 @@ def parse_worker_command(raw_line: str) -> WorkerCommand:
             mode=str(payload.get("mode", "stop_after_current")),
         )
 
+    if command_type == "pause_batch":
+        return PauseBatchCommand(batch_id=str(payload["batchId"]))
+
+    if command_type == "resume_batch":
+        return ResumeBatchCommand(batch_id=str(payload["batchId"]))
+
+    if command_type == "pause_task":
+        return PauseTaskCommand(task_id=str(payload["taskId"]))
+
+    if command_type == "resume_task":
+        return ResumeTaskCommand(task_id=str(payload["taskId"]))
+
+    if command_type == "provide_cut_plan":
+        return ProvideCutPlanCommand(
+            task_id=str(payload["taskId"]),
+            job=_parse_planned_cut_job(payload["job"]),
+        )
+
+    if command_type == "ping":
+        return PingCommand(ping_id=str(payload["pingId"]))
+
     raise ValueError(f"Unsupported command type: {command_type}")
 
 
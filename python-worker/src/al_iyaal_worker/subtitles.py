@@ -5,6 +5,13 @@ from pathlib import Path
 SRT_TIME_RE = re.compile(
     r"(?P<start>\d{2}:\d{2}:\d{2},\d{3})\s*-->\s*(?P<end>\d{2}:\d{2}:\d{2},\d{3})"
 )
+VTT_TIME_RE = re.compile(
+    r"(?P<start>(?:\d+:)?\d{2}:\d{2}\.\d{3})\s*-->\s*(?P<end>(?:\d+:)?\d{2}:\d{2}\.\d{3})"
+)
+VTT_TAG_RE = re.compile(r"<[^>]*>")
+ASS_OVERRIDE_RE = re.compile(r"\{[^}]*\}")
+# Preferred order when a video has several subtitle sidecars.
+SUBTITLE_SUFFIXES = (".srt", ".vtt", ".ass", ".ssa")
 
 
 @dataclass(slots=True)
@@ -55,9 +62,161 @@ def parse_srt(content: str) -> list[SubtitleEntry]:
     return entries
 
 
+def parse_clock_timestamp(value: str) -> float:
+    """Parses `[H:]MM:SS.fff` as used by WebVTT and ASS (`H:MM:SS.cc`)."""
+    parts = value.strip().split(":")
+    seconds = float(parts[-1])
+    minutes = int(parts[-2]) if len(parts) >= 2 else 0
+    hours = int(parts[-3]) if len(parts) >= 3 else 0
+    return hours * 3600 + minutes * 60 + seconds
+
+
+def parse_vtt(content: str) -> list[SubtitleEntry]:
+    normalized = content.lstrip("\ufeff").replace("\r\n", "\n").replace("\r", "\n").strip()
+    entries: list[SubtitleEntry] = []
+    for block in normalized.split("\n\n"):
+        lines = [line for line in block.split("\n") if line.strip()]
+        timing_index = next((index for index, line in enumerate(lines) if "-->" in line), None)
+        if timing_index is None:
+            continue
+
+        time_match = VTT_TIME_RE.search(lines[timing_index])
+        if time_match is None:
+            continue
+
+        entries.append(
+            SubtitleEntry(
+                index=len(entries) + 1,
+                start_time=parse_clock_timestamp(time_match.group("start")),
+                end_time=parse_clock_timestamp(time_match.group("end")),
+                text=VTT_TAG_RE.sub("", "\n".join(lines[timing_index + 1 :])).strip(),
+            )
+        )
+
+    return entries
+
+
+def _ass_text_to_plain(text: str) -> str:
+    plain = ASS_OVERRIDE_RE.sub("", text)
+    return plain.replace("\\N", "\n").replace("\\n", "\n").replace("\\h", " ").strip()
+
+
+def parse_ass(content: str) -> list[SubtitleEntry]:
+    """Reads `Dialogue:` lines of the `[Events]` section, locating the start,
+    end and text columns through its `Format:` line."""
+    in_events = False
+    fields: list[str] = []
+    entries: list[SubtitleEntry] = []
+    for raw_line in content.lstrip("\ufeff").splitlines():
+        line = raw_line.strip()
+        if line.startswith("["):
+            in_events = line.lower() == "[events]"
+            continue
+        key, separator, value = line.partition(":")
+        if not in_events or not separator:
+            continue
+
+        if key.strip().lower() == "format":
+            fields = [field.strip().lower() for field in value.split(",")]
+            continue
+        if key.strip().lower() != "dialogue" or not {"start", "end", "text"} <= set(fields):
+            continue
+
+        values = value.split(",", len(fields) - 1)
+        if len(values) != len(fields):
+            continue
+        row = dict(zip(fields, values))
+        try:
+            start_time = parse_clock_timestamp(row["start"])
+            end_time = parse_clock_timestamp(row["end"])
+        except ValueError:
+            continue
+
+        entries.append(
+            SubtitleEntry(
+                index=len(entries) + 1,
+                start_time=start_time,
+                end_time=end_time,
+                text=_ass_text_to_plain(row["text"]),
+            )
+        )
+
+    return entries
+
+
+def parse_subtitle_file(path: Path) -> list[SubtitleEntry]:
+    content = path.read_text(encoding="utf-8-sig")
+    suffix = path.suffix.lower()
+    if suffix == ".vtt":
+        return parse_vtt(content)
+    if suffix in (".ass", ".ssa"):
+        return parse_ass(content)
+    return parse_srt(content)
+
+
+def format_srt_timestamp(seconds: float) -> str:
+    total_millis = max(0, round(seconds * 1000))
+    hours, remainder = divmod(total_millis, 3_600_000)
+    minutes, remainder = divmod(remainder, 60_000)
+    whole_seconds, millis = divmod(remainder, 1000)
+    return f"{hours:02}:{minutes:02}:{whole_seconds:02},{millis:03}"
+
+
+def serialize_srt(entries: list[SubtitleEntry]) -> str:
+    blocks = [
+        f"{entry.index}\n"
+        f"{format_srt_timestamp(entry.start_time)} --> {format_srt_timestamp(entry.end_time)}\n"
+        f"{entry.text}"
+        for entry in entries
+    ]
+    return "\n\n".join(blocks) + "\n" if blocks else ""
+
+
+def retime_subtitles(
+    entries: list[SubtitleEntry], keep_ranges: list[tuple[float, float]]
+) -> list[SubtitleEntry]:
+    """Maps cues onto a video made of `keep_ranges` played back to back: cues
+    wholly inside removed time are dropped, later cues move earlier by the
+    time removed before them, and cues straddling a cut are trimmed."""
+    retimed: list[SubtitleEntry] = []
+    for entry in entries:
+        offset = 0.0
+        start: float | None = None
+        end = 0.0
+        for keep_start, keep_end in keep_ranges:
+            overlap_start = max(entry.start_time, keep_start)
+            overlap_end = min(entry.end_time, keep_end)
+            if overlap_end > overlap_start:
+                if start is None:
+                    start = offset + overlap_start - keep_start
+                end = offset + overlap_end - keep_start
+            offset += keep_end - keep_start
+
+        if start is not None:
+            retimed.append(
+                SubtitleEntry(index=len(retimed) + 1, start_time=start, end_time=end, text=entry.text)
+            )
+    return retimed
+
+
 def sidecar_srt_path(video_path: Path) -> Path:
     return video_path.with_suffix(".srt")
 
 
+def find_subtitle_sidecar(video_path: Path) -> Path | None:
+    for suffix in SUBTITLE_SUFFIXES:
+        candidate = video_path.with_suffix(suffix)
+        if candidate.is_file():
+            return candidate
+    return None
+
+
 def sidecar_analysis_path(video_path: Path) -> Path:
     return video_path.with_suffix(".analysis.json")
+
+
+def engine_analysis_path(analysis_path: Path, engine: str) -> Path:
+    """`episode.analysis.json` -> `episode.analysis.<engine>.json`, so each
+    engine's result survives runs of the others."""
+    safe_engine = re.sub(r"[^a-z0-9_-]", "_", engine.strip().lower()) or "unknown"
+    return analysis_path.with_name(f"{analysis_path.name.removesuffix('.json')}.{safe_engine}.json")
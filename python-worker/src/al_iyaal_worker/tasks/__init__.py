@@ -1,5 +1,6 @@
-from .cut import process_cut_job
+from .cut import process_cut_batch, process_cut_job
 from .flag import process_flag_batch
+from .pipeline import process_pipeline
 from .remove_music import process_remove_music_batch
 from .transcribe import process_transcription_batch
 
@@ -8,4 +9,6 @@ __all__ = [
     "process_transcription_batch",
     "process_flag_batch",
     "process_cut_job",
+    "process_cut_batch",
+    "process_pipeline",
 ]
@@ -2,21 +2,32 @@ from collections.abc import Callable
 import os
 from pathlib import Path
 import shutil
-import subprocess
 import tempfile
 
 from ..commands import (
+    build_audio_redacted_output_path,
+    build_ffmpeg_bleep_command,
     build_ffmpeg_concat_command,
+    build_ffmpeg_mute_command,
     build_ffmpeg_slice_command,
     build_video_cleaned_output_path,
     generate_concat_file_content,
 )
 from ..filesystem import to_job_id
-from ..models import CutRange, StartCutJobCommand
+from ..models import CutRange, StartCutBatchCommand, StartCutJobCommand
+from ..processes import run_tracked
+from ..subtitles import (
+    find_subtitle_sidecar,
+    parse_subtitle_file,
+    retime_subtitles,
+    serialize_srt,
+    sidecar_srt_path,
+)
 from ..timecode import parse_time_to_seconds
 from .events import (
     emit_job_log,
     emit_task_done,
+    emit_task_job_cancelled,
     emit_task_job_done,
     emit_task_job_error,
     emit_task_job_progress,
@@ -24,6 +35,10 @@ from .events import (
 
 EmitEvent = Callable[[dict[str, object]], None]
 ShouldCancel = Callable[[], bool]
+WaitIfPaused = Callable[[], None]
+
+# Modes that keep the timeline and silence the given ranges instead of keeping them.
+AUDIO_REDACTION_MODES = ("mute", "bleep")
 
 
 def _is_valid_range(cut_range: CutRange) -> bool:
@@ -41,10 +56,86 @@ def _to_seconds(cut_range: CutRange) -> tuple[float, float]:
     return start, end
 
 
+def _write_retimed_subtitles(
+    command: StartCutJobCommand,
+    video_path: Path,
+    output_path: Path,
+    job_id: str,
+    emit: EmitEvent,
+) -> Path | None:
+    source_srt_path = find_subtitle_sidecar(video_path)
+    if source_srt_path is None:
+        return None
+
+    retimed_srt_path = sidecar_srt_path(output_path)
+    try:
+        entries = parse_subtitle_file(source_srt_path)
+        keep_ranges = [_to_seconds(cut_range) for cut_range in command.ranges]
+        retimed_srt_path.write_text(serialize_srt(retime_subtitles(entries, keep_ranges)), encoding="utf-8")
+    except Exception as error:
+        emit_job_log(
+            emit,
+            command.task_id,
+            "cut",
+            job_id,
+            f"Failed writing retimed subtitles: {error}",
+            stream="stderr",
+        )
+        return None
+
+    emit_job_log(emit, command.task_id, "cut", job_id, f"Wrote retimed subtitles to {retimed_srt_path}")
+    return retimed_srt_path
+
+
+def _process_audio_redaction(
+    command: StartCutJobCommand,
+    video_path: Path,
+    job_id: str,
+    ffmpeg_path: str,
+    emit: EmitEvent,
+    should_abort: ShouldCancel,
+) -> None:
+    task_id = command.task_id
+    output_path = build_audio_redacted_output_path(video_path, command.output_mode)
+    output_path.parent.mkdir(parents=True, exist_ok=True)
+    ranges_seconds = sorted(_to_seconds(cut_range) for cut_range in command.ranges)
+    build_command = build_ffmpeg_mute_command if command.output_mode == "mute" else build_ffmpeg_bleep_command
+
+    emit_task_job_progress(emit, task_id, "cut", job_id, 5)
+    result = run_tracked(build_command(ffmpeg_path, video_path, output_path, ranges_seconds))
+    if should_abort():
+        output_path.unlink(missing_ok=True)
+        emit_task_job_cancelled(emit, task_id, "cut", job_id)
+        emit_task_done(emit, task_id, "cut", ok=0, failed=0, cancelled=1)
+        return
+    if result.returncode != 0:
+        emit_task_job_error(
+            emit,
+            task_id,
+            "cut",
+            job_id,
+            f"ffmpeg {command.output_mode} failed: {result.stderr.strip() or f'exit {result.returncode}'}",
+        )
+        emit_task_done(emit, task_id, "cut", ok=0, failed=1, cancelled=0)
+        return
+
+    emit_job_log(
+        emit,
+        task_id,
+        "cut",
+        job_id,
+        f"Wrote {'muted' if command.output_mode == 'mute' else 'bleeped'} video to {output_path}",
+        stream="stdout",
+    )
+    emit_task_job_done(emit, task_id, "cut", job_id, output_path=str(output_path))
+    emit_task_done(emit, task_id, "cut", ok=1, failed=0, cancelled=0)
+
+
 def process_cut_job(
     command: StartCutJobCommand,
     emit: EmitEvent,
     should_cancel: ShouldCancel,
+    should_abort: ShouldCancel = lambda: False,
 ) -> None:
     ffmpeg_path = os.getenv("AIYAAL_FFMPEG_PATH", "ffmpeg")
     task_id = command.task_id
@@ -83,6 +174,10 @@ def process_cut_job(
         emit_task_done(emit, task_id, "cut", ok=0, failed=1, cancelled=0)
         return
 
+    if command.output_mode in AUDIO_REDACTION_MODES:
+        _process_audio_redaction(command, video_path, job_id, ffmpeg_path, emit, should_abort)
+        return
+
     output_path = build_video_cleaned_output_path(video_path)
     output_path.parent.mkdir(parents=True, exist_ok=True)
 
@@ -108,12 +203,11 @@ def process_cut_job(
                 start_seconds=start,
                 duration_seconds=duration,
             )
-            slice_result = subprocess.run(  # noqa: S603
-                slice_command,
-                check=False,
-                capture_output=True,
-                text=True,
-            )
+            slice_result = run_tracked(slice_command)
+            if should_abort():
+                emit_task_job_cancelled(emit, task_id, "cut", job_id)
+                emit_task_done(emit, task_id, "cut", ok=0, failed=0, cancelled=1)
+                return
             if slice_result.returncode != 0:
                 emit_task_job_error(
                     emit,
@@ -140,12 +234,12 @@ def process_cut_job(
                 concat_file_path=concat_file,
                 output_path=output_path,
             )
-            concat_result = subprocess.run(  # noqa: S603
-                concat_command,
-                check=False,
-                capture_output=True,
-                text=True,
-            )
+            concat_result = run_tracked(concat_command)
+            if should_abort():
+                output_path.unlink(missing_ok=True)
+                emit_task_job_cancelled(emit, task_id, "cut", job_id)
+                emit_task_done(emit, task_id, "cut", ok=0, failed=0, cancelled=1)
+                return
             if concat_result.returncode != 0:
                 emit_task_job_error(
                     emit,
@@ -167,13 +261,104 @@ def process_cut_job(
             f"Wrote cleaned video to {output_path}",
             stream="stdout",
         )
+        subtitle_path = _write_retimed_subtitles(command, video_path, output_path, job_id, emit)
         emit_task_job_done(
             emit,
             task_id,
             "cut",
             job_id,
             output_path=str(output_path),
+            artifacts={"subtitlePath": str(subtitle_path)} if subtitle_path is not None else None,
         )
         emit_task_done(emit, task_id, "cut", ok=1, failed=0, cancelled=0)
     finally:
         shutil.rmtree(temp_dir, ignore_errors=True)
+
+
+def process_cut_batch(
+    command: StartCutBatchCommand,
+    emit: EmitEvent,
+    should_cancel: ShouldCancel,
+    should_abort: ShouldCancel = lambda: False,
+    wait_if_paused: WaitIfPaused = lambda: None,
+) -> None:
+    """Cuts the flagged segments out of every video, using the ranges the host
+    planned from each video's `.analysis.json` sidecar."""
+    task_id = command.task_id
+    ok_count = 0
+    failed_count = 0
+    cancelled_count = 0
+
+    for index, job in enumerate(command.jobs):
+        wait_if_paused()
+        if should_cancel():
+            cancelled_count = len(command.jobs) - index
+            break
+
+        job_id = to_job_id(job.video_path)
+        emit_task_job_progress(emit, task_id, "cut", job_id, 2)
+
+        if job.error is not None:
+            failed_count += 1
+            emit_task_job_error(emit, task_id, "cut", job_id, f"Failed planning cuts: {job.error}")
+            continue
+
+        if not job.removed_ranges:
+            ok_count += 1
+            emit_job_log(emit, task_id, "cut", job_id, "No flagged segments matched. Nothing to cut.")
+            emit_task_job_done(emit, task_id, "cut", job_id, artifacts={"removedRangeCount": 0})
+            continue
+
+        redacting_audio = command.output_mode in AUDIO_REDACTION_MODES
+        if not job.ranges and not redacting_audio:
+            failed_count += 1
+            emit_task_job_error(
+                emit,
+                task_id,
+                "cut",
+                job_id,
+                "Nothing would remain after removing the flagged segments.",
+            )
+            continue
+
+        emit_job_log(
+            emit, task_id, "cut", job_id, f"Processing {len(job.removed_ranges)} flagged segment(s)."
+        )
+        outcomes: list[str] = []
+
+        def forward(payload: dict[str, object]) -> None:
+            # Each file reports its own job events; only the batch reports task_done.
+            if payload.get("type") == "task_done":
+                return
+            if payload.get("type") in ("job_done", "job_error", "job_cancelled"):
+                outcomes.append(str(payload["type"]))
+            emit(payload)
+
+        process_cut_job(
+            StartCutJobCommand(
+                task_id=task_id,
+                video_path=job.video_path,
+                ranges=job.ranges,
+                output_mode=command.output_mode,
+            ),
+            forward,
+            lambda: False,
+            should_abort,
+        )
+
+        if "job_cancelled" in outcomes or should_abort():
+            cancelled_count = len(command.jobs) - index
+            break
+        if "job_done" in outcomes:
+            ok_count += 1
+        else:
+            failed_count += 1
+
+    emit_task_done(
+        emit,
+        task_id,
+        "cut",
+        ok=ok_count,
+        failed=failed_count,
+        cancelled=cancelled_count,
+    )
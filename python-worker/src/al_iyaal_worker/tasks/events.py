@@ -59,6 +59,22 @@ def emit_task_job_error(
     )
 
 
+def emit_task_job_cancelled(
+    emit: Callable[[dict[str, Any]], None],
+    task_id: str,
+    task_kind: str,
+    job_id: str,
+) -> None:
+    emit(
+        {
+            "type": "job_cancelled",
+            "taskId": task_id,
+            "taskKind": task_kind,
+            "jobId": job_id,
+        }
+    )
+
+
 def emit_task_done(
     emit: Callable[[dict[str, Any]], None],
     task_id: str,
@@ -2,15 +2,25 @@ from collections.abc import Callable
 from datetime import datetime, timezone
 import json
 from pathlib import Path
+import shutil
 from typing import Any
 
 from ..filesystem import to_job_id
-from ..models import StartFlagBatchCommand
+from ..models import ProfileRef, StartFlagBatchCommand
 from ..moderation import analyze_subtitles, analyze_with_llm, describe_llm_request
-from ..subtitles import parse_srt, sidecar_analysis_path, sidecar_srt_path
+from ..processes import OperationAborted, run_until_aborted
+from ..subtitles import (
+    SUBTITLE_SUFFIXES,
+    engine_analysis_path,
+    find_subtitle_sidecar,
+    parse_subtitle_file,
+    sidecar_analysis_path,
+    sidecar_srt_path,
+)
 from .events import (
     emit_job_log,
     emit_task_done,
+    emit_task_job_cancelled,
     emit_task_job_done,
     emit_task_job_error,
     emit_task_job_progress,
@@ -18,36 +28,57 @@ from .events import (
 
 EmitEvent = Callable[[dict[str, object]], None]
 ShouldCancel = Callable[[], bool]
+WaitIfPaused = Callable[[], None]
+
+# Bump together with `ANALYSIS_SCHEMA_VERSION` in the Rust host.
+ANALYSIS_SCHEMA_VERSION = 1
 
 
 def _build_analysis_payload(
-    source_path: Path, engine: str, flagged: list[dict[str, Any]], summary: str
+    source_path: Path, engine: str, flagged: list[dict[str, Any]], summary: str, profile: ProfileRef | None
 ) -> dict[str, Any]:
-    return {
+    payload: dict[str, Any] = {
+        "schemaVersion": ANALYSIS_SCHEMA_VERSION,
         "engine": engine,
         "flagged": flagged,
         "summary": summary,
         "createdAt": datetime.now(tz=timezone.utc).isoformat(),
         "videoFileName": source_path.name,
     }
+    if profile is not None:
+        payload["profile"] = {"id": profile.id, "name": profile.name}
+    return payload
+
+
+def _backup_previous_analysis(analysis_path: Path) -> Path | None:
+    """Keeps the sidecar being replaced as `.analysis.json.bak`, like the host's
+    writer, so the host can carry its reviewer decisions over."""
+    if not analysis_path.exists():
+        return None
+    backup_path = analysis_path.with_name(f"{analysis_path.name}.bak")
+    shutil.copyfile(analysis_path, backup_path)
+    return backup_path
 
 
 def _resolve_sidecars(path: Path) -> tuple[Path, Path]:
-    if path.suffix.lower() == ".srt":
+    if path.suffix.lower() in SUBTITLE_SUFFIXES:
         return path, path.with_suffix(".analysis.json")
-    return sidecar_srt_path(path), sidecar_analysis_path(path)
+    return find_subtitle_sidecar(path) or sidecar_srt_path(path), sidecar_analysis_path(path)
 
 
 def process_flag_batch(
     command: StartFlagBatchCommand,
     emit: EmitEvent,
     should_cancel: ShouldCancel,
+    should_abort: ShouldCancel = lambda: False,
+    wait_if_paused: WaitIfPaused = lambda: None,
 ) -> None:
     ok_count = 0
     failed_count = 0
     cancelled_count = 0
 
     for index, raw_input_path in enumerate(command.input_paths):
+        wait_if_paused()
         if should_cancel():
             cancelled_count = len(command.input_paths) - index
             break
@@ -78,7 +109,7 @@ def process_flag_batch(
             continue
 
         try:
-            subtitles = parse_srt(srt_path.read_text(encoding="utf-8"))
+            subtitles = parse_subtitle_file(srt_path)
         except Exception as error:
             failed_count += 1
             emit_task_job_error(
@@ -112,10 +143,17 @@ def process_flag_batch(
                     job_id,
                     f"LLM request config: endpoint={request_config.endpoint} model={request_config.model}",
                 )
-                llm_result = analyze_with_llm(subtitles, command.settings, source_path.name)
+                llm_result = run_until_aborted(
+                    lambda: analyze_with_llm(subtitles, command.settings, source_path.name),
+                    should_abort,
+                )
                 flagged = llm_result.flagged
                 summary = llm_result.summary
                 analysis_engine = llm_result.engine
+        except OperationAborted:
+            cancelled_count = len(command.input_paths) - index
+            emit_task_job_cancelled(emit, command.task_id, "flag", job_id)
+            break
         except Exception as error:
             failed_count += 1
             emit_task_job_error(
@@ -136,12 +174,13 @@ def process_flag_batch(
             f"Flagged {len(flagged)} subtitle item(s).",
         )
 
-        payload = _build_analysis_payload(source_path, analysis_engine, flagged, summary)
+        payload = _build_analysis_payload(source_path, analysis_engine, flagged, summary, command.profile)
+        per_engine_path = engine_analysis_path(analysis_path, analysis_engine)
         try:
-            analysis_path.write_text(
-                json.dumps(payload, separators=(",", ":"), ensure_ascii=False),
-                encoding="utf-8",
-            )
+            content = json.dumps(payload, separators=(",", ":"), ensure_ascii=False)
+            previous_path = _backup_previous_analysis(analysis_path)
+            analysis_path.write_text(content, encoding="utf-8")
+            per_engine_path.write_text(content, encoding="utf-8")
         except Exception as error:
             failed_count += 1
             emit_task_job_error(
@@ -153,6 +192,19 @@ def process_flag_batch(
             )
             continue
 
+        if previous_path is not None:
+            emit(
+                {
+                    "type": "analysis_replaced",
+                    "taskId": command.task_id,
+                    "taskKind": "flag",
+                    "jobId": job_id,
+                    "analysisPath": str(analysis_path),
+                    "engineAnalysisPath": str(per_engine_path),
+                    "previousAnalysisPath": str(previous_path),
+                }
+            )
+
         ok_count += 1
         emit_task_job_done(
             emit,
@@ -163,6 +215,7 @@ def process_flag_batch(
             artifacts={
                 "flaggedCount": len(flagged),
                 "summary": summary,
+                "engineAnalysisPath": str(per_engine_path),
             },
         )
 
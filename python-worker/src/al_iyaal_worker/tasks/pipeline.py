@@ -0,0 +1,315 @@
+from collections.abc import Callable
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Any
+
+from ..filesystem import to_job_id
+from ..models import (
+    PipelineStage,
+    PlannedCutJob,
+    StartBatchCommand,
+    StartCutJobCommand,
+    StartFlagBatchCommand,
+    StartPipelineCommand,
+    StartTranscriptionBatchCommand,
+)
+from ..subtitles import sidecar_analysis_path
+from .cut import AUDIO_REDACTION_MODES, process_cut_job
+from .events import (
+    emit_job_log,
+    emit_task_done,
+    emit_task_job_cancelled,
+    emit_task_job_done,
+    emit_task_job_error,
+    emit_task_job_progress,
+)
+from .flag import process_flag_batch
+from .remove_music import process_remove_music_batch
+from .transcribe import process_transcription_batch
+
+EmitEvent = Callable[[dict[str, object]], None]
+ShouldCancel = Callable[[], bool]
+WaitIfPaused = Callable[[], None]
+# Asks the host to plan a cut of (video, analysis sidecar); None when the task was aborted first.
+RequestCutPlan = Callable[[Path, Path], PlannedCutJob | None]
+
+TASK_KIND = "pipeline"
+
+
+@dataclass(slots=True)
+class StageOutcome:
+    output_path: str | None = None
+    artifacts: dict[str, Any] | None = None
+    error: str | None = None
+    cancelled: bool = False
+
+
+class _StageRelay:
+    """Receives a single-file run of an existing task and re-emits its progress
+    and logs as activity of the pipeline job."""
+
+    def __init__(
+        self,
+        emit: EmitEvent,
+        task_id: str,
+        job_id: str,
+        stage: str,
+        stage_index: int,
+        stage_count: int,
+    ):
+        self._emit = emit
+        self._task_id = task_id
+        self._job_id = job_id
+        self._stage = stage
+        self._stage_index = stage_index
+        self._stage_count = stage_count
+        self.outcome = StageOutcome()
+
+    def __call__(self, payload: dict[str, Any]) -> None:
+        event_type = payload.get("type")
+        if event_type == "job_progress":
+            stage_pct = int(payload.get("progressPct", 0))
+            overall = (self._stage_index * 100 + stage_pct) // self._stage_count
+            emit_task_job_progress(self._emit, self._task_id, TASK_KIND, self._job_id, overall)
+        elif event_type == "job_log":
+            emit_job_log(
+                self._emit,
+                self._task_id,
+                TASK_KIND,
+                self._job_id,
+                f"[{self._stage}] {payload.get('message', '')}",
+                stream=str(payload.get("stream", "stdout")),
+            )
+        elif event_type == "job_done":
+            output_path = payload.get("outputPath")
+            self.outcome.output_path = str(output_path) if output_path is not None else None
+            artifacts = payload.get("artifacts")
+            self.outcome.artifacts = artifacts if isinstance(artifacts, dict) else None
+        elif event_type == "job_error":
+            self.outcome.error = str(payload.get("error", "unknown error"))
+        elif event_type == "job_cancelled":
+            self.outcome.cancelled = True
+        elif event_type == "analysis_replaced":
+            # The host carries reviewer decisions over from the replaced sidecar.
+            self._emit({**payload, "taskKind": TASK_KIND, "jobId": self._job_id})
+
+
+def _emit_stage(emit: EmitEvent, task_id: str, job_id: str, stage: str) -> None:
+    emit(
+        {
+            "type": "job_stage",
+            "taskId": task_id,
+            "taskKind": TASK_KIND,
+            "jobId": job_id,
+            "stage": stage,
+        }
+    )
+
+
+def _find_analysis_path(current_video: Path, original_video: Path) -> Path | None:
+    for candidate in (sidecar_analysis_path(current_video), sidecar_analysis_path(original_video)):
+        if candidate.exists():
+            return candidate
+    return None
+
+
+def _run_cut_stage(
+    command: StartPipelineCommand,
+    stage: PipelineStage,
+    relay: _StageRelay,
+    current_video: Path,
+    original_video: Path,
+    analysis_path: Path | None,
+    should_abort: ShouldCancel,
+    request_cut_plan: RequestCutPlan,
+) -> None:
+    analysis_path = analysis_path or _find_analysis_path(current_video, original_video)
+    if analysis_path is None:
+        relay.outcome.error = f"Missing analysis sidecar. Run flagging first: {sidecar_analysis_path(current_video)}"
+        return
+
+    job = request_cut_plan(current_video, analysis_path)
+    if job is None:
+        relay.outcome.cancelled = True
+        return
+    if job.error is not None:
+        relay.outcome.error = f"Failed planning cuts: {job.error}"
+        return
+
+    if not job.removed_ranges:
+        relay({"type": "job_log", "message": "No flagged segments matched. Keeping the video as is."})
+        relay.outcome.output_path = str(current_video)
+        return
+
+    output_mode = str(stage.options.get("outputMode", "video_cleaned_default"))
+    if not job.ranges and output_mode not in AUDIO_REDACTION_MODES:
+        relay.outcome.error = "Nothing would remain after removing the flagged segments."
+        return
+
+    process_cut_job(
+        StartCutJobCommand(
+            task_id=command.task_id,
+            video_path=str(current_video),
+            ranges=job.ranges,
+            output_mode=output_mode,
+        ),
+        relay,
+        lambda: False,
+        should_abort,
+    )
+
+
+def _run_stage(
+    command: StartPipelineCommand,
+    stage: PipelineStage,
+    relay: _StageRelay,
+    current_video: Path,
+    original_video: Path,
+    analysis_path: Path | None,
+    should_abort: ShouldCancel,
+    request_cut_plan: RequestCutPlan,
+) -> None:
+    # Pipelines stop between files, so the per-stage runs never see a cancel request.
+    never_cancel: ShouldCancel = lambda: False
+    if stage.stage == "remove_music":
+        process_remove_music_batch(
+            StartBatchCommand(
+                batch_id=command.task_id,
+                input_paths=[str(current_video)],
+                output_dir=str(current_video.parent / "audio_replaced"),
+                compute_mode=str(stage.options.get("computeMode", "auto")),
+            ),
+            relay,
+            never_cancel,
+            should_abort=should_abort,
+        )
+    elif stage.stage == "transcribe":
+        process_transcription_batch(
+            StartTranscriptionBatchCommand(
+                task_id=command.task_id,
+                input_paths=[str(current_video)],
+                yap_mode=str(stage.options.get("yapMode", "auto")),
+            ),
+            relay,
+            never_cancel,
+            should_abort,
+        )
+    elif stage.stage == "flag":
+        process_flag_batch(
+            StartFlagBatchCommand(
+                task_id=command.task_id,
+                input_paths=[str(current_video)],
+                settings=command.settings,
+                profile=command.profile,
+            ),
+            relay,
+            never_cancel,
+            should_abort,
+        )
+    elif stage.stage == "cut":
+        _run_cut_stage(
+            command,
+            stage,
+            relay,
+            current_video,
+            original_video,
+            analysis_path,
+            should_abort,
+            request_cut_plan,
+        )
+    else:
+        relay.outcome.error = f"Unsupported pipeline stage: {stage.stage}"
+
+
+def process_pipeline(
+    command: StartPipelineCommand,
+    emit: EmitEvent,
+    should_cancel: ShouldCancel,
+    should_abort: ShouldCancel = lambda: False,
+    wait_if_paused: WaitIfPaused = lambda: None,
+    request_cut_plan: RequestCutPlan = lambda video, analysis: None,
+) -> None:
+    ok_count = 0
+    failed_count = 0
+    cancelled_count = 0
+    stage_count = len(command.stages)
+
+    for index, raw_input_path in enumerate(command.input_paths):
+        wait_if_paused()
+        if should_cancel():
+            cancelled_count = len(command.input_paths) - index
+            break
+
+        job_id = to_job_id(raw_input_path)
+        original_video = Path(raw_input_path)
+        current_video = original_video
+        analysis_path: Path | None = None
+        stage_outputs: dict[str, str] = {}
+        artifacts: dict[str, Any] = {}
+        failure: str | None = None
+        aborted = False
+
+        for stage_index, stage in enumerate(command.stages):
+            _emit_stage(emit, command.task_id, job_id, stage.stage)
+            relay = _StageRelay(emit, command.task_id, job_id, stage.stage, stage_index, stage_count)
+            _run_stage(
+                command,
+                stage,
+                relay,
+                current_video,
+                original_video,
+                analysis_path,
+                should_abort,
+                request_cut_plan,
+            )
+            outcome = relay.outcome
+
+            if outcome.cancelled or should_abort():
+                aborted = True
+                break
+            if outcome.error is not None:
+                failure = f"{stage.stage} stage failed: {outcome.error}"
+                break
+            if outcome.output_path is None:
+                failure = f"{stage.stage} stage finished without an output."
+                break
+
+            stage_outputs[stage.stage] = outcome.output_path
+            if stage.stage in ("remove_music", "cut"):
+                current_video = Path(outcome.output_path)
+                if outcome.artifacts is not None and "subtitlePath" in outcome.artifacts:
+                    artifacts["subtitlePath"] = outcome.artifacts["subtitlePath"]
+            elif stage.stage == "flag":
+                analysis_path = Path(outcome.output_path)
+                if outcome.artifacts is not None and "flaggedCount" in outcome.artifacts:
+                    artifacts["flaggedCount"] = outcome.artifacts["flaggedCount"]
+
+        if aborted:
+            cancelled_count = len(command.input_paths) - index
+            emit_task_job_cancelled(emit, command.task_id, TASK_KIND, job_id)
+            break
+
+        if failure is not None:
+            failed_count += 1
+            emit_task_job_error(emit, command.task_id, TASK_KIND, job_id, failure)
+            continue
+
+        ok_count += 1
+        artifacts["stageOutputs"] = stage_outputs
+        emit_task_job_done(
+            emit,
+            command.task_id,
+            TASK_KIND,
+            job_id,
+            output_path=str(current_video),
+            artifacts=artifacts,
+        )
+
+    emit_task_done(
+        emit,
+        command.task_id,
+        TASK_KIND,
+        ok=ok_count,
+        failed=failed_count,
+        cancelled=cancelled_count,
+    )
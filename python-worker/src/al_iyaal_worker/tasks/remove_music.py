@@ -6,6 +6,7 @@ from ..processor import process_batch, run_command
 
 EmitEvent = Callable[[dict[str, object]], None]
 ShouldCancel = Callable[[], bool]
+WaitIfPaused = Callable[[], None]
 RunCommand = Callable[[list[str]], subprocess.CompletedProcess[str]]
 
 
@@ -14,10 +15,14 @@ def process_remove_music_batch(
     emit: EmitEvent,
     should_cancel: ShouldCancel,
     command_runner: RunCommand | None = None,
+    should_abort: ShouldCancel = lambda: False,
+    wait_if_paused: WaitIfPaused = lambda: None,
 ) -> None:
     process_batch(
         command=command,
         emit=emit,
         should_cancel=should_cancel,
         command_runner=command_runner or run_command,
+        should_abort=should_abort,
+        wait_if_paused=wait_if_paused,
     )
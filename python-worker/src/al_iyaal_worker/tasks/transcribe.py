@@ -1,16 +1,20 @@
 from collections.abc import Callable
+from contextlib import ExitStack
 import os
 from pathlib import Path
 import re
 import subprocess
+import time
 
 from ..commands import build_transcribe_command
 from ..filesystem import to_job_id
 from ..models import StartTranscriptionBatchCommand
+from ..processes import tracked_popen
 from ..subtitles import sidecar_srt_path
 from .events import (
     emit_job_log,
     emit_task_done,
+    emit_task_job_cancelled,
     emit_task_job_done,
     emit_task_job_error,
     emit_task_job_progress,
@@ -18,6 +22,7 @@ from .events import (
 
 EmitEvent = Callable[[dict[str, object]], None]
 ShouldCancel = Callable[[], bool]
+WaitIfPaused = Callable[[], None]
 
 ANSI_RE = re.compile(r"\x1B\[[0-9;]*[a-zA-Z]")
 YAP_PROGRESS_RE = re.compile(r"\[\s*(\d+)%\s*\]\s*(.+)")
@@ -49,10 +54,47 @@ def _sanitize_command_preview(transcribe_command: list[str], video_path: Path, s
     return command_preview
 
 
+def _stream_transcription_output(
+    process: subprocess.Popen[str],
+    emit: EmitEvent,
+    task_id: str,
+    job_id: str,
+) -> None:
+    max_progress = 3
+    stdout_pipe = process.stdout
+    if stdout_pipe is not None:
+        for line in stdout_pipe:
+            stripped_line = line.strip()
+            if stripped_line:
+                emit_job_log(
+                    emit,
+                    task_id,
+                    "transcription",
+                    job_id,
+                    stripped_line,
+                )
+            progress = parse_yap_progress_line(line)
+            if progress is None:
+                continue
+            percent, _message = progress
+            mapped_progress = 3 + int(percent * 0.9)
+            if mapped_progress > max_progress:
+                max_progress = mapped_progress
+                emit_task_job_progress(
+                    emit,
+                    task_id,
+                    "transcription",
+                    job_id,
+                    mapped_progress,
+                )
+
+
 def process_transcription_batch(
     command: StartTranscriptionBatchCommand,
     emit: EmitEvent,
     should_cancel: ShouldCancel,
+    should_abort: ShouldCancel = lambda: False,
+    wait_if_paused: WaitIfPaused = lambda: None,
 ) -> None:
     yap_path = os.getenv("AIYAAL_YAP_PATH", "yap")
     ok_count = 0
@@ -60,6 +102,7 @@ def process_transcription_batch(
     cancelled_count = 0
 
     for index, raw_video_path in enumerate(command.input_paths):
+        wait_if_paused()
         if should_cancel():
             cancelled_count = len(command.input_paths) - index
             break
@@ -90,54 +133,39 @@ def process_transcription_batch(
             f"Command: {command_preview}",
         )
 
-        try:
-            process = subprocess.Popen(  # noqa: S603
-                transcribe_command,
-                stdout=subprocess.PIPE,
-                stderr=subprocess.STDOUT,
-                text=True,
-                env={**os.environ, "NSUnbufferedIO": "YES"},
-            )
-        except Exception as error:
-            failed_count += 1
-            emit_task_job_error(
-                emit,
-                command.task_id,
-                "transcription",
-                job_id,
-                f"Failed to start transcription command: {error}",
-            )
-            continue
-
-        max_progress = 3
-        stdout_pipe = process.stdout
-        if stdout_pipe is not None:
-            for line in stdout_pipe:
-                stripped_line = line.strip()
-                if stripped_line:
-                    emit_job_log(
-                        emit,
-                        command.task_id,
-                        "transcription",
-                        job_id,
-                        stripped_line,
-                    )
-                progress = parse_yap_progress_line(line)
-                if progress is None:
-                    continue
-                percent, _message = progress
-                mapped_progress = 3 + int(percent * 0.9)
-                if mapped_progress > max_progress:
-                    max_progress = mapped_progress
-                    emit_task_job_progress(
-                        emit,
-                        command.task_id,
-                        "transcription",
-                        job_id,
-                        mapped_progress,
+        started_at = time.time()
+        with ExitStack() as process_scope:
+            try:
+                process = process_scope.enter_context(
+                    tracked_popen(
+                        transcribe_command,
+                        stdout=subprocess.PIPE,
+                        stderr=subprocess.STDOUT,
+                        text=True,
+                        env={**os.environ, "NSUnbufferedIO": "YES"},
                     )
+                )
+            except Exception as error:
+                failed_count += 1
+                emit_task_job_error(
+                    emit,
+                    command.task_id,
+                    "transcription",
+                    job_id,
+                    f"Failed to start transcription command: {error}",
+                )
+                continue
+
+            _stream_transcription_output(process, emit, command.task_id, job_id)
+            return_code = process.wait()
+
+        if should_abort():
+            cancelled_count = len(command.input_paths) - index
+            if srt_path.exists() and srt_path.stat().st_mtime >= started_at:
+                srt_path.unlink(missing_ok=True)
+            emit_task_job_cancelled(emit, command.task_id, "transcription", job_id)
+            break
 
-        return_code = process.wait()
         emit_job_log(
             emit,
             command.task_id,
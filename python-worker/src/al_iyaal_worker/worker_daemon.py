@@ -1,20 +1,35 @@
+import queue
 import sys
 import threading
+from collections.abc import Callable
 from dataclasses import dataclass
+from pathlib import Path
 from typing import TextIO
 
 from .models import (
     CancelBatchCommand,
     CancelTaskCommand,
+    PauseBatchCommand,
+    PauseTaskCommand,
+    PingCommand,
+    PlannedCutJob,
+    ProvideCutPlanCommand,
+    ResumeBatchCommand,
+    ResumeTaskCommand,
     StartBatchCommand,
+    StartCutBatchCommand,
     StartCutJobCommand,
     StartFlagBatchCommand,
+    StartPipelineCommand,
     StartTranscriptionBatchCommand,
 )
-from .protocol import emit_event, emit_worker_status, parse_worker_command
+from .processes import terminate_active_processes
+from .protocol import build_hello_event, emit_event, emit_worker_status, parse_worker_command
 from .tasks import (
+    process_cut_batch,
     process_cut_job,
     process_flag_batch,
+    process_pipeline,
     process_remove_music_batch,
     process_transcription_batch,
 )
@@ -33,6 +48,10 @@ class WorkerDaemon:
         self._active_operation_lock = threading.Lock()
         self._active_operation_id: str | None = None
         self._cancel_events: dict[str, threading.Event] = {}
+        self._abort_events: dict[str, threading.Event] = {}
+        # Set while the operation may run; cleared while it is paused.
+        self._resume_events: dict[str, threading.Event] = {}
+        self._cut_plan_replies: dict[str, queue.Queue[PlannedCutJob]] = {}
 
     def _emit(self, payload: dict[str, object]) -> None:
         emit_event(payload, self._context.output_stream, self._context.output_lock)
@@ -57,12 +76,38 @@ class WorkerDaemon:
 
             self._active_operation_id = operation_id
             self._cancel_events[operation_id] = threading.Event()
+            self._abort_events[operation_id] = threading.Event()
+            self._resume_events[operation_id] = threading.Event()
+            self._resume_events[operation_id].set()
             return True
 
     def _release_operation(self, operation_id: str) -> None:
         with self._active_operation_lock:
             self._active_operation_id = None
             self._cancel_events.pop(operation_id, None)
+            self._abort_events.pop(operation_id, None)
+            self._resume_events.pop(operation_id, None)
+
+    def _pause_gate(
+        self,
+        operation_id: str,
+        operation_kind: str,
+        scope: dict[str, object],
+    ) -> Callable[[], None]:
+        """Tasks call `wait_if_paused` before each queued job, so a paused
+        operation parks there until it is resumed or cancelled."""
+        cancel_event = self._cancel_events[operation_id]
+        resume_event = self._resume_events[operation_id]
+
+        def wait_if_paused() -> None:
+            if resume_event.is_set() or cancel_event.is_set():
+                return
+            self._emit({"type": f"{operation_kind}_paused", **scope})
+            resume_event.wait()
+            if not cancel_event.is_set():
+                self._emit({"type": f"{operation_kind}_resumed", **scope})
+
+        return wait_if_paused
 
     def _start_batch(self, command: StartBatchCommand) -> None:
         if not self._reserve_operation(
@@ -150,14 +195,100 @@ class WorkerDaemon:
         )
         thread.start()
 
+    def _start_cut_batch(self, command: StartCutBatchCommand) -> None:
+        if not self._reserve_operation(
+            operation_id=command.task_id,
+            conflict_summary={
+                "type": "task_done",
+                "taskId": command.task_id,
+                "taskKind": "cut",
+                "summary": {"ok": 0, "failed": 0, "cancelled": len(command.jobs)},
+            },
+        ):
+            return
+
+        self._emit_status(
+            "starting",
+            f"Running cut task {command.task_id} with {len(command.jobs)} file(s).",
+        )
+        thread = threading.Thread(
+            target=self._run_cut_batch,
+            args=(command,),
+            daemon=False,
+            name=f"cut-{command.task_id}",
+        )
+        thread.start()
+
+    def _start_pipeline(self, command: StartPipelineCommand) -> None:
+        if not self._reserve_operation(
+            operation_id=command.task_id,
+            conflict_summary={
+                "type": "task_done",
+                "taskId": command.task_id,
+                "taskKind": "pipeline",
+                "summary": {"ok": 0, "failed": 0, "cancelled": len(command.input_paths)},
+            },
+        ):
+            return
+
+        stage_names = " -> ".join(stage.stage for stage in command.stages)
+        self._emit_status("starting", f"Running pipeline task {command.task_id}: {stage_names}.")
+        thread = threading.Thread(
+            target=self._run_pipeline,
+            args=(command,),
+            daemon=False,
+            name=f"pipeline-{command.task_id}",
+        )
+        thread.start()
+
+    def _request_cut_plan(self, task_id: str, video_path: Path, analysis_path: Path) -> PlannedCutJob | None:
+        """The host owns cut planning, so a pipeline's cut stage asks it for the
+        ranges and waits for `provide_cut_plan`, or returns None once aborted."""
+        replies: queue.Queue[PlannedCutJob] = queue.Queue(maxsize=1)
+        abort_event = self._abort_events[task_id]
+        with self._active_operation_lock:
+            self._cut_plan_replies[task_id] = replies
+        try:
+            self._emit(
+                {
+                    "type": "cut_plan_needed",
+                    "taskId": task_id,
+                    "videoPath": str(video_path),
+                    "analysisPath": str(analysis_path),
+                }
+            )
+            while not abort_event.is_set():
+                try:
+                    return replies.get(timeout=0.2)
+                except queue.Empty:
+                    continue
+            return None
+        finally:
+            with self._active_operation_lock:
+                self._cut_plan_replies.pop(task_id, None)
+
+    def _provide_cut_plan(self, command: ProvideCutPlanCommand) -> None:
+        with self._active_operation_lock:
+            replies = self._cut_plan_replies.get(command.task_id)
+
+        if replies is None:
+            self._emit_status("error", f"No cut plan was requested by task {command.task_id}.")
+            return
+
+        replies.put(command.job)
+
     def _run_batch(self, command: StartBatchCommand) -> None:
+        wait_if_paused = self._pause_gate(command.batch_id, "batch", {"batchId": command.batch_id})
         cancel_event = self._cancel_events[command.batch_id]
+        abort_event = self._abort_events[command.batch_id]
 
         try:
             process_remove_music_batch(
                 command=command,
                 emit=self._emit,
                 should_cancel=cancel_event.is_set,
+                should_abort=abort_event.is_set,
+                wait_if_paused=wait_if_paused,
             )
         except Exception as error:
             self._emit(
@@ -173,12 +304,20 @@ class WorkerDaemon:
             self._emit_status("ready", "Worker ready for next batch.")
 
     def _run_transcription_batch(self, command: StartTranscriptionBatchCommand) -> None:
+        wait_if_paused = self._pause_gate(
+            command.task_id,
+            "task",
+            {"taskId": command.task_id, "taskKind": "transcription"},
+        )
         cancel_event = self._cancel_events[command.task_id]
+        abort_event = self._abort_events[command.task_id]
         try:
             process_transcription_batch(
                 command=command,
                 emit=self._emit,
                 should_cancel=cancel_event.is_set,
+                should_abort=abort_event.is_set,
+                wait_if_paused=wait_if_paused,
             )
         except Exception as error:
             self._emit(
@@ -195,12 +334,20 @@ class WorkerDaemon:
             self._emit_status("ready", "Worker ready for next batch.")
 
     def _run_flag_batch(self, command: StartFlagBatchCommand) -> None:
+        wait_if_paused = self._pause_gate(
+            command.task_id,
+            "task",
+            {"taskId": command.task_id, "taskKind": "flag"},
+        )
         cancel_event = self._cancel_events[command.task_id]
+        abort_event = self._abort_events[command.task_id]
         try:
             process_flag_batch(
                 command=command,
                 emit=self._emit,
                 should_cancel=cancel_event.is_set,
+                should_abort=abort_event.is_set,
+                wait_if_paused=wait_if_paused,
             )
         except Exception as error:
             self._emit(
@@ -218,11 +365,13 @@ class WorkerDaemon:
 
     def _run_cut_job(self, command: StartCutJobCommand) -> None:
         cancel_event = self._cancel_events[command.task_id]
+        abort_event = self._abort_events[command.task_id]
         try:
             process_cut_job(
                 command=command,
                 emit=self._emit,
                 should_cancel=cancel_event.is_set,
+                should_abort=abort_event.is_set,
             )
         except Exception as error:
             self._emit(
@@ -238,29 +387,134 @@ class WorkerDaemon:
             self._release_operation(command.task_id)
             self._emit_status("ready", "Worker ready for next batch.")
 
+    def _run_cut_batch(self, command: StartCutBatchCommand) -> None:
+        wait_if_paused = self._pause_gate(
+            command.task_id,
+            "task",
+            {"taskId": command.task_id, "taskKind": "cut"},
+        )
+        cancel_event = self._cancel_events[command.task_id]
+        abort_event = self._abort_events[command.task_id]
+        try:
+            process_cut_batch(
+                command=command,
+                emit=self._emit,
+                should_cancel=cancel_event.is_set,
+                should_abort=abort_event.is_set,
+                wait_if_paused=wait_if_paused,
+            )
+        except Exception as error:
+            self._emit(
+                {
+                    "type": "task_done",
+                    "taskId": command.task_id,
+                    "taskKind": "cut",
+                    "summary": {"ok": 0, "failed": 1, "cancelled": 0},
+                }
+            )
+            self._emit_status("error", f"Unhandled worker failure: {error}")
+        finally:
+            self._release_operation(command.task_id)
+            self._emit_status("ready", "Worker ready for next batch.")
+
+    def _run_pipeline(self, command: StartPipelineCommand) -> None:
+        wait_if_paused = self._pause_gate(
+            command.task_id,
+            "task",
+            {"taskId": command.task_id, "taskKind": "pipeline"},
+        )
+        cancel_event = self._cancel_events[command.task_id]
+        abort_event = self._abort_events[command.task_id]
+        try:
+            process_pipeline(
+                command=command,
+                emit=self._emit,
+                should_cancel=cancel_event.is_set,
+                should_abort=abort_event.is_set,
+                wait_if_paused=wait_if_paused,
+                request_cut_plan=lambda video, analysis: self._request_cut_plan(
+                    command.task_id, video, analysis
+                ),
+            )
+        except Exception as error:
+            self._emit(
+                {
+                    "type": "task_done",
+                    "taskId": command.task_id,
+                    "taskKind": "pipeline",
+                    "summary": {"ok": 0, "failed": 1, "cancelled": 0},
+                }
+            )
+            self._emit_status("error", f"Unhandled worker failure: {error}")
+        finally:
+            self._release_operation(command.task_id)
+            self._emit_status("ready", "Worker ready for next batch.")
+
     def _cancel_batch(self, command: CancelBatchCommand) -> None:
         with self._active_operation_lock:
             cancel_event = self._cancel_events.get(command.batch_id)
+            abort_event = self._abort_events.get(command.batch_id)
+            resume_event = self._resume_events.get(command.batch_id)
 
-        if cancel_event is None:
+        if cancel_event is None or abort_event is None or resume_event is None:
             self._emit_status("error", f"No active batch found for cancel request: {command.batch_id}.")
             return
 
         cancel_event.set()
+        resume_event.set()
+        if command.mode == "immediate":
+            abort_event.set()
+            terminate_active_processes()
+            self._emit_status("starting", f"Immediate cancellation requested for batch {command.batch_id}.")
+            return
+
         self._emit_status("starting", f"Cancellation requested for batch {command.batch_id}.")
 
     def _cancel_task(self, command: CancelTaskCommand) -> None:
         with self._active_operation_lock:
             cancel_event = self._cancel_events.get(command.task_id)
+            abort_event = self._abort_events.get(command.task_id)
+            resume_event = self._resume_events.get(command.task_id)
 
-        if cancel_event is None:
+        if cancel_event is None or abort_event is None or resume_event is None:
             self._emit_status("error", f"No active task found for cancel request: {command.task_id}.")
             return
 
         cancel_event.set()
+        resume_event.set()
+        if command.mode == "immediate":
+            abort_event.set()
+            terminate_active_processes()
+            self._emit_status("starting", f"Immediate cancellation requested for task {command.task_id}.")
+            return
+
         self._emit_status("starting", f"Cancellation requested for task {command.task_id}.")
 
+    def _set_paused(self, operation_id: str, operation_label: str, paused: bool) -> None:
+        with self._active_operation_lock:
+            resume_event = self._resume_events.get(operation_id)
+
+        request = "pause" if paused else "resume"
+        if resume_event is None:
+            self._emit_status("error", f"No active {operation_label} found for {request} request: {operation_id}.")
+            return
+
+        if paused:
+            resume_event.clear()
+            self._emit_status(
+                "starting",
+                f"Pause requested for {operation_label} {operation_id}. It will pause after the current file.",
+            )
+            return
+
+        resume_event.set()
+        self._emit_status("starting", f"Resuming {operation_label} {operation_id}.")
+
+    def _pong(self, command: PingCommand) -> None:
+        self._emit({"type": "pong", "pingId": command.ping_id})
+
     def run(self) -> None:
+        self._emit(build_hello_event())
         self._emit_status("ready", "Worker booted and ready.")
         for raw_line in self._input_stream:
             line = raw_line.strip()
@@ -289,12 +543,44 @@ class WorkerDaemon:
                 self._start_cut_job(command)
                 continue
 
+            if isinstance(command, StartCutBatchCommand):
+                self._start_cut_batch(command)
+                continue
+
+            if isinstance(command, StartPipelineCommand):
+                self._start_pipeline(command)
+                continue
+
             if isinstance(command, CancelBatchCommand):
                 self._cancel_batch(command)
                 continue
 
             if isinstance(command, CancelTaskCommand):
                 self._cancel_task(command)
+                continue
+
+            if isinstance(command, PauseBatchCommand):
+                self._set_paused(command.batch_id, "batch", paused=True)
+                continue
+
+            if isinstance(command, ResumeBatchCommand):
+                self._set_paused(command.batch_id, "batch", paused=False)
+                continue
+
+            if isinstance(command, PauseTaskCommand):
+                self._set_paused(command.task_id, "task", paused=True)
+                continue
+
+            if isinstance(command, ResumeTaskCommand):
+                self._set_paused(command.task_id, "task", paused=False)
+                continue
+
+            if isinstance(command, ProvideCutPlanCommand):
+                self._provide_cut_plan(command)
+                continue
+
+            if isinstance(command, PingCommand):
+                self._pong(command)
 
 
 def run_worker(input_stream: TextIO = sys.stdin, output_stream: TextIO = sys.stdout) -> None:
@@ -0,0 +1,43 @@
+import json
+from pathlib import Path
+from typing import Any
+
+import pytest
+
+from al_iyaal_worker.moderation import analyze_subtitles
+from al_iyaal_worker.subtitles import SubtitleEntry
+
+FIXTURES_DIR = Path(__file__).parent / "fixtures"
+
+
+def _analyze_fixture(fixture: dict[str, Any]) -> tuple[list[dict[str, Any]], str]:
+    subtitles = [
+        SubtitleEntry(index=cue["index"], start_time=cue["startTime"], end_time=cue["endTime"], text=cue["text"])
+        for cue in fixture["cues"]
+    ]
+    return analyze_subtitles(subtitles, fixture["settings"])
+
+
+def test_should_flag_the_shared_fixtures_exactly_like_the_app() -> None:
+    # The app's native blacklist engine checks the same fixtures (`blacklist.rs`).
+    for name in ("blacklist_parity.json", "blacklist_parity_tashkeel.json"):
+        fixture = json.loads((FIXTURES_DIR / name).read_text())
+
+        flagged, summary = _analyze_fixture(fixture)
+
+        assert flagged == fixture["flagged"], name
+        assert summary == fixture["summary"], name
+
+
+def test_should_match_the_word_list_fixture_exactly_like_the_app() -> None:
+    better_profanity = pytest.importorskip("better_profanity")
+    fixture = json.loads((FIXTURES_DIR / "blacklist_parity_word_list.json").read_text())
+
+    better_profanity.profanity.load_censor_words(fixture["wordList"])
+    try:
+        flagged, summary = _analyze_fixture(fixture)
+    finally:
+        better_profanity.profanity.load_censor_words()
+
+    assert flagged == fixture["flagged"]
+    assert summary == fixture["summary"]
@@ -1,7 +1,10 @@
 from pathlib import Path
 
 from al_iyaal_worker.commands import (
+    build_audio_redacted_output_path,
+    build_ffmpeg_bleep_command,
     build_ffmpeg_concat_command,
+    build_ffmpeg_mute_command,
     build_demucs_command,
     build_ffmpeg_command,
     build_ffmpeg_slice_command,
@@ -145,3 +148,40 @@ def test_should_build_video_cleaned_output_path(tmp_path: Path) -> None:
     output_path = build_video_cleaned_output_path(video_path)
     assert output_path == tmp_path / "video_cleaned" / "clip.mov"
     assert not output_path.parent.exists()
+
+
+def test_should_build_ffmpeg_mute_command_for_every_range() -> None:
+    command = build_ffmpeg_mute_command(
+        ffmpeg_path="ffmpeg",
+        video_path=Path("/tmp/a.mp4"),
+        output_path=Path("/tmp/video_muted/a.mp4"),
+        ranges_seconds=[(1.0, 2.5), (10.0, 11.0)],
+    )
+
+    assert command[command.index("-af") + 1] == (
+        "volume=enable='between(t,1.000,2.500)+between(t,10.000,11.000)':volume=0"
+    )
+    assert command[command.index("-c:v") + 1] == "copy"
+    assert command[-1] == "/tmp/video_muted/a.mp4"
+
+
+def test_should_build_ffmpeg_bleep_command_with_a_tone_input() -> None:
+    command = build_ffmpeg_bleep_command(
+        ffmpeg_path="ffmpeg",
+        video_path=Path("/tmp/a.mp4"),
+        output_path=Path("/tmp/video_bleeped/a.mp4"),
+        ranges_seconds=[(1.0, 2.0)],
+    )
+
+    assert "sine=frequency=1000:sample_rate=48000" in command
+    filter_graph = command[command.index("-filter_complex") + 1]
+    assert "[0:a]volume=enable='between(t,1.000,2.000)':volume=0[speech]" in filter_graph
+    assert "not(between(t,1.000,2.000))" in filter_graph
+    assert command[-1] == "/tmp/video_bleeped/a.mp4"
+
+
+def test_should_write_audio_redactions_to_their_own_folders() -> None:
+    video_path = Path("/tmp/clip.mov")
+
+    assert build_audio_redacted_output_path(video_path, "mute") == Path("/tmp/video_muted/clip.mov")
+    assert build_audio_redacted_output_path(video_path, "bleep") == Path("/tmp/video_bleeped/clip.mov")
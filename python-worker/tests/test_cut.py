@@ -1,8 +1,13 @@
 from pathlib import Path
 import subprocess
 
-from al_iyaal_worker.models import CutRange, StartCutJobCommand
-from al_iyaal_worker.tasks.cut import process_cut_job
+from al_iyaal_worker.models import (
+    CutRange,
+    PlannedCutJob,
+    StartCutBatchCommand,
+    StartCutJobCommand,
+)
+from al_iyaal_worker.tasks.cut import process_cut_batch, process_cut_job
 
 
 def test_should_clean_temp_artifacts_after_cut_completion(
@@ -16,14 +21,14 @@ def test_should_clean_temp_artifacts_after_cut_completion(
         temp_dir.mkdir(parents=True, exist_ok=True)
         return str(temp_dir)
 
-    def fake_run(command: list[str], check: bool, capture_output: bool, text: bool):
+    def fake_run(command: list[str]):
         output_path = Path(command[-1])
         output_path.parent.mkdir(parents=True, exist_ok=True)
         output_path.write_text("slice")
         return subprocess.CompletedProcess(command, returncode=0, stdout="", stderr="")
 
     monkeypatch.setattr("al_iyaal_worker.tasks.cut.tempfile.mkdtemp", fake_mkdtemp)
-    monkeypatch.setattr("al_iyaal_worker.tasks.cut.subprocess.run", fake_run)
+    monkeypatch.setattr("al_iyaal_worker.tasks.cut.run_tracked", fake_run)
 
     events: list[dict[str, object]] = []
     process_cut_job(
@@ -42,3 +47,130 @@ def test_should_clean_temp_artifacts_after_cut_completion(
     assert output_path.exists()
     assert output_path.parent.name == "video_cleaned"
     assert not temp_dir.exists()
+
+
+def test_should_cut_each_planned_video_and_report_one_task_done(
+    tmp_path: Path, monkeypatch
+) -> None:
+    first = tmp_path / "a.mp4"
+    second = tmp_path / "b.mp4"
+    third = tmp_path / "c.mp4"
+    for video_path in (first, second, third):
+        video_path.write_text("video")
+    sliced: list[str] = []
+
+    def fake_run(command: list[str]):
+        if "-ss" in command:
+            sliced.append(command[command.index("-i") + 1])
+        output_path = Path(command[-1])
+        output_path.parent.mkdir(parents=True, exist_ok=True)
+        output_path.write_text("slice")
+        return subprocess.CompletedProcess(command, returncode=0, stdout="", stderr="")
+
+    monkeypatch.setattr("al_iyaal_worker.tasks.cut.run_tracked", fake_run)
+
+    events: list[dict[str, object]] = []
+    process_cut_batch(
+        command=StartCutBatchCommand(
+            task_id="cut-batch",
+            jobs=[
+                PlannedCutJob(
+                    video_path=str(first),
+                    ranges=[CutRange(start="0:00", end="0:02"), CutRange(start="0:03", end="0:10")],
+                    removed_ranges=[CutRange(start="0:02", end="0:03")],
+                ),
+                PlannedCutJob(video_path=str(second), ranges=[], removed_ranges=[]),
+                PlannedCutJob(
+                    video_path=str(third),
+                    ranges=[],
+                    removed_ranges=[],
+                    error="Could not determine the duration",
+                ),
+            ],
+            output_mode="video_cleaned_default",
+        ),
+        emit=events.append,
+        should_cancel=lambda: False,
+    )
+
+    done_events = [event for event in events if event.get("type") == "job_done"]
+    assert [event.get("outputPath") for event in done_events] == [
+        str(tmp_path / "video_cleaned" / "a.mp4"),
+        None,
+    ]
+    assert set(sliced) == {str(first)}
+    error_event = next(event for event in events if event.get("type") == "job_error")
+    assert error_event["error"] == "Failed planning cuts: Could not determine the duration"
+    task_done = [event for event in events if event.get("type") == "task_done"]
+    assert len(task_done) == 1
+    assert task_done[0]["summary"] == {"ok": 2, "failed": 1, "cancelled": 0}
+
+
+def test_should_mute_ranges_without_slicing(tmp_path: Path, monkeypatch) -> None:
+    video_path = tmp_path / "clip.mp4"
+    video_path.write_text("video")
+    commands: list[list[str]] = []
+
+    def fake_run(command: list[str]):
+        commands.append(command)
+        return subprocess.CompletedProcess(command, returncode=0, stdout="", stderr="")
+
+    monkeypatch.setattr("al_iyaal_worker.tasks.cut.run_tracked", fake_run)
+
+    events: list[dict[str, object]] = []
+    process_cut_job(
+        command=StartCutJobCommand(
+            task_id="cut-task",
+            video_path=str(video_path),
+            ranges=[CutRange(start="0:05", end="0:06"), CutRange(start="0:01", end="0:02")],
+            output_mode="mute",
+        ),
+        emit=events.append,
+        should_cancel=lambda: False,
+    )
+
+    assert len(commands) == 1
+    assert "between(t,1.000,2.000)+between(t,5.000,6.000)" in commands[0][commands[0].index("-af") + 1]
+    done_event = next(event for event in events if event.get("type") == "job_done")
+    assert done_event["outputPath"] == str(tmp_path / "video_muted" / "clip.mp4")
+
+
+def test_should_write_retimed_subtitles_next_to_the_cleaned_video(
+    tmp_path: Path, monkeypatch
+) -> None:
+    video_path = tmp_path / "clip.mp4"
+    video_path.write_text("video")
+    video_path.with_suffix(".srt").write_text(
+        "1\n00:00:01,000 --> 00:00:02,000\nkept\n\n"
+        "2\n00:00:04,000 --> 00:00:05,000\nremoved\n\n"
+        "3\n00:00:08,000 --> 00:00:09,000\nshifted\n",
+        encoding="utf-8",
+    )
+
+    def fake_run(command: list[str]):
+        output_path = Path(command[-1])
+        output_path.parent.mkdir(parents=True, exist_ok=True)
+        output_path.write_text("slice")
+        return subprocess.CompletedProcess(command, returncode=0, stdout="", stderr="")
+
+    monkeypatch.setattr("al_iyaal_worker.tasks.cut.run_tracked", fake_run)
+
+    events: list[dict[str, object]] = []
+    process_cut_job(
+        command=StartCutJobCommand(
+            task_id="cut-task",
+            video_path=str(video_path),
+            ranges=[CutRange(start="0:00", end="0:03"), CutRange(start="0:06", end="0:10")],
+            output_mode="video_cleaned_default",
+        ),
+        emit=events.append,
+        should_cancel=lambda: False,
+    )
+
+    done_event = next(event for event in events if event.get("type") == "job_done")
+    subtitle_path = tmp_path / "video_cleaned" / "clip.srt"
+    assert done_event["artifacts"] == {"subtitlePath": str(subtitle_path)}
+    assert subtitle_path.read_text(encoding="utf-8") == (
+        "1\n00:00:01,000 --> 00:00:02,000\nkept\n\n"
+        "2\n00:00:05,000 --> 00:00:06,000\nshifted\n"
+    )
@@ -1,7 +1,7 @@
 import json
 from pathlib import Path
 
-from al_iyaal_worker.models import StartFlagBatchCommand
+from al_iyaal_worker.models import ProfileRef, StartFlagBatchCommand
 from al_iyaal_worker.tasks.flag import process_flag_batch
 
 
@@ -49,8 +49,176 @@ def test_should_flag_profanity_and_aqeedah_keywords(tmp_path: Path) -> None:
     assert analysis_path.exists()
 
     payload = json.loads(analysis_path.read_text())
+    assert json.loads((tmp_path / "clip.analysis.blacklist.json").read_text()) == payload
+    assert done_event["artifacts"]["engineAnalysisPath"] == str(tmp_path / "clip.analysis.blacklist.json")
+    assert payload["schemaVersion"] == 1
     assert payload["engine"] == "blacklist"
     assert len(payload["flagged"]) >= 2
     categories = {item["category"] for item in payload["flagged"]}
     assert "language" in categories
     assert "aqeedah" in categories
+
+
+def test_should_flag_from_a_vtt_sidecar_passed_as_input(tmp_path: Path) -> None:
+    vtt_path = tmp_path / "downloaded.vtt"
+    srt_content = (Path(__file__).parent / "fixtures" / "sample.srt").read_text()
+    vtt_path.write_text("WEBVTT\n\n" + srt_content.replace(",", "."))
+
+    events: list[dict[str, object]] = []
+    process_flag_batch(
+        command=StartFlagBatchCommand(
+            task_id="task-3",
+            input_paths=[str(vtt_path)],
+            settings={},
+        ),
+        emit=lambda payload: events.append(payload),
+        should_cancel=lambda: False,
+    )
+
+    done_event = next(event for event in events if event.get("type") == "job_done")
+    assert Path(str(done_event["outputPath"])) == tmp_path / "downloaded.analysis.json"
+    payload = json.loads((tmp_path / "downloaded.analysis.json").read_text())
+    assert {item["category"] for item in payload["flagged"]} >= {"language", "aqeedah"}
+
+
+def test_should_back_up_the_reviewed_analysis_when_reanalyzing(tmp_path: Path) -> None:
+    video_path = tmp_path / "clip.mp4"
+    video_path.write_text("video")
+    (tmp_path / "clip.srt").write_text((Path(__file__).parent / "fixtures" / "sample.srt").read_text())
+    command = StartFlagBatchCommand(task_id="task-4", input_paths=[str(video_path)], settings={})
+
+    process_flag_batch(command=command, emit=lambda payload: None, should_cancel=lambda: False)
+    analysis_path = tmp_path / "clip.analysis.json"
+    payload = json.loads(analysis_path.read_text())
+    dismissed, edited = payload["flagged"][0], payload["flagged"][1]
+    dismissed["review"] = {"decision": "dismissed", "reviewedAtEpochSeconds": 10}
+    edited["review"] = {
+        "decision": "edited",
+        "reviewedAtEpochSeconds": 11,
+        "original": {"startTime": edited["startTime"], "endTime": edited["endTime"], "priority": edited["priority"]},
+    }
+    edited["endTime"] = 9.5
+    analysis_path.write_text(json.dumps(payload))
+
+    events: list[dict[str, object]] = []
+    process_flag_batch(command=command, emit=lambda payload: events.append(payload), should_cancel=lambda: False)
+
+    backup_path = tmp_path / "clip.analysis.json.bak"
+    assert json.loads(backup_path.read_text())["flagged"][1]["endTime"] == 9.5
+    assert "review" not in json.loads(analysis_path.read_text())["flagged"][0]
+    replaced = next(event for event in events if event["type"] == "analysis_replaced")
+    assert replaced == {
+        "type": "analysis_replaced",
+        "taskId": "task-4",
+        "taskKind": "flag",
+        "jobId": replaced["jobId"],
+        "analysisPath": str(analysis_path),
+        "engineAnalysisPath": str(tmp_path / "clip.analysis.blacklist.json"),
+        "previousAnalysisPath": str(backup_path),
+    }
+    types = [event["type"] for event in events]
+    assert types.index("analysis_replaced") < types.index("job_done")
+
+
+def test_should_not_report_a_replaced_analysis_on_the_first_run(tmp_path: Path) -> None:
+    video_path = tmp_path / "clip.mp4"
+    video_path.write_text("video")
+    (tmp_path / "clip.srt").write_text((Path(__file__).parent / "fixtures" / "sample.srt").read_text())
+    command = StartFlagBatchCommand(task_id="task-5", input_paths=[str(video_path)], settings={})
+
+    events: list[dict[str, object]] = []
+    process_flag_batch(command=command, emit=lambda payload: events.append(payload), should_cancel=lambda: False)
+
+    assert not (tmp_path / "clip.analysis.json.bak").exists()
+    assert not any(event["type"] == "analysis_replaced" for event in events)
+
+
+def test_should_apply_pattern_match_types_and_exclusions(tmp_path: Path) -> None:
+    srt_path = tmp_path / "typed.srt"
+    srt_path.write_text(
+        "1\n00:00:01,000 --> 00:00:02,000\nA spelling bee\n\n"
+        "2\n00:00:03,000 --> 00:00:04,000\nCast a Spell!\n\n"
+        "3\n00:00:05,000 --> 00:00:06,000\nThey prayed,\nto him\n\n"
+        "4\n00:00:07,000 --> 00:00:08,000\nThey killed it\n"
+    )
+    rules = [
+        {"ruleId": "whole", "patterns": [{"value": "spell", "matchType": "whole_word"}]},
+        {"ruleId": "phrase", "patterns": [{"value": "prayed to", "matchType": "phrase"}]},
+        {"ruleId": "stem", "patterns": [{"value": "kill", "matchType": "stem"}]},
+        {"ruleId": "regex", "patterns": [{"value": r"\bspell(ing)?\b", "matchType": "regex"}], "exclusions": ["spelling bee"]},
+        {"ruleId": "broken", "patterns": [{"value": "(unclosed", "matchType": "regex"}]},
+    ]
+
+    events: list[dict[str, object]] = []
+    process_flag_batch(
+        command=StartFlagBatchCommand(task_id="task-5", input_paths=[str(srt_path)], settings={"rules": rules}),
+        emit=lambda payload: events.append(payload),
+        should_cancel=lambda: False,
+    )
+
+    payload = json.loads((tmp_path / "typed.analysis.json").read_text())
+    assert [(item["startTime"], item["ruleId"]) for item in payload["flagged"]] == [
+        (3.0, "whole"),
+        (3.0, "regex"),
+        (5.0, "phrase"),
+        (7.0, "stem"),
+    ]
+
+
+def test_should_only_drop_pattern_matches_an_exclusion_overlaps(tmp_path: Path) -> None:
+    srt_path = tmp_path / "excluded.srt"
+    srt_path.write_text(
+        "1\n00:00:01,000 --> 00:00:02,000\nOur spelling bee team\n\n"
+        "2\n00:00:03,000 --> 00:00:04,000\nAfter the spelling bee we cast a spell\n"
+    )
+    rules = [{"ruleId": "spell", "patterns": ["spell"], "exclusions": ["spelling bee"]}]
+
+    process_flag_batch(
+        command=StartFlagBatchCommand(task_id="task-6", input_paths=[str(srt_path)], settings={"rules": rules}),
+        emit=lambda _payload: None,
+        should_cancel=lambda: False,
+    )
+
+    payload = json.loads((tmp_path / "excluded.analysis.json").read_text())
+    assert [(item["startTime"], item["ruleId"]) for item in payload["flagged"]] == [(3.0, "spell")]
+
+
+def test_should_record_the_moderation_profile_in_the_sidecar(tmp_path: Path) -> None:
+    srt_path = tmp_path / "profiled.srt"
+    srt_path.write_text("1\n00:00:01,000 --> 00:00:02,000\nHello\n")
+
+    process_flag_batch(
+        command=StartFlagBatchCommand(
+            task_id="task-6",
+            input_paths=[str(srt_path)],
+            settings={},
+            profile=ProfileRef(id="under-6", name="Under 6"),
+        ),
+        emit=lambda _payload: None,
+        should_cancel=lambda: False,
+    )
+
+    payload = json.loads((tmp_path / "profiled.analysis.json").read_text())
+    assert payload["profile"] == {"id": "under-6", "name": "Under 6"}
+
+
+def test_should_only_wait_for_resume_before_queued_jobs(tmp_path: Path) -> None:
+    input_paths = []
+    for name in ("first.mp4", "second.mp4"):
+        video_path = tmp_path / name
+        video_path.write_text("video")
+        input_paths.append(str(video_path))
+
+    events: list[dict[str, object]] = []
+    pause_points: list[list[str]] = []
+    process_flag_batch(
+        command=StartFlagBatchCommand(task_id="task-7", input_paths=input_paths, settings={}),
+        emit=lambda payload: events.append(payload),
+        should_cancel=lambda: False,
+        wait_if_paused=lambda: pause_points.append([str(event.get("type")) for event in events]),
+    )
+
+    assert len(pause_points) == 2
+    assert "job_error" not in pause_points[0]
+    assert pause_points[1].count("job_error") == 1
+    assert events[-1]["type"] == "task_done"
@@ -0,0 +1,50 @@
+from al_iyaal_worker.moderation import analyze_subtitles
+from al_iyaal_worker.moderation.normalization import TextNormalization, normalize_text
+from al_iyaal_worker.subtitles import SubtitleEntry
+
+ALL_ENABLED = TextNormalization(
+    fold_case=True,
+    strip_diacritics=True,
+    unify_arabic_letters=True,
+    fold_digits=True,
+    fold_leetspeak=True,
+)
+
+
+def test_should_strip_tashkeel_and_unify_arabic_letters() -> None:
+    # "إِلَٰهٌ" and "مدرسة" normalize to "اله" and "مدرسه".
+    text = "إِلَٰهٌ مدرسة"
+
+    assert normalize_text(text, ALL_ENABLED) == "اله مدرسه"
+
+
+def test_should_fold_digits_and_leetspeak_only_inside_words() -> None:
+    assert normalize_text("St00pid 2024 ٣٠", ALL_ENABLED) == "stoopid 2024 30"
+    assert normalize_text("a$$ h3ll", ALL_ENABLED) == "ass hell"
+
+
+def test_should_only_lowercase_without_normalization_settings() -> None:
+    options = TextNormalization.from_settings({})
+
+    assert normalize_text("St00pid إِله", options) == "st00pid إِله"
+
+
+def test_should_flag_normalized_arabic_and_leetspeak() -> None:
+    subtitles = [
+        SubtitleEntry(index=1, start_time=1.0, end_time=2.0, text="إِلَٰهٌ"),
+        SubtitleEntry(index=2, start_time=2.0, end_time=3.0, text="So ST00PID"),
+    ]
+    settings = {
+        "profanityWords": ["stoopid"],
+        "rules": [{"ruleId": "deity", "patterns": [{"value": "إله", "matchType": "whole_word"}]}],
+        "normalization": {
+            "stripDiacritics": True,
+            "unifyArabicLetters": True,
+            "foldDigits": True,
+            "foldLeetspeak": True,
+        },
+    }
+
+    flagged, _ = analyze_subtitles(subtitles, settings)
+
+    assert [item["ruleId"] for item in flagged] == ["deity", "profanity"]
@@ -0,0 +1,191 @@
+import json
+from pathlib import Path
+
+from al_iyaal_worker.models import CutRange, PipelineStage, PlannedCutJob, ProfileRef, StartPipelineCommand
+from al_iyaal_worker.tasks.pipeline import process_pipeline
+
+
+def _write_analysis(video_path: Path, flagged: list[dict[str, object]]) -> None:
+    video_path.with_suffix(".analysis.json").write_text(json.dumps({"flagged": flagged}))
+
+
+def test_should_feed_each_stage_output_into_the_next(tmp_path: Path, monkeypatch) -> None:
+    video_path = tmp_path / "clip.mp4"
+    video_path.write_text("video")
+    replaced_path = tmp_path / "audio_replaced" / "clip.mp4"
+    seen_inputs: dict[str, list[str]] = {}
+
+    def fake_remove_music(command, emit, should_cancel, should_abort=lambda: False):
+        seen_inputs["remove_music"] = command.input_paths
+        replaced_path.parent.mkdir(parents=True, exist_ok=True)
+        replaced_path.write_text("video")
+        emit({"type": "job_progress", "batchId": command.batch_id, "jobId": "x", "progressPct": 50})
+        emit({"type": "job_done", "batchId": command.batch_id, "jobId": "x", "outputPath": str(replaced_path)})
+        emit({"type": "batch_done", "batchId": command.batch_id, "summary": {}})
+
+    def fake_cut(command, emit, should_cancel, should_abort=lambda: False):
+        seen_inputs["cut"] = [command.video_path]
+        seen_inputs["ranges"] = [f"{item.start}-{item.end}" for item in command.ranges]
+        emit({"type": "job_done", "taskId": command.task_id, "jobId": "x", "outputPath": "/out/clip.mp4"})
+
+    _write_analysis(video_path, [{"startTime": 2, "endTime": 4, "priority": "high", "category": "music"}])
+    monkeypatch.setattr("al_iyaal_worker.tasks.pipeline.process_remove_music_batch", fake_remove_music)
+    monkeypatch.setattr("al_iyaal_worker.tasks.pipeline.process_cut_job", fake_cut)
+
+    def fake_request_cut_plan(video: Path, analysis: Path) -> PlannedCutJob:
+        seen_inputs["cut_plan"] = [str(video), str(analysis)]
+        return PlannedCutJob(
+            video_path=str(video),
+            ranges=[CutRange("0:00.000", "0:02.000"), CutRange("0:04.000", "0:10.000")],
+            removed_ranges=[CutRange("0:02.000", "0:04.000")],
+        )
+
+    events: list[dict[str, object]] = []
+    process_pipeline(
+        StartPipelineCommand(
+            task_id="pipeline-1",
+            input_paths=[str(video_path)],
+            stages=[PipelineStage("remove_music", {}), PipelineStage("cut", {})],
+            settings={},
+        ),
+        emit=events.append,
+        should_cancel=lambda: False,
+        request_cut_plan=fake_request_cut_plan,
+    )
+
+    assert seen_inputs["remove_music"] == [str(video_path)]
+    assert seen_inputs["cut_plan"] == [str(replaced_path), str(video_path.with_suffix(".analysis.json"))]
+    assert seen_inputs["cut"] == [str(replaced_path)]
+    assert seen_inputs["ranges"] == ["0:00.000-0:02.000", "0:04.000-0:10.000"]
+    assert [event["stage"] for event in events if event["type"] == "job_stage"] == ["remove_music", "cut"]
+    progress = [event["progressPct"] for event in events if event["type"] == "job_progress"]
+    assert progress == [25]
+    assert not any(event["type"] == "batch_done" for event in events)
+
+    done = next(event for event in events if event["type"] == "job_done")
+    assert done["taskKind"] == "pipeline"
+    assert done["outputPath"] == "/out/clip.mp4"
+    assert done["artifacts"] == {
+        "stageOutputs": {"remove_music": str(replaced_path), "cut": "/out/clip.mp4"}
+    }
+    assert events[-1]["summary"] == {"ok": 1, "failed": 0, "cancelled": 0}
+
+
+def test_should_fail_the_file_and_continue_when_a_stage_fails(tmp_path: Path) -> None:
+    first = tmp_path / "a.mp4"
+    second = tmp_path / "b.mp4"
+    first.write_text("video")
+    second.write_text("video")
+
+    events: list[dict[str, object]] = []
+    process_pipeline(
+        StartPipelineCommand(
+            task_id="pipeline-2",
+            input_paths=[str(first), str(second)],
+            stages=[PipelineStage("cut", {})],
+            settings={},
+        ),
+        emit=events.append,
+        should_cancel=lambda: False,
+    )
+
+    errors = [event for event in events if event["type"] == "job_error"]
+    assert len(errors) == 2
+    assert str(errors[0]["error"]).startswith("cut stage failed: Missing analysis sidecar")
+    assert events[-1]["summary"] == {"ok": 0, "failed": 2, "cancelled": 0}
+
+
+def test_should_keep_the_video_when_nothing_matches(tmp_path: Path) -> None:
+    video_path = tmp_path / "clip.mp4"
+    video_path.write_text("video")
+    _write_analysis(video_path, [{"startTime": 2, "endTime": 4, "priority": "low", "category": "music"}])
+
+    events: list[dict[str, object]] = []
+    process_pipeline(
+        StartPipelineCommand(
+            task_id="pipeline-3",
+            input_paths=[str(video_path)],
+            stages=[PipelineStage("cut", {"minPriority": "high"})],
+            settings={},
+        ),
+        emit=events.append,
+        should_cancel=lambda: False,
+        request_cut_plan=lambda video, analysis: PlannedCutJob(
+            video_path=str(video),
+            ranges=[CutRange("0:00.000", "0:10.000")],
+            removed_ranges=[],
+        ),
+    )
+
+    done = next(event for event in events if event["type"] == "job_done")
+    assert done["outputPath"] == str(video_path)
+
+
+def test_should_fail_the_file_with_the_hosts_planning_error(tmp_path: Path) -> None:
+    video_path = tmp_path / "clip.mp4"
+    video_path.write_text("video")
+    _write_analysis(video_path, [])
+
+    events: list[dict[str, object]] = []
+    process_pipeline(
+        StartPipelineCommand(
+            task_id="pipeline-5",
+            input_paths=[str(video_path)],
+            stages=[PipelineStage("cut", {})],
+            settings={},
+        ),
+        emit=events.append,
+        should_cancel=lambda: False,
+        request_cut_plan=lambda video, analysis: PlannedCutJob(
+            video_path=str(video), ranges=[], removed_ranges=[], error="ffmpeg is missing"
+        ),
+    )
+
+    error = next(event for event in events if event["type"] == "job_error")
+    assert error["error"] == "cut stage failed: Failed planning cuts: ffmpeg is missing"
+    assert events[-1]["summary"] == {"ok": 0, "failed": 1, "cancelled": 0}
+
+
+def test_should_pass_a_replaced_analysis_on_to_the_host(tmp_path: Path) -> None:
+    video_path = tmp_path / "clip.mp4"
+    video_path.write_text("video")
+    video_path.with_suffix(".srt").write_text("1\n00:00:01,000 --> 00:00:02,000\nMerry Christmas\n")
+    _write_analysis(video_path, [])
+
+    events: list[dict[str, object]] = []
+    process_pipeline(
+        StartPipelineCommand(
+            task_id="pipeline-6",
+            input_paths=[str(video_path)],
+            stages=[PipelineStage("flag", {})],
+            settings={"engine": "blacklist"},
+        ),
+        emit=events.append,
+        should_cancel=lambda: False,
+    )
+
+    replaced = next(event for event in events if event["type"] == "analysis_replaced")
+    assert replaced["taskId"] == "pipeline-6"
+    assert replaced["taskKind"] == "pipeline"
+    assert replaced["previousAnalysisPath"] == str(video_path.with_suffix(".analysis.json.bak"))
+
+
+def test_should_record_the_moderation_profile_of_the_flag_stage(tmp_path: Path) -> None:
+    video_path = tmp_path / "clip.mp4"
+    video_path.write_text("video")
+    video_path.with_suffix(".srt").write_text("1\n00:00:01,000 --> 00:00:02,000\nMerry Christmas\n")
+
+    process_pipeline(
+        StartPipelineCommand(
+            task_id="pipeline-4",
+            input_paths=[str(video_path)],
+            stages=[PipelineStage("flag", {"profile": "under-6"})],
+            settings={"engine": "blacklist"},
+            profile=ProfileRef(id="under-6", name="Under 6"),
+        ),
+        emit=lambda event: None,
+        should_cancel=lambda: False,
+    )
+
+    analysis = json.loads(video_path.with_suffix(".analysis.json").read_text())
+    assert analysis["profile"] == {"id": "under-6", "name": "Under 6"}
@@ -0,0 +1,40 @@
+import sys
+import threading
+
+import pytest
+
+from al_iyaal_worker.processes import (
+    OperationAborted,
+    run_tracked,
+    run_until_aborted,
+    terminate_active_processes,
+    tracked_popen,
+)
+
+
+def test_should_return_completed_process_from_tracked_run() -> None:
+    result = run_tracked([sys.executable, "-c", "print('ok')"])
+
+    assert result.returncode == 0
+    assert result.stdout.strip() == "ok"
+
+
+def test_should_terminate_tracked_processes() -> None:
+    with tracked_popen([sys.executable, "-c", "import time; time.sleep(30)"]) as process:
+        assert terminate_active_processes(grace_seconds=2.0) == 1
+        assert process.poll() is not None
+
+    assert terminate_active_processes() == 0
+
+
+def test_should_abandon_blocking_work_when_aborted() -> None:
+    release = threading.Event()
+
+    with pytest.raises(OperationAborted):
+        run_until_aborted(lambda: release.wait(5), lambda: True, poll_interval=0.01)
+
+    release.set()
+
+
+def test_should_return_value_when_work_finishes() -> None:
+    assert run_until_aborted(lambda: 42, lambda: False, poll_interval=0.01) == 42
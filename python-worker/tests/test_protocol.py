@@ -0,0 +1,175 @@
+import inspect
+import io
+import json
+import re
+
+from al_iyaal_worker.models import (
+    CutRange,
+    PingCommand,
+    PipelineStage,
+    PlannedCutJob,
+    ProfileRef,
+    ProvideCutPlanCommand,
+    StartCutBatchCommand,
+    StartFlagBatchCommand,
+    StartPipelineCommand,
+)
+from al_iyaal_worker.protocol import PROTOCOL_VERSION, SUPPORTED_COMMANDS, parse_worker_command
+from al_iyaal_worker.worker_daemon import WorkerDaemon
+
+
+def test_should_parse_ping_command() -> None:
+    command = parse_worker_command('{"type":"ping","pingId":"ping-1"}')
+
+    assert command == PingCommand(ping_id="ping-1")
+
+
+def test_should_answer_ping_with_pong() -> None:
+    output = io.StringIO()
+    WorkerDaemon(
+        input_stream=io.StringIO('{"type":"ping","pingId":"ping-3"}\n'),
+        output_stream=output,
+    ).run()
+
+    lines = output.getvalue().splitlines()
+    assert lines[-1] == '{"type":"pong","pingId":"ping-3"}'
+
+
+def test_should_send_hello_before_anything_else() -> None:
+    output = io.StringIO()
+    WorkerDaemon(input_stream=io.StringIO(""), output_stream=output).run()
+
+    hello = json.loads(output.getvalue().splitlines()[0])
+    assert hello["type"] == "hello"
+    assert hello["protocolVersion"] == PROTOCOL_VERSION
+    assert "ping" in hello["capabilities"]
+
+
+MINIMAL_COMMAND_PAYLOADS = {
+    "start_batch": {"batchId": "batch-1", "inputPaths": ["/tmp/a.mp4"], "outputDir": "/tmp/out"},
+    "start_transcription_batch": {"taskId": "task-1", "inputPaths": ["/tmp/a.mp4"]},
+    "start_flag_batch": {"taskId": "task-1", "inputPaths": ["/tmp/a.srt"]},
+    "start_cut_job": {"taskId": "task-1", "videoPath": "/tmp/a.mp4"},
+    "start_cut_batch": {"taskId": "task-1", "jobs": [{"videoPath": "/tmp/a.mp4"}]},
+    "start_pipeline": {"taskId": "task-1", "inputPaths": ["/tmp/a.mp4"], "stages": [{"stage": "flag"}]},
+    "cancel_batch": {"batchId": "batch-1"},
+    "cancel_task": {"taskId": "task-1"},
+    "pause_batch": {"batchId": "batch-1"},
+    "resume_batch": {"batchId": "batch-1"},
+    "pause_task": {"taskId": "task-1"},
+    "resume_task": {"taskId": "task-1"},
+    "provide_cut_plan": {"taskId": "task-1", "job": {"videoPath": "/tmp/a.mp4"}},
+    "ping": {"pingId": "ping-1"},
+}
+
+
+def test_should_advertise_every_parsable_command() -> None:
+    parsable = set(re.findall(r'command_type == "(\w+)"', inspect.getsource(parse_worker_command)))
+
+    assert set(SUPPORTED_COMMANDS) == parsable
+    assert set(MINIMAL_COMMAND_PAYLOADS) == parsable
+    for command_type in SUPPORTED_COMMANDS:
+        payload = {"type": command_type, **MINIMAL_COMMAND_PAYLOADS[command_type]}
+        assert parse_worker_command(json.dumps(payload)) is not None
+
+
+def test_should_advertise_optional_features() -> None:
+    output = io.StringIO()
+    WorkerDaemon(input_stream=io.StringIO(""), output_stream=output).run()
+
+    hello = json.loads(output.getvalue().splitlines()[0])
+    assert "cancel_immediate" in hello["capabilities"]
+    assert "audio_redaction" in hello["capabilities"]
+    assert "flag_profiles" in hello["capabilities"]
+
+
+def test_should_parse_pipeline_stages_with_their_options() -> None:
+    command = parse_worker_command(
+        json.dumps(
+            {
+                "type": "start_pipeline",
+                "taskId": "pipeline-1",
+                "inputPaths": ["/tmp/a.mp4"],
+                "stages": [{"stage": "transcribe", "yapMode": "auto"}, {"stage": "cut", "minPriority": "medium"}],
+                "settings": {"engine": "blacklist"},
+                "profile": {"id": "under-6", "name": "Under 6"},
+            }
+        )
+    )
+
+    assert command == StartPipelineCommand(
+        task_id="pipeline-1",
+        input_paths=["/tmp/a.mp4"],
+        stages=[
+            PipelineStage(stage="transcribe", options={"yapMode": "auto"}),
+            PipelineStage(stage="cut", options={"minPriority": "medium"}),
+        ],
+        settings={"engine": "blacklist"},
+        profile=ProfileRef(id="under-6", name="Under 6"),
+    )
+
+
+def test_should_parse_the_moderation_profile_of_a_flag_batch() -> None:
+    payload = {"type": "start_flag_batch", "taskId": "flag-1", "inputPaths": ["/tmp/a.srt"], "settings": {}}
+
+    with_profile = parse_worker_command(json.dumps(payload | {"profile": {"id": "default", "name": "Default"}}))
+    without_profile = parse_worker_command(json.dumps(payload))
+
+    assert with_profile == StartFlagBatchCommand(
+        task_id="flag-1",
+        input_paths=["/tmp/a.srt"],
+        settings={},
+        profile=ProfileRef(id="default", name="Default"),
+    )
+    assert isinstance(without_profile, StartFlagBatchCommand)
+    assert without_profile.profile is None
+
+
+def test_should_parse_the_planned_jobs_of_a_cut_batch() -> None:
+    payload = {
+        "type": "start_cut_batch",
+        "taskId": "cut-1",
+        "jobs": [
+            {
+                "videoPath": "/tmp/a.mp4",
+                "ranges": [{"start": "0:00.000", "end": "0:02.000"}],
+                "removedRanges": [{"start": "0:02.000", "end": "0:03.000"}],
+            },
+            {"videoPath": "/tmp/b.mp4", "ranges": [], "removedRanges": [], "error": "No duration"},
+        ],
+        "outputMode": "mute",
+    }
+
+    assert parse_worker_command(json.dumps(payload)) == StartCutBatchCommand(
+        task_id="cut-1",
+        jobs=[
+            PlannedCutJob(
+                video_path="/tmp/a.mp4",
+                ranges=[CutRange(start="0:00.000", end="0:02.000")],
+                removed_ranges=[CutRange(start="0:02.000", end="0:03.000")],
+            ),
+            PlannedCutJob(video_path="/tmp/b.mp4", ranges=[], removed_ranges=[], error="No duration"),
+        ],
+        output_mode="mute",
+    )
+
+
+def test_should_parse_the_cut_plan_the_host_provides() -> None:
+    payload = {
+        "type": "provide_cut_plan",
+        "taskId": "pipeline-1",
+        "job": {
+            "videoPath": "/tmp/a.mp4",
+            "ranges": [{"start": "0:00.000", "end": "0:02.000"}],
+            "removedRanges": [{"start": "0:02.000", "end": "0:03.000"}],
+        },
+    }
+
+    assert parse_worker_command(json.dumps(payload)) == ProvideCutPlanCommand(
+        task_id="pipeline-1",
+        job=PlannedCutJob(
+            video_path="/tmp/a.mp4",
+            ranges=[CutRange(start="0:00.000", end="0:02.000")],
+            removed_ranges=[CutRange(start="0:02.000", end="0:03.000")],
+        ),
+    )
@@ -0,0 +1,83 @@
+from pathlib import Path
+
+from al_iyaal_worker.subtitles import (
+    SubtitleEntry,
+    engine_analysis_path,
+    find_subtitle_sidecar,
+    parse_ass,
+    parse_srt,
+    parse_vtt,
+    retime_subtitles,
+    serialize_srt,
+)
+
+
+def _entry(index: int, start_time: float, end_time: float, text: str) -> SubtitleEntry:
+    return SubtitleEntry(index=index, start_time=start_time, end_time=end_time, text=text)
+
+
+def test_should_round_trip_serialized_srt() -> None:
+    entries = [_entry(1, 1.5, 2.25, "first line"), _entry(2, 3661.0, 3662.001, "two\nlines")]
+
+    content = serialize_srt(entries)
+
+    assert "01:01:01,000 --> 01:01:02,001" in content
+    assert parse_srt(content) == entries
+
+
+def test_should_drop_removed_cues_and_shift_later_ones() -> None:
+    entries = [
+        _entry(1, 1.0, 2.0, "kept"),
+        _entry(2, 4.0, 5.0, "removed"),
+        _entry(3, 8.0, 9.0, "shifted"),
+    ]
+
+    retimed = retime_subtitles(entries, [(0.0, 3.0), (6.0, 10.0)])
+
+    assert retimed == [_entry(1, 1.0, 2.0, "kept"), _entry(2, 5.0, 6.0, "shifted")]
+
+
+def test_should_trim_cues_that_straddle_a_cut() -> None:
+    entries = [_entry(1, 2.0, 7.0, "across the cut")]
+
+    retimed = retime_subtitles(entries, [(0.0, 3.0), (6.0, 10.0)])
+
+    assert retimed == [_entry(1, 2.0, 4.0, "across the cut")]
+
+
+def test_should_parse_vtt_cues_without_header_notes_or_markup() -> None:
+    content = (
+        "WEBVTT\n\nNOTE downloaded\n\nintro\n00:01.000 --> 00:02.500 line:0\n"
+        "<v Host>Hello</v> there\n\n01:00:00.000 --> 01:00:01.000\n<i>Bye</i>\n"
+    )
+
+    assert parse_vtt(content) == [_entry(1, 1.0, 2.5, "Hello there"), _entry(2, 3600.0, 3601.0, "Bye")]
+
+
+def test_should_parse_ass_dialogue_lines() -> None:
+    content = (
+        "[Script Info]\nTitle: Episode\n\n[Events]\n"
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text\n"
+        "Comment: 0,0:00:00.00,0:00:01.00,Default,,0,0,0,,ignored\n"
+        "Dialogue: 0,0:00:01.50,0:00:03.00,Default,,0,0,0,,{\\i1}Hello{\\i0}, there\\Nfriend\n"
+    )
+
+    assert parse_ass(content) == [_entry(1, 1.5, 3.0, "Hello, there\nfriend")]
+
+
+def test_should_prefer_srt_when_finding_subtitle_sidecars(tmp_path: Path) -> None:
+    video_path = tmp_path / "episode.mp4"
+    (tmp_path / "episode.vtt").write_text("WEBVTT\n")
+
+    assert find_subtitle_sidecar(video_path) == tmp_path / "episode.vtt"
+
+    (tmp_path / "episode.srt").write_text("")
+    assert find_subtitle_sidecar(video_path) == tmp_path / "episode.srt"
+    assert find_subtitle_sidecar(tmp_path / "other.mp4") is None
+
+
+def test_should_name_engine_specific_analysis_sidecars() -> None:
+    analysis_path = Path("/videos/episode.analysis.json")
+
+    assert engine_analysis_path(analysis_path, "gemini") == Path("/videos/episode.analysis.gemini.json")
+    assert engine_analysis_path(analysis_path, "Nova Pro") == Path("/videos/episode.analysis.nova_pro.json")
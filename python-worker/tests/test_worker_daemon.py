@@ -0,0 +1,121 @@
+import io
+import json
+import threading
+from pathlib import Path
+
+from al_iyaal_worker.models import CancelTaskCommand, PlannedCutJob, ProvideCutPlanCommand
+from al_iyaal_worker.worker_daemon import WorkerDaemon
+
+
+def _events(output: io.StringIO) -> list[dict[str, object]]:
+    return [json.loads(line) for line in output.getvalue().splitlines()]
+
+
+def _reserved_daemon(operation_id: str) -> tuple[WorkerDaemon, io.StringIO]:
+    output = io.StringIO()
+    daemon = WorkerDaemon(input_stream=io.StringIO(""), output_stream=output)
+    assert daemon._reserve_operation(operation_id, conflict_summary={})
+    return daemon, output
+
+
+def test_should_park_paused_batch_until_resumed() -> None:
+    daemon, output = _reserved_daemon("batch-1")
+    wait_if_paused = daemon._pause_gate("batch-1", "batch", {"batchId": "batch-1"})
+    daemon._set_paused("batch-1", "batch", paused=True)
+
+    worker = threading.Thread(target=wait_if_paused)
+    worker.start()
+    worker.join(0.2)
+    assert worker.is_alive()
+
+    daemon._set_paused("batch-1", "batch", paused=False)
+    worker.join(2)
+
+    assert not worker.is_alive()
+    types = [event["type"] for event in _events(output)]
+    assert types.index("batch_paused") < types.index("batch_resumed")
+
+
+def test_should_wake_paused_task_when_cancelled() -> None:
+    daemon, output = _reserved_daemon("task-1")
+    wait_if_paused = daemon._pause_gate(
+        "task-1",
+        "task",
+        {"taskId": "task-1", "taskKind": "flag"},
+    )
+    daemon._set_paused("task-1", "task", paused=True)
+
+    worker = threading.Thread(target=wait_if_paused)
+    worker.start()
+    worker.join(0.2)
+    assert worker.is_alive()
+
+    daemon._cancel_task(CancelTaskCommand(task_id="task-1", mode="stop_after_current"))
+    worker.join(2)
+
+    assert not worker.is_alive()
+    paused = next(event for event in _events(output) if event["type"] == "task_paused")
+    assert paused == {"type": "task_paused", "taskId": "task-1", "taskKind": "flag"}
+    assert not any(event["type"] == "task_resumed" for event in _events(output))
+
+
+def test_should_not_park_a_running_operation_that_is_not_paused() -> None:
+    daemon, output = _reserved_daemon("task-1")
+    wait_if_paused = daemon._pause_gate("task-1", "task", {"taskId": "task-1", "taskKind": "flag"})
+
+    wait_if_paused()
+
+    assert not any(event["type"] == "task_paused" for event in _events(output))
+
+
+def test_should_report_pause_for_unknown_operation() -> None:
+    output = io.StringIO()
+    WorkerDaemon(
+        input_stream=io.StringIO('{"type":"pause_batch","batchId":"missing"}\n'),
+        output_stream=output,
+    ).run()
+
+    assert "No active batch found for pause request: missing." in output.getvalue()
+
+
+def test_should_hand_the_hosts_cut_plan_to_the_waiting_pipeline() -> None:
+    daemon, output = _reserved_daemon("pipeline-1")
+    job = PlannedCutJob(video_path="/tmp/a.mp4", ranges=[], removed_ranges=[])
+    replies: list[PlannedCutJob | None] = []
+
+    worker = threading.Thread(
+        target=lambda: replies.append(
+            daemon._request_cut_plan("pipeline-1", Path("/tmp/a.mp4"), Path("/tmp/a.analysis.json"))
+        )
+    )
+    worker.start()
+    worker.join(0.2)
+    assert worker.is_alive()
+
+    daemon._provide_cut_plan(ProvideCutPlanCommand(task_id="pipeline-1", job=job))
+    worker.join(2)
+
+    assert replies == [job]
+    request = next(event for event in _events(output) if event["type"] == "cut_plan_needed")
+    assert request == {
+        "type": "cut_plan_needed",
+        "taskId": "pipeline-1",
+        "videoPath": "/tmp/a.mp4",
+        "analysisPath": "/tmp/a.analysis.json",
+    }
+
+
+def test_should_stop_waiting_for_a_cut_plan_when_aborted() -> None:
+    daemon, _ = _reserved_daemon("pipeline-1")
+    replies: list[PlannedCutJob | None] = []
+
+    worker = threading.Thread(
+        target=lambda: replies.append(
+            daemon._request_cut_plan("pipeline-1", Path("/tmp/a.mp4"), Path("/tmp/a.analysis.json"))
+        )
+    )
+    worker.start()
+    daemon._cancel_task(CancelTaskCommand(task_id="pipeline-1", mode="immediate"))
+    worker.join(2)
+
+    assert replies == [None]
@@ -0,0 +1,787 @@
+use std::{
+    ffi::OsString,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::profiles::ProfileRef;
+
+/// Sidecars written before `schemaVersion` existed are read as version 1.
+pub const ANALYSIS_SCHEMA_VERSION: u32 = 1;
+
+fn default_schema_version() -> u32 {
+    1
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum FlagPriority {
+    High,
+    Medium,
+    Low,
+}
+
+impl FlagPriority {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "high" => Some(Self::High),
+            "medium" => Some(Self::Medium),
+            "low" => Some(Self::Low),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewDecision {
+    Confirmed,
+    Dismissed,
+    Edited,
+}
+
+/// The engine's values for an item a reviewer edited, kept so the edit can be
+/// undone and matched again when the video is re-analyzed.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OriginalFlagValues {
+    pub start_time: f64,
+    pub end_time: f64,
+    pub priority: FlagPriority,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FlagReview {
+    pub decision: ReviewDecision,
+    pub reviewed_at_epoch_seconds: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original: Option<OriginalFlagValues>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FlagEdit {
+    pub start_time: Option<f64>,
+    pub end_time: Option<f64>,
+    pub priority: Option<FlagPriority>,
+}
+
+impl FlagEdit {
+    fn is_empty(&self) -> bool {
+        self.start_time.is_none() && self.end_time.is_none() && self.priority.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FlaggedItem {
+    pub start_time: f64,
+    pub end_time: f64,
+    pub text: String,
+    pub reason: String,
+    pub priority: FlagPriority,
+    pub category: String,
+    pub rule_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub review: Option<FlagReview>,
+    /// Engines that flagged this span, set on merged analyses.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub engines: Vec<String>,
+}
+
+impl FlaggedItem {
+    pub fn review_decision(&self) -> Option<ReviewDecision> {
+        self.review.as_ref().map(|review| review.decision)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalysisDocument {
+    #[serde(default = "default_schema_version")]
+    pub schema_version: u32,
+    pub engine: String,
+    pub created_at: String,
+    pub video_file_name: String,
+    pub summary: String,
+    pub flagged: Vec<FlaggedItem>,
+    /// The moderation profile the analysis ran with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<ProfileRef>,
+}
+
+impl AnalysisDocument {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.schema_version == 0 || self.schema_version > ANALYSIS_SCHEMA_VERSION {
+            return Err(format!(
+                "Unsupported schemaVersion {} (this app reads up to {ANALYSIS_SCHEMA_VERSION}).",
+                self.schema_version
+            ));
+        }
+        if self.engine.trim().is_empty() {
+            return Err("engine must not be empty.".to_string());
+        }
+
+        for (index, item) in self.flagged.iter().enumerate() {
+            if !item.start_time.is_finite() || item.start_time < 0.0 {
+                return Err(format!(
+                    "flagged[{index}].startTime must be zero or more seconds, got {}.",
+                    item.start_time
+                ));
+            }
+            if !item.end_time.is_finite() || item.end_time < item.start_time {
+                return Err(format!(
+                    "flagged[{index}].endTime ({}) must not be before startTime ({}).",
+                    item.end_time, item.start_time
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// `createdAt` in the worker's format, e.g. `2026-01-02T03:04:05.123456+00:00`.
+pub fn format_created_at(time: SystemTime) -> String {
+    let elapsed = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let seconds = elapsed.as_secs();
+    let (hours, minutes, whole_seconds) = ((seconds / 3600) % 24, (seconds / 60) % 60, seconds % 60);
+
+    // Days since the epoch to a proleptic Gregorian date (Howard Hinnant's `civil_from_days`).
+    let days = (seconds / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{hours:02}:{minutes:02}:{whole_seconds:02}.{:06}+00:00",
+        elapsed.subsec_micros()
+    )
+}
+
+fn review_key(item: &FlaggedItem) -> (&str, i64) {
+    let start_time = item
+        .review
+        .as_ref()
+        .and_then(|review| review.original.as_ref())
+        .map_or(item.start_time, |original| original.start_time);
+    (item.rule_id.as_str(), (start_time * 1000.0).round() as i64)
+}
+
+/// Copies reviewer decisions from `previous` onto the newly flagged items of
+/// the same rule at the same (engine) start time; edited items keep their
+/// edited times and priority. Returns how many carried over.
+pub fn carry_over_reviews(previous: &AnalysisDocument, flagged: &mut [FlaggedItem]) -> usize {
+    let mut carried = 0;
+    for item in flagged.iter_mut() {
+        let key = review_key(item);
+        let Some(previous_item) = previous
+            .flagged
+            .iter()
+            .find(|candidate| candidate.review.is_some() && review_key(candidate) == key)
+        else {
+            continue;
+        };
+        let review = previous_item.review.clone();
+        if review.as_ref().is_some_and(|review| review.original.is_some()) {
+            item.start_time = previous_item.start_time;
+            item.end_time = previous_item.end_time;
+            item.priority = previous_item.priority;
+        }
+        item.review = review;
+        carried += 1;
+    }
+    carried
+}
+
+/// The media stem of `<stem>.analysis.json` or an engine-specific
+/// `<stem>.analysis.<engine>.json` file name.
+pub fn analysis_file_stem(file_name: &str) -> Option<&str> {
+    let without_json = file_name.strip_suffix(".json")?;
+    let stem = match without_json.strip_suffix(".analysis") {
+        Some(stem) => stem,
+        None => {
+            let (rest, engine) = without_json.rsplit_once('.')?;
+            let is_engine_name = !engine.is_empty()
+                && engine
+                    .chars()
+                    .all(|character| character.is_ascii_alphanumeric() || character == '_' || character == '-');
+            if !is_engine_name {
+                return None;
+            }
+            rest.strip_suffix(".analysis")?
+        }
+    };
+    (!stem.is_empty()).then_some(stem)
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeMode {
+    /// Every span flagged by at least one engine.
+    Union,
+    /// Only spans flagged by every engine.
+    Intersection,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EngineOnlyItems {
+    pub engine: String,
+    pub flagged: Vec<FlaggedItem>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalysisDiff {
+    pub engines: Vec<String>,
+    /// Spans flagged by more than one engine.
+    pub shared_count: usize,
+    /// Per engine, the items no other engine flagged at an overlapping time.
+    pub unique: Vec<EngineOnlyItems>,
+}
+
+fn ensure_distinct_engines(documents: &[AnalysisDocument]) -> Result<(), String> {
+    if documents.len() < 2 {
+        return Err("Select at least two analyses to compare.".to_string());
+    }
+    for (index, document) in documents.iter().enumerate() {
+        if documents[..index].iter().any(|other| other.engine == document.engine) {
+            return Err(format!("Each analysis must come from a different engine; {} appears twice.", document.engine));
+        }
+    }
+    Ok(())
+}
+
+/// Groups the flagged items of all documents into spans of overlapping time,
+/// each item tagged with its document's engine.
+fn overlap_clusters(documents: &[AnalysisDocument]) -> Vec<Vec<(&str, &FlaggedItem)>> {
+    let mut items = documents
+        .iter()
+        .flat_map(|document| document.flagged.iter().map(|item| (document.engine.as_str(), item)))
+        .collect::<Vec<_>>();
+    items.sort_by(|left, right| left.1.start_time.total_cmp(&right.1.start_time));
+
+    let mut clusters: Vec<Vec<(&str, &FlaggedItem)>> = Vec::new();
+    let mut cluster_end = f64::NEG_INFINITY;
+    for (engine, item) in items {
+        match clusters.last_mut() {
+            Some(cluster) if item.start_time <= cluster_end => {
+                cluster.push((engine, item));
+                cluster_end = cluster_end.max(item.end_time);
+            }
+            _ => {
+                clusters.push(vec![(engine, item)]);
+                cluster_end = item.end_time;
+            }
+        }
+    }
+    clusters
+}
+
+fn cluster_engines(cluster: &[(&str, &FlaggedItem)]) -> Vec<String> {
+    let mut engines = cluster.iter().map(|(engine, _)| engine.to_string()).collect::<Vec<_>>();
+    engines.sort();
+    engines.dedup();
+    engines
+}
+
+/// Combines per-engine analyses of one video. Each span of overlapping items
+/// becomes one item covering the span, with the highest priority, that
+/// item's text, category and rule, every distinct reason and the engines
+/// that agreed. Reviews are not carried into the merged document.
+pub fn merge_analyses(documents: &[AnalysisDocument], mode: MergeMode) -> Result<AnalysisDocument, String> {
+    ensure_distinct_engines(documents)?;
+
+    let flagged = overlap_clusters(documents)
+        .into_iter()
+        .filter_map(|cluster| {
+            let engines = cluster_engines(&cluster);
+            if mode == MergeMode::Intersection && engines.len() < documents.len() {
+                return None;
+            }
+            let (_, lead) = cluster.iter().min_by_key(|(_, item)| item.priority)?;
+            let mut reasons: Vec<&str> = Vec::new();
+            for (_, item) in &cluster {
+                if !reasons.contains(&item.reason.as_str()) {
+                    reasons.push(&item.reason);
+                }
+            }
+
+            Some(FlaggedItem {
+                start_time: cluster.iter().map(|(_, item)| item.start_time).fold(f64::INFINITY, f64::min),
+                end_time: cluster.iter().map(|(_, item)| item.end_time).fold(f64::NEG_INFINITY, f64::max),
+                text: lead.text.clone(),
+                reason: reasons.join(" / "),
+                priority: lead.priority,
+                category: lead.category.clone(),
+                rule_id: lead.rule_id.clone(),
+                review: None,
+                engines,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let engine_names = documents
+        .iter()
+        .map(|document| document.engine.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mode_name = match mode {
+        MergeMode::Union => "union",
+        MergeMode::Intersection => "intersection",
+    };
+    Ok(AnalysisDocument {
+        schema_version: ANALYSIS_SCHEMA_VERSION,
+        engine: "merged".to_string(),
+        // The newest source analysis; ISO-8601 strings in one zone sort chronologically.
+        created_at: documents
+            .iter()
+            .map(|document| document.created_at.clone())
+            .max()
+            .unwrap_or_default(),
+        video_file_name: documents[0].video_file_name.clone(),
+        summary: format!("Merged {} item(s) from {engine_names} ({mode_name}).", flagged.len()),
+        flagged,
+        // Kept only when every source analysis ran with the same profile.
+        profile: documents[0].profile.clone().filter(|profile| {
+            documents
+                .iter()
+                .all(|document| document.profile.as_ref() == Some(profile))
+        }),
+    })
+}
+
+pub fn diff_analyses(documents: &[AnalysisDocument]) -> Result<AnalysisDiff, String> {
+    ensure_distinct_engines(documents)?;
+
+    let clusters = overlap_clusters(documents);
+    let shared_count = clusters
+        .iter()
+        .filter(|cluster| cluster_engines(cluster).len() > 1)
+        .count();
+    let unique = documents
+        .iter()
+        .map(|document| EngineOnlyItems {
+            engine: document.engine.clone(),
+            flagged: clusters
+                .iter()
+                .filter(|cluster| cluster.iter().all(|(engine, _)| *engine == document.engine))
+                .flat_map(|cluster| cluster.iter().map(|(_, item)| (*item).clone()))
+                .collect(),
+        })
+        .collect();
+
+    Ok(AnalysisDiff {
+        engines: documents.iter().map(|document| document.engine.clone()).collect(),
+        shared_count,
+        unique,
+    })
+}
+
+/// Records a reviewer decision on `flagged[index]`. Edits apply on top of the
+/// current values; clearing the review (`decision: None`) restores the
+/// engine's values of an edited item.
+pub fn apply_review(
+    document: &mut AnalysisDocument,
+    index: usize,
+    decision: Option<ReviewDecision>,
+    edit: &FlagEdit,
+    reviewed_at_epoch_seconds: u64,
+) -> Result<(), String> {
+    let item_count = document.flagged.len();
+    let item = document
+        .flagged
+        .get_mut(index)
+        .ok_or_else(|| format!("Flagged item {index} does not exist (the sidecar has {item_count})."))?;
+    if !edit.is_empty() && decision != Some(ReviewDecision::Edited) {
+        return Err("Only edited reviews can change times or priority.".to_string());
+    }
+
+    let original = item.review.take().and_then(|review| review.original);
+    let Some(decision) = decision else {
+        if let Some(original) = original {
+            item.start_time = original.start_time;
+            item.end_time = original.end_time;
+            item.priority = original.priority;
+        }
+        return document.validate();
+    };
+
+    if decision == ReviewDecision::Edited {
+        if edit.is_empty() {
+            return Err("Edited reviews need a new startTime, endTime or priority.".to_string());
+        }
+        let original = original.unwrap_or(OriginalFlagValues {
+            start_time: item.start_time,
+            end_time: item.end_time,
+            priority: item.priority,
+        });
+        item.start_time = edit.start_time.unwrap_or(item.start_time);
+        item.end_time = edit.end_time.unwrap_or(item.end_time);
+        item.priority = edit.priority.unwrap_or(item.priority);
+        item.review = Some(FlagReview {
+            decision,
+            reviewed_at_epoch_seconds,
+            original: Some(original),
+        });
+    } else {
+        item.review = Some(FlagReview {
+            decision,
+            reviewed_at_epoch_seconds,
+            original,
+        });
+    }
+
+    document.validate()
+}
+
+pub fn parse_analysis_document(content: &str) -> Result<AnalysisDocument, String> {
+    let document: AnalysisDocument = serde_json::from_str(content).map_err(|error| error.to_string())?;
+    document.validate()?;
+    Ok(document)
+}
+
+pub fn read_analysis_document(path: &Path) -> Result<AnalysisDocument, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|error| format!("Failed reading analysis sidecar {}: {error}", path.display()))?;
+    parse_analysis_document(&content)
+        .map_err(|error| format!("Invalid analysis sidecar {}: {error}", path.display()))
+}
+
+fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut file_name = path.file_name().map(OsString::from).unwrap_or_default();
+    file_name.push(suffix);
+    path.with_file_name(file_name)
+}
+
+/// Validates `document` and replaces `path` atomically, keeping the previous
+/// version as `<name>.analysis.json.bak`.
+pub fn write_analysis_document(path: &Path, document: &AnalysisDocument) -> Result<(), String> {
+    replace_analysis_document(path, document, true)
+}
+
+fn replace_analysis_document(path: &Path, document: &AnalysisDocument, keep_backup: bool) -> Result<(), String> {
+    document.validate()?;
+    let content = serde_json::to_string(document)
+        .map_err(|error| format!("Failed serializing analysis sidecar: {error}"))?;
+
+    let temp_path = sibling_path(path, ".tmp");
+    let mut temp_file = fs::File::create(&temp_path)
+        .map_err(|error| format!("Failed creating temp analysis sidecar {}: {error}", temp_path.display()))?;
+    temp_file
+        .write_all(content.as_bytes())
+        .and_then(|_| temp_file.sync_all())
+        .map_err(|error| format!("Failed writing temp analysis sidecar {}: {error}", temp_path.display()))?;
+    drop(temp_file);
+
+    if keep_backup && path.exists() {
+        let backup_path = sibling_path(path, ".bak");
+        fs::copy(path, &backup_path)
+            .map_err(|error| format!("Failed backing up analysis sidecar to {}: {error}", backup_path.display()))?;
+    }
+    fs::rename(&temp_path, path)
+        .map_err(|error| format!("Failed renaming analysis sidecar {}: {error}", path.display()))
+}
+
+/// Carries the reviewer decisions of the sidecar the worker replaced (kept at
+/// `previous_path`) over to the sidecars it just wrote. The backup already
+/// holds the previous version, so the rewrite does not make another one.
+/// Returns how many carried over.
+pub fn carry_over_reviews_from(previous_path: &Path, paths: &[&Path]) -> Result<usize, String> {
+    let previous = read_analysis_document(previous_path)?;
+    let mut carried = 0;
+    for path in paths {
+        let mut document = read_analysis_document(path)?;
+        let carried_into_path = carry_over_reviews(&previous, &mut document.flagged);
+        if carried_into_path > 0 {
+            replace_analysis_document(path, &document, false)?;
+        }
+        carried = carried.max(carried_into_path);
+    }
+    Ok(carried)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        time::{Duration, UNIX_EPOCH},
+    };
+
+    use crate::ids::unique_temp_dir;
+
+    use super::{
+        analysis_file_stem, apply_review, carry_over_reviews, carry_over_reviews_from, diff_analyses,
+        format_created_at, merge_analyses, parse_analysis_document, read_analysis_document,
+        write_analysis_document, AnalysisDocument, FlagEdit, FlagPriority, FlaggedItem, MergeMode,
+        ProfileRef, ReviewDecision,
+    };
+
+    const LEGACY_SIDECAR: &str = r#"{
+        "engine": "blacklist",
+        "createdAt": "2026-01-01T00:00:00+00:00",
+        "videoFileName": "clip.mp4",
+        "summary": "Flagged 1 subtitle item(s).",
+        "flagged": [{
+            "startTime": 1.0, "endTime": 2.0, "text": "damn", "reason": "Profanity.",
+            "priority": "medium", "category": "language", "ruleId": "profanity"
+        }]
+    }"#;
+
+    #[test]
+    fn should_read_sidecars_without_schema_version_as_version_one() {
+        let document = parse_analysis_document(LEGACY_SIDECAR).unwrap();
+
+        assert_eq!(document.schema_version, 1);
+        assert_eq!(document.flagged[0].priority, FlagPriority::Medium);
+        assert_eq!(document.flagged[0].rule_id, "profanity");
+        assert!(document.profile.is_none());
+    }
+
+    #[test]
+    fn should_read_the_profile_id_and_name_of_a_sidecar() {
+        let sidecar = LEGACY_SIDECAR.replacen('{', r#"{ "profile": { "id": "default", "name": "Default" },"#, 1);
+        let document = parse_analysis_document(&sidecar).unwrap();
+
+        assert_eq!(
+            document.profile,
+            Some(ProfileRef {
+                id: "default".to_string(),
+                name: "Default".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn should_report_invalid_fields_precisely() {
+        let bad_priority = LEGACY_SIDECAR.replace("\"medium\"", "\"urgent\"");
+        let missing_rule = LEGACY_SIDECAR.replace(", \"ruleId\": \"profanity\"", "");
+        let backwards = LEGACY_SIDECAR.replace("\"endTime\": 2.0", "\"endTime\": 0.5");
+
+        assert!(parse_analysis_document(&bad_priority).unwrap_err().contains("unknown variant `urgent`"));
+        assert!(parse_analysis_document(&missing_rule).unwrap_err().contains("missing field `ruleId`"));
+        assert_eq!(
+            parse_analysis_document(&backwards).unwrap_err(),
+            "flagged[0].endTime (0.5) must not be before startTime (1)."
+        );
+    }
+
+    #[test]
+    fn should_reject_newer_schema_versions() {
+        let newer = LEGACY_SIDECAR.replacen('{', "{\"schemaVersion\": 2,", 1);
+
+        assert!(parse_analysis_document(&newer).unwrap_err().contains("Unsupported schemaVersion 2"));
+    }
+
+    #[test]
+    fn should_order_priorities_from_high_to_low() {
+        assert!(FlagPriority::High < FlagPriority::Medium);
+        assert!(FlagPriority::Medium < FlagPriority::Low);
+        assert_eq!(FlagPriority::parse("low"), Some(FlagPriority::Low));
+        assert_eq!(FlagPriority::parse("High"), None);
+    }
+
+    #[test]
+    fn should_record_edits_and_restore_them_when_the_review_is_cleared() {
+        let mut document = parse_analysis_document(LEGACY_SIDECAR).unwrap();
+        let edit = FlagEdit {
+            end_time: Some(3.0),
+            priority: Some(FlagPriority::High),
+            ..FlagEdit::default()
+        };
+
+        apply_review(&mut document, 0, Some(ReviewDecision::Edited), &edit, 100).unwrap();
+        apply_review(&mut document, 0, Some(ReviewDecision::Confirmed), &FlagEdit::default(), 200).unwrap();
+
+        let item = &document.flagged[0];
+        let review = item.review.as_ref().unwrap();
+        assert_eq!((item.end_time, item.priority), (3.0, FlagPriority::High));
+        assert_eq!((review.decision, review.reviewed_at_epoch_seconds), (ReviewDecision::Confirmed, 200));
+        assert_eq!(review.original.as_ref().unwrap().end_time, 2.0);
+
+        apply_review(&mut document, 0, None, &FlagEdit::default(), 300).unwrap();
+        let item = &document.flagged[0];
+        assert_eq!((item.end_time, item.priority, item.review.is_none()), (2.0, FlagPriority::Medium, true));
+    }
+
+    #[test]
+    fn should_reject_invalid_reviews() {
+        let mut document = parse_analysis_document(LEGACY_SIDECAR).unwrap();
+        let backwards = FlagEdit {
+            end_time: Some(0.5),
+            ..FlagEdit::default()
+        };
+
+        assert!(apply_review(&mut document, 3, Some(ReviewDecision::Dismissed), &FlagEdit::default(), 1)
+            .unwrap_err()
+            .contains("does not exist"));
+        assert!(apply_review(&mut document, 0, Some(ReviewDecision::Dismissed), &backwards, 1).is_err());
+        assert!(apply_review(&mut document, 0, Some(ReviewDecision::Edited), &FlagEdit::default(), 1).is_err());
+        assert!(apply_review(&mut document, 0, Some(ReviewDecision::Edited), &backwards, 1).is_err());
+    }
+
+    #[test]
+    fn should_write_atomically_and_keep_a_backup() {
+        let base_dir = unique_temp_dir("analysis");
+        fs::create_dir_all(&base_dir).unwrap();
+        let path = base_dir.join("clip.analysis.json");
+        fs::write(&path, LEGACY_SIDECAR).unwrap();
+        let mut document = parse_analysis_document(LEGACY_SIDECAR).unwrap();
+        document.summary = "Reviewed.".to_string();
+
+        write_analysis_document(&path, &document).unwrap();
+        document.flagged[0].end_time = 0.0;
+        let invalid = write_analysis_document(&path, &document);
+
+        let written = read_analysis_document(&path).unwrap();
+        let backup = fs::read_to_string(base_dir.join("clip.analysis.json.bak")).unwrap();
+        let leftovers = fs::read_dir(&base_dir).unwrap().count();
+        fs::remove_dir_all(&base_dir).unwrap();
+
+        assert!(invalid.is_err());
+        assert_eq!(written.summary, "Reviewed.");
+        assert_eq!(backup, LEGACY_SIDECAR);
+        assert_eq!(leftovers, 2);
+    }
+
+    fn item(start_time: f64, end_time: f64, priority: FlagPriority, reason: &str) -> FlaggedItem {
+        FlaggedItem {
+            start_time,
+            end_time,
+            text: reason.to_lowercase(),
+            reason: reason.to_string(),
+            priority,
+            category: "language".to_string(),
+            rule_id: "rule".to_string(),
+            review: None,
+            engines: Vec::new(),
+        }
+    }
+
+    fn document(engine: &str, flagged: Vec<FlaggedItem>) -> AnalysisDocument {
+        AnalysisDocument {
+            schema_version: 1,
+            engine: engine.to_string(),
+            created_at: format!("2026-01-0{}T00:00:00+00:00", flagged.len()),
+            video_file_name: "clip.mp4".to_string(),
+            summary: String::new(),
+            flagged,
+            profile: None,
+        }
+    }
+
+    fn engine_documents() -> Vec<AnalysisDocument> {
+        vec![
+            document(
+                "blacklist",
+                vec![item(1.0, 2.0, FlagPriority::Medium, "Word"), item(10.0, 11.0, FlagPriority::Low, "Other")],
+            ),
+            document(
+                "gemini",
+                vec![item(1.5, 3.0, FlagPriority::High, "Llm"), item(20.0, 21.0, FlagPriority::High, "Late")],
+            ),
+        ]
+    }
+
+    #[test]
+    fn should_merge_overlapping_items_and_record_agreeing_engines() {
+        let union = merge_analyses(&engine_documents(), MergeMode::Union).unwrap();
+        let intersection = merge_analyses(&engine_documents(), MergeMode::Intersection).unwrap();
+
+        assert_eq!(union.engine, "merged");
+        assert_eq!(union.created_at, "2026-01-02T00:00:00+00:00");
+        assert_eq!(union.flagged.len(), 3);
+        let agreed = &intersection.flagged[..];
+        assert_eq!(agreed.len(), 1);
+        assert_eq!((agreed[0].start_time, agreed[0].end_time), (1.0, 3.0));
+        assert_eq!((agreed[0].priority, agreed[0].reason.as_str()), (FlagPriority::High, "Word / Llm"));
+        assert_eq!(agreed[0].engines, vec!["blacklist", "gemini"]);
+    }
+
+    #[test]
+    fn should_list_items_unique_to_each_engine() {
+        let diff = diff_analyses(&engine_documents()).unwrap();
+
+        assert_eq!(diff.shared_count, 1);
+        assert_eq!(diff.unique[0].engine, "blacklist");
+        assert_eq!(diff.unique[0].flagged[0].start_time, 10.0);
+        assert_eq!(diff.unique[1].flagged[0].start_time, 20.0);
+    }
+
+    #[test]
+    fn should_require_distinct_engines() {
+        let documents = vec![document("gemini", Vec::new()), document("gemini", Vec::new())];
+
+        assert!(diff_analyses(&documents).unwrap_err().contains("gemini appears twice"));
+        assert!(merge_analyses(&documents[..1], MergeMode::Union).is_err());
+    }
+
+    #[test]
+    fn should_read_stems_of_engine_specific_analysis_files() {
+        assert_eq!(analysis_file_stem("ep.analysis.json"), Some("ep"));
+        assert_eq!(analysis_file_stem("ep.analysis.nova_pro.json"), Some("ep"));
+        assert_eq!(analysis_file_stem("ep.gemini.json"), None);
+        assert_eq!(analysis_file_stem(".analysis.json"), None);
+    }
+
+    #[test]
+    fn should_format_created_at_like_the_worker() {
+        let time = UNIX_EPOCH + Duration::from_micros(1_709_251_199_123_456);
+
+        assert_eq!(format_created_at(time), "2024-02-29T23:59:59.123456+00:00");
+        assert_eq!(format_created_at(UNIX_EPOCH), "1970-01-01T00:00:00.000000+00:00");
+    }
+
+    #[test]
+    fn should_carry_reviews_over_to_reanalyzed_items() {
+        let mut previous = parse_analysis_document(LEGACY_SIDECAR).unwrap();
+        let edit = FlagEdit {
+            end_time: Some(4.0),
+            ..FlagEdit::default()
+        };
+        apply_review(&mut previous, 0, Some(ReviewDecision::Edited), &edit, 7).unwrap();
+        let mut flagged = parse_analysis_document(LEGACY_SIDECAR).unwrap().flagged;
+        flagged.push(item(9.0, 10.0, FlagPriority::High, "New"));
+
+        assert_eq!(carry_over_reviews(&previous, &mut flagged), 1);
+        assert_eq!(flagged[0].end_time, 4.0);
+        assert_eq!(flagged[0].review_decision(), Some(ReviewDecision::Edited));
+        assert!(flagged[1].review.is_none());
+    }
+
+    #[test]
+    fn should_carry_reviews_from_the_replaced_sidecar_in_place() {
+        let base_dir = unique_temp_dir("analysis-carry-over");
+        fs::create_dir_all(&base_dir).unwrap();
+        let path = base_dir.join("clip.analysis.json");
+        let engine_path = base_dir.join("clip.analysis.blacklist.json");
+        let backup_path = base_dir.join("clip.analysis.json.bak");
+        let mut previous = parse_analysis_document(LEGACY_SIDECAR).unwrap();
+        apply_review(&mut previous, 0, Some(ReviewDecision::Dismissed), &FlagEdit::default(), 7).unwrap();
+        write_analysis_document(&backup_path, &previous).unwrap();
+        fs::write(&path, LEGACY_SIDECAR).unwrap();
+        fs::write(&engine_path, LEGACY_SIDECAR).unwrap();
+
+        let carried = carry_over_reviews_from(&backup_path, &[&path, &engine_path]).unwrap();
+
+        let written = read_analysis_document(&path).unwrap();
+        let engine_written = read_analysis_document(&engine_path).unwrap();
+        let backup = read_analysis_document(&backup_path).unwrap();
+        fs::remove_dir_all(&base_dir).unwrap();
+
+        assert_eq!(carried, 1);
+        assert_eq!(written.flagged[0].review_decision(), Some(ReviewDecision::Dismissed));
+        assert_eq!(engine_written.flagged[0].review_decision(), Some(ReviewDecision::Dismissed));
+        assert_eq!(backup.flagged[0].review_decision(), Some(ReviewDecision::Dismissed));
+    }
+}
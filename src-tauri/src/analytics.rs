@@ -197,12 +197,13 @@ fn create_batch_record(batch: &BatchState, started_at_epoch_seconds: Option<u64>
 
 fn create_task_record(task: &TaskState, started_at_epoch_seconds: Option<u64>) -> AnalyticsWorkRecord {
     let summary = task.summary.clone().unwrap_or_default();
-    let flagged_item_count = if task.task_kind == TaskKind::Flag {
+    let reports_flags = matches!(task.task_kind, TaskKind::Flag | TaskKind::Pipeline);
+    let flagged_item_count = if reports_flags {
         task.jobs.iter().map(|job| flagged_count_from_artifacts(&job.artifacts)).sum()
     } else {
         0
     };
-    let flagged_file_count = if task.task_kind == TaskKind::Flag {
+    let flagged_file_count = if reports_flags {
         task.jobs
             .iter()
             .filter(|job| flagged_count_from_artifacts(&job.artifacts) > 0)
@@ -229,6 +230,7 @@ fn task_kind_to_analytics_kind(task_kind: &TaskKind) -> AnalyticsTaskKind {
         TaskKind::Cut => AnalyticsTaskKind::Cut,
         TaskKind::Flag => AnalyticsTaskKind::Flag,
         TaskKind::Transcription => AnalyticsTaskKind::Transcription,
+        TaskKind::Pipeline => AnalyticsTaskKind::Pipeline,
     }
 }
 
@@ -255,6 +257,7 @@ fn snapshot_from_store(store: &AnalyticsStore) -> AnalyticsSnapshot {
             AnalyticsTaskKind::Flag => totals.total_flag_jobs += record.job_count,
             AnalyticsTaskKind::RemoveMusic => totals.total_remove_music_jobs += record.job_count,
             AnalyticsTaskKind::Transcription => totals.total_transcription_jobs += record.job_count,
+            AnalyticsTaskKind::Pipeline => totals.total_pipeline_jobs += record.job_count,
         }
 
         totals
@@ -274,6 +277,7 @@ fn snapshot_from_store(store: &AnalyticsStore) -> AnalyticsSnapshot {
             ),
             breakdown_entry(AnalyticsTaskKind::Flag, totals.total_flag_jobs, "Detection Runs"),
             breakdown_entry(AnalyticsTaskKind::Cut, totals.total_cut_jobs, "Cut Exports"),
+            breakdown_entry(AnalyticsTaskKind::Pipeline, totals.total_pipeline_jobs, "Pipelines"),
         ],
         recent_runs: store.records.len(),
         totals,
@@ -306,10 +310,9 @@ pub fn record_task_completion(
 
 #[cfg(test)]
 mod tests {
-    use std::{path::PathBuf, sync::Arc};
-
-    use uuid::Uuid;
+    use std::sync::Arc;
 
+    use crate::ids::unique_temp_dir;
     use crate::types::{
         AnalyticsTaskKind, BatchState, BatchStatus, BatchSummary, JobRecord, JobStatus,
         TaskJobRecord, TaskJobStatus, TaskKind, TaskState, TaskStatus, TaskSummary,
@@ -320,10 +323,6 @@ mod tests {
         read_store, snapshot_from_store,
     };
 
-    fn temp_path() -> PathBuf {
-        std::env::temp_dir().join(format!("al-iyaal-kids-analytics-{}", Uuid::new_v4()))
-    }
-
     fn seed_batch() -> BatchState {
         BatchState {
             batch_id: "batch-1".to_string(),
@@ -340,8 +339,12 @@ mod tests {
             summary: Some(BatchSummary {
                 cancelled: 0,
                 failed: 0,
+                interrupted: 0,
                 ok: 1,
             }),
+            created_at_epoch_seconds: 0,
+            parameters: None,
+            parent_task_id: None,
         }
     }
 
@@ -359,22 +362,27 @@ mod tests {
                 logs: vec![],
                 output_path: Some("/tmp/sample.analysis.json".to_string()),
                 progress_pct: 100,
+                stage: None,
                 status: TaskJobStatus::Completed,
             }],
             status: TaskStatus::Completed,
             summary: Some(TaskSummary {
                 cancelled: 0,
                 failed: 0,
+                interrupted: 0,
                 ok: 1,
             }),
             task_id: "task-1".to_string(),
             task_kind: TaskKind::Flag,
+            created_at_epoch_seconds: 0,
+            parameters: None,
+            parent_task_id: None,
         }
     }
 
     #[test]
     fn should_build_an_analytics_snapshot_from_persisted_task_history() {
-        let base_dir = temp_path();
+        let base_dir = unique_temp_dir("analytics");
         let path = analytics_store_path_from_dir(&base_dir);
 
         append_record(&path, create_batch_record(&seed_batch(), Some(1))).unwrap();
@@ -393,7 +401,7 @@ mod tests {
 
     #[test]
     fn should_append_analytics_records_without_losing_concurrent_writes() {
-        let base_dir = temp_path();
+        let base_dir = unique_temp_dir("analytics");
         let path = analytics_store_path_from_dir(&base_dir);
         let shared_path = Arc::new(path.clone());
 
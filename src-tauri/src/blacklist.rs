@@ -0,0 +1,1227 @@
+use std::{
+    collections::HashSet,
+    fs,
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::SystemTime,
+};
+
+use regex::{Regex, RegexBuilder};
+use regex_syntax::ast::{
+    self, parse::Parser, Assertion, AssertionKind, Ast, ClassSetBinaryOp, ClassSetItem, Flag, Flags, FlagsItemKind,
+    GroupKind, LiteralKind,
+};
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::{
+    analysis::{
+        carry_over_reviews, format_created_at, read_analysis_document, write_analysis_document, AnalysisDocument,
+        FlagPriority, FlaggedItem, ANALYSIS_SCHEMA_VERSION,
+    },
+    file_discovery::find_subtitle_sidecar,
+    ids::to_job_id,
+    profiles::ProfileRef,
+    protocol::WorkerEvent,
+    srt::SubtitleCue,
+    state::AppState,
+    subtitles::{parse_subtitles, SubtitleFormat},
+    text_normalization::{is_python_alphanumeric, normalize_text, normalize_with_origins},
+    types::{ModerationRule, ModerationSettings, PatternMatchType, RulePattern, TaskSummary, TextNormalization},
+    worker::handle_worker_event,
+};
+
+pub const ENGINE: &str = "blacklist";
+const TASK_KIND: &str = "flag";
+/// What the worker matches when `better_profanity` is not installed.
+const FALLBACK_PROFANITY_WORDS: [&str; 4] = ["damn", "hell", "crap", "stupid"];
+const FALLBACK_PROFANITY_NOTE: &str =
+    "Profanity was checked against a 4-word fallback list because the worker runtime is not installed.";
+
+/// Python's `\W+`, which unlike the regex crate's counts combining marks
+/// such as harakat as separators.
+const NON_WORD_RUN: &str = r"[^\p{L}\p{N}_]+";
+
+enum PatternMatcher {
+    Substring(String),
+    Regex(Regex),
+    /// A pattern the worker wraps in `\b`, with the boundaries checked the way
+    /// Python's `\b` places them rather than the regex crate's.
+    WordBounded { regex: Regex, bounded_end: bool },
+}
+
+impl PatternMatcher {
+    /// `None` for blank patterns, which match nothing. Non-regex patterns are
+    /// normalized like the text they are matched against.
+    fn compile(pattern: &RulePattern, normalization: &TextNormalization) -> Result<Option<Self>, regex::Error> {
+        let value = pattern.value.trim();
+        if value.is_empty() {
+            return Ok(None);
+        }
+        let normalized = normalize_text(value, normalization);
+        let (expression, bounded_end) = match pattern.match_type {
+            PatternMatchType::Substring => return Ok(Some(Self::Substring(normalized))),
+            PatternMatchType::WholeWord => (regex::escape(&normalized), Some(true)),
+            PatternMatchType::Phrase => {
+                let words = normalized.split_whitespace().map(regex::escape).collect::<Vec<_>>();
+                (words.join(NON_WORD_RUN), Some(true))
+            }
+            PatternMatchType::Stem => (regex::escape(&normalized), Some(false)),
+            PatternMatchType::Regex => (value.to_string(), None),
+        };
+        let regex = RegexBuilder::new(&expression)
+            .case_insensitive(normalization.fold_case)
+            .build()?;
+        Ok(Some(match bounded_end {
+            Some(bounded_end) => Self::WordBounded { regex, bounded_end },
+            None => Self::Regex(regex),
+        }))
+    }
+
+    fn find_spans(&self, normalized_text: &str) -> Vec<Range<usize>> {
+        match self {
+            Self::Substring(pattern) => normalized_text
+                .match_indices(pattern.as_str())
+                .map(|(start, matched)| start..start + matched.len())
+                .collect(),
+            Self::Regex(regex) => regex.find_iter(normalized_text).map(|found| found.range()).collect(),
+            Self::WordBounded { regex, bounded_end } => {
+                let mut spans = Vec::new();
+                let mut from = 0;
+                while let Some(found) = regex.find_at(normalized_text, from) {
+                    let span = found.range();
+                    let next_start = normalized_text[span.start..]
+                        .chars()
+                        .next()
+                        .map_or(normalized_text.len() + 1, |character| span.start + character.len_utf8());
+                    if is_word_boundary(normalized_text, span.start)
+                        && (!bounded_end || is_word_boundary(normalized_text, span.end))
+                    {
+                        from = span.end.max(next_start);
+                        spans.push(span);
+                    } else {
+                        from = next_start;
+                    }
+                    if from > normalized_text.len() {
+                        break;
+                    }
+                }
+                spans
+            }
+        }
+    }
+}
+
+/// Python's `\b`: whether exactly one side of `offset` is a word character.
+fn is_word_boundary(text: &str, offset: usize) -> bool {
+    let before = text[..offset].chars().next_back().is_some_and(is_word_character);
+    let after = text[offset..].chars().next().is_some_and(is_word_character);
+    before != after
+}
+
+struct Rule {
+    rule_id: String,
+    category: String,
+    priority: FlagPriority,
+    reason: String,
+    patterns: Vec<PatternMatcher>,
+    exclusions: Vec<PatternMatcher>,
+}
+
+impl Rule {
+    fn is_match(&self, normalized_text: &str) -> bool {
+        !self.find_spans(normalized_text).is_empty()
+    }
+
+    /// Where the rule's patterns match, minus the spans an exclusion overlaps.
+    fn find_spans(&self, normalized_text: &str) -> Vec<Range<usize>> {
+        let excluded = self
+            .exclusions
+            .iter()
+            .flat_map(|exclusion| exclusion.find_spans(normalized_text))
+            .collect::<Vec<_>>();
+        let mut spans = self
+            .patterns
+            .iter()
+            .flat_map(|pattern| pattern.find_spans(normalized_text))
+            .filter(|span| {
+                !excluded
+                    .iter()
+                    .any(|exclusion| span.start < exclusion.end && exclusion.start < span.end)
+            })
+            .collect::<Vec<_>>();
+        spans.sort_by_key(|span| (span.start, span.end));
+        spans.dedup();
+        spans
+    }
+}
+
+fn rule(rule_id: &str, category: &str, priority: FlagPriority, reason: &str, patterns: &[&str]) -> Rule {
+    Rule {
+        rule_id: rule_id.to_string(),
+        category: category.to_string(),
+        priority,
+        reason: reason.to_string(),
+        patterns: patterns
+            .iter()
+            .map(|pattern| PatternMatcher::Substring(pattern.to_string()))
+            .collect(),
+        exclusions: Vec::new(),
+    }
+}
+
+/// The worker's built-in rules, used when the settings have none.
+fn default_rules() -> Vec<Rule> {
+    vec![
+        rule(
+            "aqeedah_christmas",
+            "aqeedah",
+            FlagPriority::High,
+            "Promotes non-Islamic religious celebration.",
+            &["christmas", "xmas", "easter"],
+        ),
+        rule(
+            "aqeedah_shirk",
+            "aqeedah",
+            FlagPriority::High,
+            "Contains shirk-related expressions.",
+            &["worship", "pray to", "god of", "goddess"],
+        ),
+        rule(
+            "magic_sorcery",
+            "magic",
+            FlagPriority::High,
+            "References magic or sorcery.",
+            &["spell", "sorcery", "magic ritual", "witchcraft", "summon"],
+        ),
+        rule(
+            "romance_dating",
+            "relationships",
+            FlagPriority::Medium,
+            "References romantic relationship themes.",
+            &["boyfriend", "girlfriend", "date", "kiss", "romantic"],
+        ),
+        rule(
+            "violent_language",
+            "violence",
+            FlagPriority::Medium,
+            "Contains violent phrasing.",
+            &["kill", "murder", "stab", "blood", "beat up"],
+        ),
+    ]
+}
+
+/// Skips patterns that fail to compile; `validate_rules` rejects them when
+/// settings are saved, but stored runs may predate that.
+fn compile_patterns(patterns: &[RulePattern], normalization: &TextNormalization) -> Vec<PatternMatcher> {
+    patterns
+        .iter()
+        .filter_map(|pattern| PatternMatcher::compile(pattern, normalization).ok().flatten())
+        .collect()
+}
+
+pub fn validate_rules(rules: &[ModerationRule]) -> Result<(), String> {
+    for rule in rules {
+        for pattern in rule.patterns.iter().chain(&rule.exclusions) {
+            PatternMatcher::compile(pattern, &TextNormalization::default()).map_err(|error| {
+                format!(
+                    "Rule {} has an invalid regex pattern {:?}: {error}",
+                    rule.rule_id, pattern.value
+                )
+            })?;
+            if pattern.match_type == PatternMatchType::Regex {
+                if let Some(syntax) = python_unsupported_syntax(pattern.value.trim()) {
+                    return Err(format!(
+                        "Rule {} has a regex pattern {:?} using {syntax}, which the worker's Python engine does not support.",
+                        rule.rule_id, pattern.value
+                    ));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Pipeline flag stages run the rules with Python's `re` in the worker, so a
+/// regex must also avoid the syntax only the `regex` crate understands.
+/// Python-only syntax (lookaround, backreferences) already fails to compile.
+fn python_unsupported_syntax(expression: &str) -> Option<&'static str> {
+    let parsed = Parser::new().parse(expression).ok()?;
+    ast::visit(&parsed, PythonDialect).err()
+}
+
+struct PythonDialect;
+
+impl PythonDialect {
+    fn check_flags(flags: &Flags, scoped: bool) -> Result<(), &'static str> {
+        for item in &flags.items {
+            match item.kind {
+                FlagsItemKind::Flag(Flag::SwapGreed) => return Err("the U flag"),
+                FlagsItemKind::Flag(Flag::CRLF) => return Err("the R flag"),
+                FlagsItemKind::Negation if !scoped => return Err("flags turned off outside a group like (?-i:...)"),
+                _ => {}
+            }
+        }
+        if flags.flag_state(Flag::Unicode) == Some(false) {
+            return Err("a disabled u flag");
+        }
+        Ok(())
+    }
+}
+
+impl ast::Visitor for PythonDialect {
+    type Output = ();
+    type Err = &'static str;
+
+    fn finish(self) -> Result<(), &'static str> {
+        Ok(())
+    }
+
+    fn visit_pre(&mut self, node: &Ast) -> Result<(), &'static str> {
+        match node {
+            Ast::Flags(set_flags) if set_flags.span.start.offset > 0 => {
+                Err("inline flags after the start of the pattern (use a group like (?i:...))")
+            }
+            Ast::Flags(set_flags) => Self::check_flags(&set_flags.flags, false),
+            Ast::Group(group) => match &group.kind {
+                GroupKind::CaptureName {
+                    starts_with_p: false, ..
+                } => Err("a named group without P (use (?P<name>...))"),
+                GroupKind::NonCapturing(flags) => Self::check_flags(flags, true),
+                _ => Ok(()),
+            },
+            Ast::Assertion(assertion) => check_assertion(assertion),
+            Ast::Literal(literal) if matches!(literal.kind, LiteralKind::HexBrace(_)) => {
+                Err(r"a braced escape like \x{...}")
+            }
+            Ast::ClassUnicode(_) => Err(r"a Unicode class like \p{L}"),
+            _ => Ok(()),
+        }
+    }
+
+    fn visit_class_set_item_pre(&mut self, item: &ClassSetItem) -> Result<(), &'static str> {
+        match item {
+            ClassSetItem::Ascii(_) => Err("a POSIX class like [[:alpha:]]"),
+            ClassSetItem::Unicode(_) => Err(r"a Unicode class like \p{L}"),
+            ClassSetItem::Bracketed(_) => Err("a nested character class"),
+            ClassSetItem::Literal(literal) if matches!(literal.kind, LiteralKind::HexBrace(_)) => {
+                Err(r"a braced escape like \x{...}")
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn visit_class_set_binary_op_pre(&mut self, _op: &ClassSetBinaryOp) -> Result<(), &'static str> {
+        Err("a character class operation like && or --")
+    }
+}
+
+fn check_assertion(assertion: &Assertion) -> Result<(), &'static str> {
+    match assertion.kind {
+        AssertionKind::StartLine
+        | AssertionKind::EndLine
+        | AssertionKind::StartText
+        | AssertionKind::WordBoundary
+        | AssertionKind::NotWordBoundary => Ok(()),
+        AssertionKind::EndText => Err(r"\z"),
+        _ => Err(r"a word boundary other than \b or \B"),
+    }
+}
+
+fn normalize_rules(settings: &ModerationSettings) -> Vec<Rule> {
+    let rules = settings
+        .rules
+        .iter()
+        .map(|raw_rule| Rule {
+            rule_id: raw_rule.rule_id.clone(),
+            category: raw_rule.category.clone(),
+            priority: FlagPriority::parse(&raw_rule.priority.trim().to_lowercase()).unwrap_or(FlagPriority::Medium),
+            reason: raw_rule.reason.clone(),
+            patterns: compile_patterns(&raw_rule.patterns, &settings.normalization),
+            exclusions: compile_patterns(&raw_rule.exclusions, &settings.normalization),
+        })
+        .collect::<Vec<_>>();
+
+    if rules.is_empty() {
+        default_rules()
+    } else {
+        rules
+    }
+}
+
+fn custom_profanity_words(settings: &ModerationSettings) -> HashSet<String> {
+    settings
+        .profanity_words
+        .iter()
+        .map(|word| normalize_text(word.trim(), &settings.normalization))
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+/// Byte ranges of the runs of characters accepted by `is_word_character`.
+fn word_spans(text: &str, is_word_character: fn(char) -> bool) -> Vec<Range<usize>> {
+    let mut spans = Vec::new();
+    let mut span_start = None;
+    for (offset, character) in text.char_indices().chain([(text.len(), ' ')]) {
+        match (is_word_character(character), span_start) {
+            (true, None) => span_start = Some(offset),
+            (false, Some(start)) => {
+                spans.push(start..offset);
+                span_start = None;
+            }
+            _ => {}
+        }
+    }
+    spans
+}
+
+/// Words as the worker's `[a-zA-Z']+` finds them.
+fn is_ascii_word_character(character: char) -> bool {
+    character.is_ascii_alphabetic() || character == '\''
+}
+
+/// Words as the worker's `[\w']+` finds them, in any script.
+fn is_word_character(character: char) -> bool {
+    is_python_alphanumeric(character) || matches!(character, '_' | '\'')
+}
+
+fn custom_word_spans(normalized_text: &str, custom_words: &HashSet<String>) -> Vec<Range<usize>> {
+    word_spans(normalized_text, is_word_character)
+        .into_iter()
+        .filter(|span| custom_words.contains(&normalized_text[span.clone()]))
+        .collect()
+}
+
+fn is_profanity_word_character(character: char) -> bool {
+    is_python_alphanumeric(character) || matches!(character, '@' | '$' | '*' | '"' | '\'')
+}
+
+/// Whether `text_character` stands for `word_character`, allowing the
+/// character substitutions `better_profanity` expands its word list with.
+fn profanity_character_matches(text_character: char, word_character: char) -> bool {
+    let substitutes = match word_character {
+        'a' => "@*4",
+        'i' => "*l1",
+        'o' => "*0@",
+        'u' => "*v",
+        'v' => "*u",
+        'l' => "1",
+        'e' => "*3",
+        's' => "$5",
+        't' => "7",
+        _ => "",
+    };
+    text_character == word_character || substitutes.contains(text_character)
+}
+
+pub enum ProfanityMatcher {
+    /// `better_profanity`'s word list, matched the way the package does: per
+    /// word, with character substitutions, and across the few following words
+    /// for multi-word entries.
+    WordList { words: Vec<Vec<char>>, max_word_count: usize },
+    /// The worker's fallback when `better_profanity` is unavailable.
+    Fallback,
+}
+
+impl ProfanityMatcher {
+    pub fn from_word_list(content: &str) -> Self {
+        let words = content
+            .lines()
+            .map(str::trim)
+            .filter(|word| !word.is_empty())
+            .map(|word| word.to_lowercase().chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let max_word_count = words
+            .iter()
+            .map(|word| word.iter().filter(|character| !is_profanity_word_character(**character)).count() + 1)
+            .max()
+            .unwrap_or(1);
+        Self::WordList { words, max_word_count }
+    }
+
+    /// Uses the word list of the `better_profanity` package installed in the
+    /// worker runtime, so results match the worker's blacklist engine. Without
+    /// the runtime this is the `Fallback`, which flag runs note in their summary.
+    pub fn load(app: &AppHandle) -> Self {
+        app.path()
+            .app_data_dir()
+            .ok()
+            .and_then(|app_data_dir| find_profanity_word_list(&app_data_dir))
+            .and_then(|path| fs::read_to_string(path).ok())
+            .map(|content| Self::from_word_list(&content))
+            .unwrap_or(Self::Fallback)
+    }
+
+    pub fn is_fallback(&self) -> bool {
+        matches!(self, Self::Fallback)
+    }
+
+    fn matches_word(words: &[Vec<char>], candidate: &str) -> bool {
+        let candidate = candidate.to_lowercase().chars().collect::<Vec<_>>();
+        words.iter().any(|word| {
+            word.len() == candidate.len()
+                && candidate
+                    .iter()
+                    .zip(word)
+                    .all(|(text_character, word_character)| profanity_character_matches(*text_character, *word_character))
+        })
+    }
+
+    /// Byte ranges of the profane words, or word combinations, in `text`.
+    pub fn find_profanity(&self, text: &str) -> Vec<Range<usize>> {
+        let Self::WordList { words, max_word_count } = self else {
+            return word_spans(text, is_ascii_word_character)
+                .into_iter()
+                .filter(|span| {
+                    FALLBACK_PROFANITY_WORDS
+                        .iter()
+                        .any(|word| word.eq_ignore_ascii_case(&text[span.clone()]))
+                })
+                .collect();
+        };
+
+        let spans = word_spans(text, is_profanity_word_character);
+        (0..spans.len())
+            .filter_map(|first| {
+                let mut joined = String::new();
+                spans[first..].iter().take(*max_word_count).enumerate().find_map(|(extra, span)| {
+                    joined.push_str(&text[span.clone()]);
+                    let combined = spans[first].start..span.end;
+                    (Self::matches_word(words, &joined)
+                        || (extra > 0 && Self::matches_word(words, &text[combined.clone()])))
+                    .then_some(combined)
+                })
+            })
+            .collect()
+    }
+
+    pub fn contains_profanity(&self, text: &str) -> bool {
+        !self.find_profanity(text).is_empty()
+    }
+}
+
+fn find_profanity_word_list(app_data_dir: &Path) -> Option<PathBuf> {
+    fs::read_dir(app_data_dir.join("runtime/venv/lib"))
+        .ok()?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path().join("site-packages/better_profanity/profanity_wordlist.txt"))
+        .find(|path| path.is_file())
+}
+
+fn flagged_item(cue: &SubtitleCue, reason: &str, priority: FlagPriority, category: &str, rule_id: &str) -> FlaggedItem {
+    FlaggedItem {
+        start_time: cue.start_time,
+        end_time: cue.end_time,
+        text: cue.text.clone(),
+        reason: reason.to_string(),
+        priority,
+        category: category.to_string(),
+        rule_id: rule_id.to_string(),
+        review: None,
+        engines: Vec::new(),
+    }
+}
+
+/// Flags cues that contain profanity or any pattern of a rule, once per rule
+/// and start time, sorted by start time with higher priorities first.
+///
+/// The worker keeps a Python copy for pipeline flag stages, which flag the
+/// subtitles transcribed earlier in the same worker run; both are checked
+/// against `python-worker/tests/fixtures/blacklist_parity.json`.
+pub fn analyze_subtitles(
+    cues: &[SubtitleCue],
+    settings: &ModerationSettings,
+    profanity: &ProfanityMatcher,
+) -> (Vec<FlaggedItem>, String) {
+    let rules = normalize_rules(settings);
+    let custom_profanity_words = custom_profanity_words(settings);
+
+    let mut flagged = Vec::new();
+    let mut seen = HashSet::new();
+    for cue in cues {
+        let normalized_text = normalize_text(&cue.text, &settings.normalization);
+        let start_millis = (cue.start_time * 1000.0) as i64;
+
+        let has_custom_word = !custom_word_spans(&normalized_text, &custom_profanity_words).is_empty();
+        if (profanity.contains_profanity(&normalized_text) || has_custom_word)
+            && seen.insert((start_millis, "profanity".to_string()))
+        {
+            flagged.push(flagged_item(
+                cue,
+                "Contains profanity or offensive language.",
+                FlagPriority::Medium,
+                "language",
+                "profanity",
+            ));
+        }
+
+        for rule in &rules {
+            if !rule.is_match(&normalized_text) {
+                continue;
+            }
+            if seen.insert((start_millis, rule.rule_id.clone())) {
+                flagged.push(flagged_item(cue, &rule.reason, rule.priority, &rule.category, &rule.rule_id));
+            }
+        }
+    }
+
+    flagged.sort_by(|left, right| {
+        left.start_time
+            .total_cmp(&right.start_time)
+            .then(left.priority.cmp(&right.priority))
+    });
+
+    let summary = if flagged.is_empty() {
+        "No concerning content detected.".to_string()
+    } else {
+        let count = |priority| flagged.iter().filter(|item| item.priority == priority).count();
+        format!(
+            "Flagged {} subtitle item(s). high={}, medium={}, low={}.",
+            flagged.len(),
+            count(FlagPriority::High),
+            count(FlagPriority::Medium),
+            count(FlagPriority::Low)
+        )
+    };
+
+    (flagged, summary)
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchSpan {
+    /// Character offsets into the line's text.
+    pub start: usize,
+    pub end: usize,
+    pub matched_text: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleTestMatch {
+    /// A rule id, or `profanity` for profanity words.
+    pub rule_id: String,
+    pub category: String,
+    pub priority: FlagPriority,
+    pub reason: String,
+    pub spans: Vec<MatchSpan>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleTestLine {
+    /// The cue index, or the 1-based line number of free text.
+    pub index: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_time: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_time: Option<f64>,
+    pub text: String,
+    pub matches: Vec<RuleTestMatch>,
+}
+
+impl RuleTestLine {
+    pub fn from_cue(cue: &SubtitleCue) -> Self {
+        Self {
+            index: cue.index,
+            start_time: Some(cue.start_time),
+            end_time: Some(cue.end_time),
+            text: cue.text.clone(),
+            matches: Vec::new(),
+        }
+    }
+
+    pub fn from_text(text: &str) -> Vec<Self> {
+        text.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| Self {
+                index: index as u32 + 1,
+                start_time: None,
+                end_time: None,
+                text: line.to_string(),
+                matches: Vec::new(),
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleTestReport {
+    pub line_count: usize,
+    /// Only the lines something matched.
+    pub lines: Vec<RuleTestLine>,
+}
+
+fn match_spans(text: &str, origins: &[usize], spans: Vec<Range<usize>>) -> Vec<MatchSpan> {
+    spans
+        .into_iter()
+        .filter(|span| !span.is_empty())
+        .map(|span| {
+            let start = origins[span.start];
+            let end = origins[span.end - 1] + 1;
+            MatchSpan {
+                start,
+                end,
+                matched_text: text.chars().skip(start).take(end - start).collect(),
+            }
+        })
+        .collect()
+}
+
+/// Dry-runs the blacklist engine: reports, per line, which rules and
+/// profanity words `analyze_subtitles` would flag it for and where.
+pub fn test_rules(lines: Vec<RuleTestLine>, settings: &ModerationSettings, profanity: &ProfanityMatcher) -> RuleTestReport {
+    let rules = normalize_rules(settings);
+    let custom_profanity_words = custom_profanity_words(settings);
+    let line_count = lines.len();
+
+    let lines = lines
+        .into_iter()
+        .filter_map(|mut line| {
+            let (normalized_text, origins) = normalize_with_origins(&line.text, &settings.normalization);
+
+            let mut profanity_spans = profanity.find_profanity(&normalized_text);
+            profanity_spans.extend(custom_word_spans(&normalized_text, &custom_profanity_words));
+            profanity_spans.sort_by_key(|span| (span.start, span.end));
+            profanity_spans.dedup();
+            let profanity_match = RuleTestMatch {
+                rule_id: "profanity".to_string(),
+                category: "language".to_string(),
+                priority: FlagPriority::Medium,
+                reason: "Contains profanity or offensive language.".to_string(),
+                spans: match_spans(&line.text, &origins, profanity_spans),
+            };
+            let rule_matches = rules.iter().map(|rule| RuleTestMatch {
+                rule_id: rule.rule_id.clone(),
+                category: rule.category.clone(),
+                priority: rule.priority,
+                reason: rule.reason.clone(),
+                spans: match_spans(&line.text, &origins, rule.find_spans(&normalized_text)),
+            });
+
+            line.matches = std::iter::once(profanity_match)
+                .chain(rule_matches)
+                .filter(|found| !found.spans.is_empty())
+                .collect();
+            (!line.matches.is_empty()).then_some(line)
+        })
+        .collect();
+
+    RuleTestReport { line_count, lines }
+}
+
+/// The subtitle file and `.analysis.json` sidecar for a flag input, which is
+/// either a subtitle file or a video with a subtitle sidecar.
+fn resolve_sidecars(path: &Path) -> (PathBuf, PathBuf) {
+    let analysis_path = path.with_extension("analysis.json");
+    if SubtitleFormat::from_path(path).is_some() {
+        return (path.to_path_buf(), analysis_path);
+    }
+    let subtitle_path = find_subtitle_sidecar(path).unwrap_or_else(|| path.with_extension("srt"));
+    (subtitle_path, analysis_path)
+}
+
+struct TaskEvents<'a> {
+    app: &'a AppHandle,
+    state: &'a AppState,
+    task_id: &'a str,
+}
+
+impl TaskEvents<'_> {
+    async fn emit(&self, event: WorkerEvent) {
+        handle_worker_event(self.app, self.state, &event).await;
+    }
+
+    async fn progress(&self, job_id: &str, progress_pct: f64) {
+        self.emit(WorkerEvent::JobProgress {
+            batch_id: None,
+            task_id: Some(self.task_id.to_string()),
+            task_kind: Some(TASK_KIND.to_string()),
+            job_id: job_id.to_string(),
+            progress_pct,
+        })
+        .await;
+    }
+
+    async fn log(&self, job_id: &str, message: String) {
+        self.emit(WorkerEvent::JobLog {
+            batch_id: None,
+            task_id: Some(self.task_id.to_string()),
+            task_kind: Some(TASK_KIND.to_string()),
+            job_id: job_id.to_string(),
+            message,
+            stream: Some("stdout".to_string()),
+        })
+        .await;
+    }
+
+    async fn error(&self, job_id: &str, error: String) {
+        self.emit(WorkerEvent::JobError {
+            batch_id: None,
+            task_id: Some(self.task_id.to_string()),
+            task_kind: Some(TASK_KIND.to_string()),
+            job_id: job_id.to_string(),
+            error,
+        })
+        .await;
+    }
+}
+
+/// Flags one input the way the worker's blacklist engine does, writing the
+/// `.analysis.json` sidecar and its `.analysis.blacklist.json` copy.
+async fn flag_input(
+    events: &TaskEvents<'_>,
+    input_path: &str,
+    job_id: &str,
+    settings: &ModerationSettings,
+    profile: Option<&ProfileRef>,
+    profanity: &ProfanityMatcher,
+) -> Result<(), String> {
+    let source_path = Path::new(input_path);
+    let (subtitle_path, analysis_path) = resolve_sidecars(source_path);
+    let file_name = source_path
+        .file_name()
+        .map(|value| value.to_string_lossy().to_string())
+        .unwrap_or_else(|| input_path.to_string());
+
+    events.progress(job_id, 5.0).await;
+    events
+        .log(job_id, format!("Starting analysis for {file_name} with engine={ENGINE}"))
+        .await;
+
+    if !subtitle_path.exists() {
+        return Err(format!(
+            "Missing subtitle sidecar. Run transcription first: {}",
+            subtitle_path.display()
+        ));
+    }
+    let format = SubtitleFormat::from_path(&subtitle_path).unwrap_or(SubtitleFormat::Srt);
+    let content = fs::read_to_string(&subtitle_path).map_err(|error| format!("Failed reading subtitle file: {error}"))?;
+    let cues = parse_subtitles(&content, format);
+    events.progress(job_id, 40.0).await;
+
+    let (mut flagged, mut summary) = analyze_subtitles(&cues, settings, profanity);
+    if profanity.is_fallback() {
+        events.log(job_id, FALLBACK_PROFANITY_NOTE.to_string()).await;
+        summary = format!("{summary} {FALLBACK_PROFANITY_NOTE}");
+    }
+    events.progress(job_id, 80.0).await;
+    events
+        .log(job_id, format!("Flagged {} subtitle item(s).", flagged.len()))
+        .await;
+
+    let carried_reviews = read_analysis_document(&analysis_path)
+        .map(|previous| carry_over_reviews(&previous, &mut flagged))
+        .unwrap_or(0);
+    if carried_reviews > 0 {
+        events
+            .log(
+                job_id,
+                format!("Kept {carried_reviews} reviewer decision(s) from the previous analysis."),
+            )
+            .await;
+    }
+
+    let flagged_count = flagged.len();
+    let document = AnalysisDocument {
+        schema_version: ANALYSIS_SCHEMA_VERSION,
+        engine: ENGINE.to_string(),
+        created_at: format_created_at(SystemTime::now()),
+        video_file_name: file_name,
+        summary: summary.clone(),
+        flagged,
+        profile: profile.cloned(),
+    };
+    let engine_analysis_path = analysis_path.with_extension(format!("{ENGINE}.json"));
+    write_analysis_document(&analysis_path, &document)
+        .and_then(|_| write_analysis_document(&engine_analysis_path, &document))
+        .map_err(|error| format!("Failed writing analysis sidecar: {error}"))?;
+
+    events
+        .emit(WorkerEvent::JobDone {
+            batch_id: None,
+            task_id: Some(events.task_id.to_string()),
+            task_kind: Some(TASK_KIND.to_string()),
+            job_id: job_id.to_string(),
+            output_path: Some(analysis_path.to_string_lossy().to_string()),
+            artifacts: Some(serde_json::json!({
+                "flaggedCount": flagged_count,
+                "summary": summary,
+                "engineAnalysisPath": engine_analysis_path.to_string_lossy(),
+            })),
+        })
+        .await;
+    Ok(())
+}
+
+async fn run_flag_task(
+    app: AppHandle,
+    state: AppState,
+    task_id: String,
+    input_paths: Vec<String>,
+    settings: ModerationSettings,
+    profile: Option<ProfileRef>,
+    cancel_requested: Arc<AtomicBool>,
+) {
+    let events = TaskEvents {
+        app: &app,
+        state: &state,
+        task_id: &task_id,
+    };
+    let profanity = ProfanityMatcher::load(&app);
+    let mut summary = TaskSummary::default();
+
+    for (index, input_path) in input_paths.iter().enumerate() {
+        if cancel_requested.load(Ordering::Acquire) {
+            summary.cancelled = input_paths.len() - index;
+            break;
+        }
+
+        let job_id = to_job_id(input_path);
+        match flag_input(&events, input_path, &job_id, &settings, profile.as_ref(), &profanity).await {
+            Ok(()) => summary.ok += 1,
+            Err(error) => {
+                summary.failed += 1;
+                events.error(&job_id, error).await;
+            }
+        }
+
+        let remaining_count = input_paths.len() - index - 1;
+        if cancel_requested.load(Ordering::Acquire) && remaining_count > 0 {
+            summary.cancelled = remaining_count;
+            events
+                .log(
+                    &job_id,
+                    format!("Cancellation requested. Skipping the remaining {remaining_count} file(s)."),
+                )
+                .await;
+            break;
+        }
+    }
+
+    state.finish_native_task(&task_id).await;
+    events
+        .emit(WorkerEvent::TaskDone {
+            task_id: task_id.clone(),
+            task_kind: TASK_KIND.to_string(),
+            summary,
+        })
+        .await;
+}
+
+/// Runs a blacklist flag task in the host, without starting the worker.
+pub async fn start_flag_task(
+    app: AppHandle,
+    state: AppState,
+    task_id: String,
+    input_paths: Vec<String>,
+    settings: ModerationSettings,
+    profile: Option<ProfileRef>,
+) {
+    let cancel_requested = state.register_native_task(&task_id).await;
+    tauri::async_runtime::spawn(run_flag_task(
+        app,
+        state,
+        task_id,
+        input_paths,
+        settings,
+        profile,
+        cancel_requested,
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        analysis::FlagPriority,
+        srt::SubtitleCue,
+        types::{ModerationRule, ModerationSettings, PatternMatchType, RulePattern, TextNormalization},
+    };
+
+    use super::{analyze_subtitles, test_rules, validate_rules, MatchSpan, ProfanityMatcher, RuleTestLine};
+
+    fn cue(start_time: f64, text: &str) -> SubtitleCue {
+        SubtitleCue {
+            index: 1,
+            start_time,
+            end_time: start_time + 1.0,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn should_flag_rules_and_profanity_like_the_worker() {
+        let rules = vec![ModerationRule {
+            rule_id: "aqeedah_christmas".to_string(),
+            category: "aqeedah".to_string(),
+            priority: " HIGH ".to_string(),
+            reason: "Celebration.".to_string(),
+            patterns: vec![RulePattern::substring(" Christmas "), RulePattern::substring("")],
+            exclusions: Vec::new(),
+        }];
+        let cues = vec![cue(3.0, "Let's celebrate Christmas, damn it."), cue(1.0, "This is fine.")];
+
+        let (flagged, summary) = analyze_subtitles(&cues, &ModerationSettings::for_tests(rules, &[]), &ProfanityMatcher::Fallback);
+
+        let rule_ids = flagged.iter().map(|item| item.rule_id.as_str()).collect::<Vec<_>>();
+        assert_eq!(rule_ids, vec!["aqeedah_christmas", "profanity"]);
+        assert_eq!(flagged[0].priority, FlagPriority::High);
+        assert_eq!(summary, "Flagged 2 subtitle item(s). high=1, medium=1, low=0.");
+    }
+
+    #[test]
+    fn should_fall_back_to_default_rules_and_match_custom_words() {
+        let cues = vec![cue(1.0, "We will summon it"), cue(2.0, "What a Blighter!")];
+
+        let (flagged, _) = analyze_subtitles(&cues, &ModerationSettings::for_tests(Vec::new(), &["blighter"]), &ProfanityMatcher::Fallback);
+
+        let rule_ids = flagged.iter().map(|item| item.rule_id.as_str()).collect::<Vec<_>>();
+        assert_eq!(rule_ids, vec!["magic_sorcery", "profanity"]);
+    }
+
+    fn assert_fixture_parity(fixture: &str, profanity: impl Fn(&serde_json::Value) -> ProfanityMatcher) {
+        let fixture: serde_json::Value = serde_json::from_str(fixture).expect("fixture should be json");
+        let settings: ModerationSettings =
+            serde_json::from_value(fixture["settings"].clone()).expect("fixture settings should deserialize");
+        let cues: Vec<SubtitleCue> =
+            serde_json::from_value(fixture["cues"].clone()).expect("fixture cues should deserialize");
+
+        let (flagged, summary) = analyze_subtitles(&cues, &settings, &profanity(&fixture));
+
+        assert_eq!(serde_json::to_value(&flagged).unwrap(), fixture["flagged"]);
+        assert_eq!(summary, fixture["summary"]);
+    }
+
+    #[test]
+    fn should_flag_the_shared_fixture_exactly_like_the_worker() {
+        // The worker's Python engine checks the same fixtures (`test_blacklist_parity.py`).
+        assert_fixture_parity(
+            include_str!("../../python-worker/tests/fixtures/blacklist_parity.json"),
+            |_| ProfanityMatcher::Fallback,
+        );
+    }
+
+    #[test]
+    fn should_split_words_at_harakat_like_the_worker() {
+        assert_fixture_parity(
+            include_str!("../../python-worker/tests/fixtures/blacklist_parity_tashkeel.json"),
+            |_| ProfanityMatcher::Fallback,
+        );
+    }
+
+    #[test]
+    fn should_match_the_word_list_fixture_exactly_like_the_worker() {
+        assert_fixture_parity(
+            include_str!("../../python-worker/tests/fixtures/blacklist_parity_word_list.json"),
+            |fixture| {
+                let words = fixture["wordList"]
+                    .as_array()
+                    .expect("fixture should list words")
+                    .iter()
+                    .filter_map(serde_json::Value::as_str)
+                    .collect::<Vec<_>>();
+                ProfanityMatcher::from_word_list(&words.join("\n"))
+            },
+        );
+    }
+
+    fn typed_rule(patterns: &[(&str, PatternMatchType)], exclusions: &[&str]) -> ModerationRule {
+        let pattern = |(value, match_type): &(&str, PatternMatchType)| RulePattern {
+            value: value.to_string(),
+            match_type: *match_type,
+        };
+        ModerationRule {
+            rule_id: "typed".to_string(),
+            category: "custom".to_string(),
+            priority: "low".to_string(),
+            reason: "Typed.".to_string(),
+            patterns: patterns.iter().map(pattern).collect(),
+            exclusions: exclusions.iter().map(|value| RulePattern::substring(value)).collect(),
+        }
+    }
+
+    fn flagged_starts(rule: ModerationRule, texts: &[&str]) -> Vec<f64> {
+        let cues = texts
+            .iter()
+            .enumerate()
+            .map(|(index, text)| cue(index as f64, text))
+            .collect::<Vec<_>>();
+        let (flagged, _) = analyze_subtitles(&cues, &ModerationSettings::for_tests(vec![rule], &[]), &ProfanityMatcher::Fallback);
+        flagged.iter().map(|item| item.start_time).collect()
+    }
+
+    #[test]
+    fn should_match_whole_words_phrases_and_stems() {
+        let texts = ["A spelling bee", "Cast a Spell!", "pray,\nto him", "They killed it"];
+
+        let whole_word = typed_rule(&[("spell", PatternMatchType::WholeWord)], &[]);
+        let phrase = typed_rule(&[("pray to", PatternMatchType::Phrase)], &[]);
+        let stem = typed_rule(&[("kill", PatternMatchType::Stem)], &[]);
+        let regex = typed_rule(&[(r"\bspell(ing)?\b", PatternMatchType::Regex)], &[]);
+
+        assert_eq!(flagged_starts(whole_word, &texts), vec![1.0]);
+        assert_eq!(flagged_starts(phrase, &texts), vec![2.0]);
+        assert_eq!(flagged_starts(stem, &texts), vec![3.0]);
+        assert_eq!(flagged_starts(regex, &texts), vec![0.0, 1.0]);
+    }
+
+    #[test]
+    fn should_skip_cues_matching_an_exclusion() {
+        let rule = typed_rule(&[("spell", PatternMatchType::Substring)], &["spelling bee"]);
+
+        assert_eq!(flagged_starts(rule, &["A spelling bee", "A spell"]), vec![1.0]);
+    }
+
+    #[test]
+    fn should_only_drop_pattern_matches_an_exclusion_overlaps() {
+        let rule = typed_rule(&[("spell", PatternMatchType::Substring)], &["spelling bee"]);
+        let text = "After the spelling bee we cast a spell";
+
+        let report = test_rules(
+            RuleTestLine::from_text(text),
+            &ModerationSettings::for_tests(vec![rule.clone()], &[]),
+            &ProfanityMatcher::Fallback,
+        );
+
+        assert_eq!(flagged_starts(rule, &[text]), vec![0.0]);
+        let spans = &report.lines[0].matches[0].spans;
+        assert_eq!(
+            spans,
+            &vec![MatchSpan {
+                start: 33,
+                end: 38,
+                matched_text: "spell".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn should_reject_invalid_regex_with_the_rule_id() {
+        let error = validate_rules(&[typed_rule(&[("(unclosed", PatternMatchType::Regex)], &[])]).unwrap_err();
+
+        assert!(error.starts_with("Rule typed has an invalid regex pattern \"(unclosed\""));
+        assert!(validate_rules(&[typed_rule(&[("(unclosed", PatternMatchType::WholeWord)], &[])]).is_ok());
+    }
+
+    #[test]
+    fn should_reject_regex_syntax_the_worker_cannot_run() {
+        let validate = |pattern: &str| validate_rules(&[typed_rule(&[(pattern, PatternMatchType::Regex)], &[])]);
+
+        for pattern in [
+            r"\p{L}+",
+            "[[:alpha:]]",
+            r"end\z",
+            r"\x{41}",
+            "(?<name>a)",
+            "a(?i)b",
+            "(?U)a+",
+            "(?-i)a",
+            "[a-z&&[^aeiou]]",
+            r"\<word",
+        ] {
+            let error = validate(pattern).unwrap_err();
+            assert!(error.contains("worker's Python engine"), "{pattern}: {error}");
+        }
+        for pattern in [r"(?i)\bspell(ing)?\b", "(?P<name>a)", "(?-i:a)b", r"^\d+\s*$", r"\Aword"] {
+            assert!(validate(pattern).is_ok(), "{pattern}");
+        }
+    }
+
+    #[test]
+    fn should_read_legacy_string_patterns_as_substrings() {
+        let rule: ModerationRule = serde_json::from_value(serde_json::json!({
+            "ruleId": "legacy",
+            "category": "custom",
+            "priority": "low",
+            "reason": "Legacy.",
+            "patterns": ["spell", { "value": "pray to", "matchType": "phrase" }],
+        }))
+        .unwrap();
+
+        assert_eq!(rule.patterns[0], RulePattern::substring("spell"));
+        assert_eq!(rule.patterns[1].match_type, PatternMatchType::Phrase);
+        assert!(rule.exclusions.is_empty());
+        assert_eq!(
+            serde_json::to_value(&rule.patterns[0]).unwrap(),
+            serde_json::json!({ "value": "spell", "matchType": "substring" })
+        );
+    }
+
+    #[test]
+    fn should_report_matching_lines_with_character_spans() {
+        let rule = typed_rule(&[("christmas", PatternMatchType::WholeWord)], &[]);
+        let lines = RuleTestLine::from_text("Café: Merry CHRISTMAS, damn\n\nnothing here\nWhat a blighter");
+
+        let report = test_rules(lines, &ModerationSettings::for_tests(vec![rule], &["blighter"]), &ProfanityMatcher::Fallback);
+
+        assert_eq!(report.line_count, 3);
+        let summary = report
+            .lines
+            .iter()
+            .map(|line| {
+                let rule_ids = line.matches.iter().map(|found| found.rule_id.as_str()).collect::<Vec<_>>();
+                (line.index, rule_ids)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(summary, vec![(1, vec!["profanity", "typed"]), (4, vec!["profanity"])]);
+        let span = |start, end, matched_text: &str| MatchSpan {
+            start,
+            end,
+            matched_text: matched_text.to_string(),
+        };
+        assert_eq!(report.lines[0].matches[0].spans, vec![span(23, 27, "damn")]);
+        assert_eq!(report.lines[0].matches[1].spans, vec![span(12, 21, "CHRISTMAS")]);
+        assert_eq!(report.lines[1].matches[0].spans, vec![span(7, 15, "blighter")]);
+    }
+
+    #[test]
+    fn should_match_normalized_arabic_and_leetspeak() {
+        // "إله" as a pattern, "إِلَٰهٌ" and "ST00PID" in the subtitles.
+        let rule = typed_rule(&[("\u{0625}\u{0644}\u{0647}", PatternMatchType::WholeWord)], &[]);
+        let cues = vec![
+            cue(1.0, "\u{0625}\u{0650}\u{0644}\u{064E}\u{0670}\u{0647}\u{064C}"),
+            cue(2.0, "So ST00PID"),
+        ];
+        let mut normalized_settings = ModerationSettings::for_tests(vec![rule], &["stoopid"]);
+
+        let (flagged, _) = analyze_subtitles(&cues, &normalized_settings, &ProfanityMatcher::Fallback);
+        assert!(flagged.is_empty());
+
+        normalized_settings.normalization = TextNormalization {
+            fold_case: true,
+            strip_diacritics: true,
+            unify_arabic_letters: true,
+            fold_digits: true,
+            fold_leetspeak: true,
+        };
+        let (flagged, _) = analyze_subtitles(&cues, &normalized_settings, &ProfanityMatcher::Fallback);
+        let rule_ids = flagged.iter().map(|item| item.rule_id.as_str()).collect::<Vec<_>>();
+        assert_eq!(rule_ids, vec!["typed", "profanity"]);
+    }
+
+    #[test]
+    fn should_report_clean_subtitles() {
+        let (flagged, summary) =
+            analyze_subtitles(&[cue(1.0, "Hello there")], &ModerationSettings::for_tests(Vec::new(), &[]), &ProfanityMatcher::Fallback);
+
+        assert!(flagged.is_empty());
+        assert_eq!(summary, "No concerning content detected.");
+    }
+
+    #[test]
+    fn should_match_word_list_entries_with_substitutions_and_phrases() {
+        let matcher = ProfanityMatcher::from_word_list("darn\nbad word\ndumbass\n");
+
+        assert!(matcher.contains_profanity("oh d4rn!"));
+        assert!(matcher.contains_profanity("a bad word here"));
+        assert!(matcher.contains_profanity("you dumb ass"));
+        assert!(!matcher.contains_profanity("darnit"));
+        assert!(!matcher.contains_profanity("bad words"));
+    }
+}
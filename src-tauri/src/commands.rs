@@ -1,6 +1,7 @@
 use std::{
     fs,
     path::{Path, PathBuf},
+    time::SystemTime,
 };
 
 use tauri::{AppHandle, Emitter, Manager, State};
@@ -10,18 +11,46 @@ use tokio::sync::oneshot;
 use uuid::Uuid;
 
 use crate::{
+    analysis::{
+        self, analysis_file_stem, apply_review, format_created_at, parse_analysis_document,
+        write_analysis_document, AnalysisDiff, AnalysisDocument, FlagEdit,
+    },
     analytics,
-    file_discovery::{build_output_dir, collect_media_files, discover_srt_items, discover_video_items},
+    blacklist::{self, ProfanityMatcher, RuleTestLine, RuleTestReport},
+    cut_planning::{
+        ensure_valid_cut_plan_options, plan_cut_job, plan_cuts, probe_duration_seconds,
+    },
+    file_discovery::{
+        build_output_dir, collect_media_files, discover_srt_items, discover_video_items,
+        find_analysis_source,
+    },
     ids::{to_file_name, to_job_id},
-    protocol::WorkerCommand,
-    state::AppState,
+    profiles::{
+        self, ModerationProfile, ModerationProfileSummary, ProfileRef, DEFAULT_PROFILE_ID,
+        DEFAULT_PROFILE_NAME,
+    },
+    protocol::{is_audio_redaction_mode, WorkerCommand},
+    rule_packs::{self, RuleImportReport},
+    runtime::resolve_ffmpeg_executable,
+    srt::SubtitleCue,
+    state::{now_epoch_seconds, AppState},
+    subtitles::{parse_subtitles, serialize_subtitles, SubtitleFormat, SUBTITLE_EXTENSIONS},
     types::{
-        AnalyticsSnapshot, BatchEvent, BatchStartedResponse, BatchState, BatchStatus, CancelAck,
-        CancelBatchRequest, CancelTaskRequest, CutJobStartedResponse, JobRecord, JobStatus,
-        ListSrtFilesRequest, ListVideosRequest, ModerationRule, ModerationSettings, SaveAck,
-        SrtListItem, StartBatchRequest, StartCutJobRequest, StartFlagBatchRequest,
-        StartTranscriptionBatchRequest, TaskCancelAck, TaskJobRecord, TaskJobStatus, TaskKind,
-        TaskState, TaskStatus, VideoListItem, WorkerStatusKind,
+        AnalyticsSnapshot, BatchControlAck, BatchControlRequest, BatchEvent, BatchStartedResponse,
+        BatchState, BatchStatus, CancelAck, CancelBatchRequest, CancelTaskRequest,
+        CloneModerationProfileRequest, ConvertSubtitleRequest, ConvertSubtitleResponse,
+        CreateModerationProfileRequest, CutJobStartedResponse, CutPlanOptions, CutPlanResponse,
+        DiffAnalysesRequest, ExportModerationRulesRequest, ExportModerationRulesResponse,
+        ImportModerationRulesRequest, JobRecord, JobStatus, ListSrtFilesRequest, ListTasksRequest,
+        ListVideosRequest, MergeAnalysesRequest, ModerationRule, ModerationSettings, PipelineStage,
+        PlanCutsRequest, PlannedCutJob, RetryJobsRequest, RetryStartedResponse,
+        ReviewFlaggedItemRequest, RulePattern, RunParameters, SaveAck,
+        SaveModerationProfileRequest, SrtListItem, StartBatchRequest, StartCutBatchRequest,
+        StartCutJobRequest, StartFlagBatchRequest, StartPipelineRequest,
+        StartTranscriptionBatchRequest, TaskCancelAck, TaskControlAck, TaskControlRequest,
+        TaskHistoryItem, TaskJobRecord, TaskJobStatus, TaskKind, TaskState, TaskStatus,
+        TestModerationRulesRequest, TextNormalization, VideoListItem, WorkerStatusKind,
+        WriteAnalysisFileRequest,
     },
     worker::ensure_worker_sender,
 };
@@ -38,21 +67,65 @@ fn ensure_supported_output_mode(output_dir_mode: &str) -> Result<(), String> {
 }
 
 fn ensure_supported_cut_output_mode(output_mode: &str) -> Result<(), String> {
-    if output_mode != "video_cleaned_default" {
-        return Err("Unsupported cut output mode. Use video_cleaned_default.".to_string());
+    if !matches!(output_mode, "video_cleaned_default" | "mute" | "bleep") {
+        return Err("Unsupported cut output mode. Use video_cleaned_default, mute or bleep.".to_string());
     }
 
     Ok(())
 }
 
 fn ensure_supported_cancel_mode(mode: &str) -> Result<(), String> {
-    if mode != "stop_after_current" {
-        return Err("Unsupported cancellation mode. Use stop_after_current.".to_string());
+    if !matches!(mode, "stop_after_current" | "immediate") {
+        return Err("Unsupported cancellation mode. Use stop_after_current or immediate.".to_string());
     }
 
     Ok(())
 }
 
+fn ensure_worker_supports_cancel_mode(worker_sender: &crate::state::WorkerSender, mode: &str) -> Result<(), String> {
+    if mode == "immediate" && !worker_sender.supports("cancel_immediate") {
+        return Err(format!(
+            "The running worker ({}) does not support immediate cancellation. Use stop_after_current.",
+            worker_sender.worker_version()
+        ));
+    }
+
+    Ok(())
+}
+
+fn ensure_batch_can_change_pause(
+    batch_id: &str,
+    status: Option<&BatchStatus>,
+    pause: bool,
+) -> Result<(), String> {
+    match status {
+        None => Err(format!("Batch {batch_id} was not found.")),
+        Some(BatchStatus::Interrupted) => Err(format!("Batch {batch_id} was interrupted. Restart it instead.")),
+        Some(status) if !status.is_active() => Err(format!("Batch {batch_id} has already finished.")),
+        Some(BatchStatus::Paused) if pause => Err(format!("Batch {batch_id} is already paused.")),
+        Some(_) => Ok(()),
+    }
+}
+
+fn ensure_task_can_change_pause(task_id: &str, status: Option<&TaskStatus>, pause: bool) -> Result<(), String> {
+    match status {
+        None => Err(format!("Task {task_id} was not found.")),
+        Some(TaskStatus::Interrupted) => Err(format!("Task {task_id} was interrupted. Restart it instead.")),
+        Some(status) if !status.is_active() => Err(format!("Task {task_id} has already finished.")),
+        Some(TaskStatus::Paused) if pause => Err(format!("Task {task_id} is already paused.")),
+        Some(_) => Ok(()),
+    }
+}
+
+/// Single cut jobs hand the whole export to ffmpeg with nowhere to wait, so
+/// pausing them would be acknowledged and then ignored.
+fn ensure_task_has_pause_point(task_id: &str, parameters: Option<&RunParameters>) -> Result<(), String> {
+    match parameters {
+        Some(RunParameters::Cut { .. }) => Err(format!("Task {task_id} exports in one step and cannot be paused.")),
+        _ => Ok(()),
+    }
+}
+
 fn ensure_supported_yap_mode(yap_mode: &str) -> Result<(), String> {
     if yap_mode != "auto" {
         return Err("Unsupported yap mode. Use auto.".to_string());
@@ -61,6 +134,40 @@ fn ensure_supported_yap_mode(yap_mode: &str) -> Result<(), String> {
     Ok(())
 }
 
+fn ensure_supported_compute_mode(compute_mode: &str) -> Result<(), String> {
+    if !matches!(compute_mode, "auto" | "cpu" | "mps") {
+        return Err("Unsupported compute mode. Use auto, cpu or mps.".to_string());
+    }
+
+    Ok(())
+}
+
+fn ensure_valid_pipeline_stages(stages: &[PipelineStage]) -> Result<(), String> {
+    if stages.is_empty() {
+        return Err("Pipeline requires at least one stage.".to_string());
+    }
+    if stages.windows(2).any(|pair| pair[0].kind() >= pair[1].kind()) {
+        return Err(
+            "Pipeline stages must appear at most once, in the order remove_music, transcribe, flag, cut."
+                .to_string(),
+        );
+    }
+
+    for stage in stages {
+        match stage {
+            PipelineStage::RemoveMusic { compute_mode } => ensure_supported_compute_mode(compute_mode)?,
+            PipelineStage::Transcribe { yap_mode } => ensure_supported_yap_mode(yap_mode)?,
+            PipelineStage::Flag { .. } => {}
+            PipelineStage::Cut { output_mode, plan } => {
+                ensure_supported_cut_output_mode(output_mode)?;
+                ensure_valid_cut_plan_options(plan)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
 fn validate_paths_have_extensions(paths: &[String], allowed_extensions: &[String]) -> Result<(), String> {
     let normalized_extensions = allowed_extensions
         .iter()
@@ -119,7 +226,10 @@ fn is_allowed_text_sidecar_path(path: &Path) -> bool {
         .and_then(|value| value.to_str())
         .map(|value| value.to_ascii_lowercase());
 
-    matches!(extension.as_deref(), Some("srt")) || file_name.ends_with(".analysis.json")
+    extension
+        .as_deref()
+        .is_some_and(|extension| SUBTITLE_EXTENSIONS.contains(&extension))
+        || analysis_file_stem(file_name).is_some()
 }
 
 fn validate_read_text_file_path(path: &str) -> Result<PathBuf, String> {
@@ -136,12 +246,44 @@ fn validate_read_text_file_path(path: &str) -> Result<PathBuf, String> {
     }
 
     if !is_allowed_text_sidecar_path(&canonical) {
-        return Err("Only .srt and .analysis.json sidecar files can be read.".to_string());
+        return Err("Only .srt, .vtt, .ass, .ssa and .analysis.json sidecar files can be read.".to_string());
     }
 
     Ok(canonical)
 }
 
+fn validate_write_analysis_path(path: &str) -> Result<PathBuf, String> {
+    if path.trim().is_empty() {
+        return Err("File path is required.".to_string());
+    }
+
+    let requested = PathBuf::from(path);
+    let file_name = requested
+        .file_name()
+        .and_then(|value| value.to_str())
+        .filter(|value| analysis_file_stem(value).is_some())
+        .ok_or_else(|| "Only .analysis.json sidecar files can be written.".to_string())?;
+    let parent = requested
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .ok_or_else(|| format!("Failed determining parent directory for {path}"))?
+        .canonicalize()
+        .map_err(|error| format!("Failed resolving file path {path}: {error}"))?;
+    let resolved = parent.join(file_name);
+
+    if resolved.is_dir() {
+        return Err(format!("Path is not a file: {}", resolved.display()));
+    }
+    if find_analysis_source(&resolved).is_none() {
+        return Err(format!(
+            "No video or subtitle file found next to {}; analysis sidecars must sit beside their media.",
+            resolved.display()
+        ));
+    }
+
+    Ok(resolved)
+}
+
 fn create_batch_jobs(input_paths: &[String]) -> Vec<JobRecord> {
     input_paths
         .iter()
@@ -170,10 +312,19 @@ fn create_task_jobs(input_paths: &[String]) -> Vec<TaskJobRecord> {
             progress_pct: 0,
             error: None,
             logs: Vec::new(),
+            stage: None,
         })
         .collect::<Vec<_>>()
 }
 
+fn without_api_keys(settings: &ModerationSettings) -> ModerationSettings {
+    ModerationSettings {
+        google_api_key: String::new(),
+        amazon_nova_api_key: String::new(),
+        ..settings.clone()
+    }
+}
+
 fn default_moderation_settings() -> ModerationSettings {
     ModerationSettings {
         amazon_nova_api_key: String::new(),
@@ -189,31 +340,32 @@ fn default_moderation_settings() -> ModerationSettings {
                 category: "aqeedah".to_string(),
                 priority: "high".to_string(),
                 reason: "Promotes non-Islamic religious celebration.".to_string(),
-                patterns: vec![
-                    "christmas".to_string(),
-                    "xmas".to_string(),
-                    "easter".to_string(),
-                ],
+                patterns: ["christmas", "xmas", "easter"].map(RulePattern::substring).to_vec(),
+                exclusions: Vec::new(),
             },
             ModerationRule {
                 rule_id: "magic_sorcery".to_string(),
                 category: "magic".to_string(),
                 priority: "high".to_string(),
                 reason: "References magic or sorcery.".to_string(),
-                patterns: vec![
-                    "spell".to_string(),
-                    "sorcery".to_string(),
-                    "witchcraft".to_string(),
-                ],
+                patterns: ["spell", "sorcery", "witchcraft"].map(RulePattern::substring).to_vec(),
+                exclusions: Vec::new(),
             },
             ModerationRule {
                 rule_id: "offensive_language".to_string(),
                 category: "language".to_string(),
                 priority: "medium".to_string(),
                 reason: "Contains offensive language.".to_string(),
-                patterns: vec!["stupid".to_string(), "idiot".to_string(), "dumb".to_string()],
+                patterns: ["stupid", "idiot", "dumb"].map(RulePattern::substring).to_vec(),
+                exclusions: Vec::new(),
             },
         ],
+        normalization: TextNormalization {
+            strip_diacritics: true,
+            unify_arabic_letters: true,
+            fold_digits: true,
+            ..TextNormalization::default()
+        },
     }
 }
 
@@ -225,6 +377,37 @@ fn is_supported_analysis_strategy(value: &str) -> bool {
     matches!(value, "fast" | "deep")
 }
 
+fn apply_moderation_overrides(
+    settings: &mut ModerationSettings,
+    engine: Option<String>,
+    analysis_strategy: Option<String>,
+) -> Result<(), String> {
+    if let Some(engine) = engine {
+        if !is_supported_moderation_engine(&engine) {
+            return Err(format!("Unsupported moderation engine: {engine}"));
+        }
+        settings.engine = engine;
+    }
+    if let Some(analysis_strategy) = analysis_strategy {
+        if !is_supported_analysis_strategy(&analysis_strategy) {
+            return Err(format!(
+                "Unsupported moderation analysis strategy: {analysis_strategy}"
+            ));
+        }
+        settings.analysis_strategy = analysis_strategy;
+    }
+
+    Ok(())
+}
+
+/// Stored run parameters carry no API keys; take them from the saved settings.
+fn with_saved_api_keys(app: &AppHandle, mut settings: ModerationSettings) -> Result<ModerationSettings, String> {
+    let saved_settings = read_or_initialize_moderation_settings(app)?;
+    settings.google_api_key = saved_settings.google_api_key;
+    settings.amazon_nova_api_key = saved_settings.amazon_nova_api_key;
+    Ok(settings)
+}
+
 fn moderation_settings_path(app: &AppHandle) -> Result<PathBuf, String> {
     let app_data_dir = app
         .path()
@@ -233,6 +416,53 @@ fn moderation_settings_path(app: &AppHandle) -> Result<PathBuf, String> {
     Ok(app_data_dir.join("settings/moderation.json"))
 }
 
+fn moderation_profiles_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(moderation_settings_path(app)?.with_file_name("profiles"))
+}
+
+fn default_moderation_profile(settings: ModerationSettings) -> ModerationProfile {
+    ModerationProfile {
+        id: DEFAULT_PROFILE_ID.to_string(),
+        name: DEFAULT_PROFILE_NAME.to_string(),
+        settings,
+    }
+}
+
+fn read_moderation_profile(app: &AppHandle, id: &str) -> Result<ModerationProfile, String> {
+    if id == DEFAULT_PROFILE_ID {
+        return Ok(default_moderation_profile(read_or_initialize_moderation_settings(app)?));
+    }
+    profiles::read_profile(&moderation_profiles_dir(app)?, id)
+}
+
+/// The reference and settings of a moderation profile (the default one when
+/// `id` is `None`), with the saved API keys. Flag runs record the reference
+/// in their sidecars, so the worker needs `flag_profiles` support.
+fn read_profile_settings(app: &AppHandle, id: Option<&str>) -> Result<(ProfileRef, ModerationSettings), String> {
+    let profile = read_moderation_profile(app, id.unwrap_or(DEFAULT_PROFILE_ID))?;
+    let reference = profile.reference();
+    if profile.id == DEFAULT_PROFILE_ID {
+        return Ok((reference, profile.settings));
+    }
+    Ok((reference, with_saved_api_keys(app, profile.settings)?))
+}
+
+fn write_moderation_profile(app: &AppHandle, id: &str, settings: &ModerationSettings) -> Result<(), String> {
+    if id == DEFAULT_PROFILE_ID {
+        return write_moderation_settings(app, settings);
+    }
+
+    let profiles_dir = moderation_profiles_dir(app)?;
+    let profile = profiles::read_profile(&profiles_dir, id)?;
+    profiles::write_profile(
+        &profiles_dir,
+        &ModerationProfile {
+            settings: without_api_keys(settings),
+            ..profile
+        },
+    )
+}
+
 fn read_or_initialize_moderation_settings(app: &AppHandle) -> Result<ModerationSettings, String> {
     let settings_path = moderation_settings_path(app)?;
     if settings_path.exists() {
@@ -242,8 +472,15 @@ fn read_or_initialize_moderation_settings(app: &AppHandle) -> Result<ModerationS
                 settings_path.display()
             )
         })?;
-        return serde_json::from_str(&content)
-            .map_err(|error| format!("Invalid moderation settings JSON: {error}"));
+        let stored: serde_json::Value = serde_json::from_str(&content)
+            .map_err(|error| format!("Invalid moderation settings JSON: {error}"))?;
+        let settings: ModerationSettings = serde_json::from_value(stored.clone())
+            .map_err(|error| format!("Invalid moderation settings JSON: {error}"))?;
+        // Rewrites files from older versions (e.g. plain-string patterns) in the current shape.
+        if serde_json::to_value(&settings).ok().as_ref() != Some(&stored) {
+            write_moderation_settings(app, &settings)?;
+        }
+        return Ok(settings);
     }
 
     let defaults = default_moderation_settings();
@@ -311,6 +548,12 @@ pub async fn start_batch(
             status: BatchStatus::Queued,
             jobs: create_batch_jobs(&input_paths),
             summary: None,
+            created_at_epoch_seconds: now_epoch_seconds(),
+            parameters: Some(RunParameters::RemoveMusic {
+                output_dir: output_dir.to_string_lossy().to_string(),
+                compute_mode: "auto".to_string(),
+            }),
+            parent_task_id: None,
         })
         .await;
 
@@ -364,6 +607,11 @@ pub async fn start_transcription_batch(
             status: TaskStatus::Queued,
             jobs: create_task_jobs(&input_paths),
             summary: None,
+            created_at_epoch_seconds: now_epoch_seconds(),
+            parameters: Some(RunParameters::Transcription {
+                yap_mode: request.yap_mode.clone(),
+            }),
+            parent_task_id: None,
         })
         .await;
 
@@ -391,29 +639,21 @@ pub async fn start_flag_batch(
 ) -> Result<BatchStartedResponse, String> {
     let allowed_extensions = request
         .allowed_extensions
-        .unwrap_or_else(|| vec![".srt".to_string()]);
+        .unwrap_or_else(|| {
+            SUBTITLE_EXTENSIONS
+                .iter()
+                .map(|extension| format!(".{extension}"))
+                .collect()
+        });
     let input_paths = resolve_input_paths(
         request.input_dir.as_deref(),
         request.input_paths.as_ref(),
         &allowed_extensions,
-        "No .srt files were selected.",
+        "No subtitle files were selected.",
     )?;
 
-    let mut settings = read_or_initialize_moderation_settings(&app)?;
-    if let Some(engine) = request.engine {
-        if !is_supported_moderation_engine(&engine) {
-            return Err(format!("Unsupported moderation engine: {engine}"));
-        }
-        settings.engine = engine;
-    }
-    if let Some(analysis_strategy) = request.analysis_strategy {
-        if !is_supported_analysis_strategy(&analysis_strategy) {
-            return Err(format!(
-                "Unsupported moderation analysis strategy: {analysis_strategy}"
-            ));
-        }
-        settings.analysis_strategy = analysis_strategy;
-    }
+    let (profile, mut settings) = read_profile_settings(&app, request.profile.as_deref())?;
+    apply_moderation_overrides(&mut settings, request.engine, request.analysis_strategy)?;
     let task_id = Uuid::new_v4().to_string();
 
     state
@@ -423,17 +663,22 @@ pub async fn start_flag_batch(
             status: TaskStatus::Queued,
             jobs: create_task_jobs(&input_paths),
             summary: None,
+            created_at_epoch_seconds: now_epoch_seconds(),
+            parameters: Some(RunParameters::Flag {
+                settings: without_api_keys(&settings),
+                profile: Some(profile.clone()),
+            }),
+            parent_task_id: None,
         })
         .await;
 
-    let worker_sender = ensure_worker_sender(app.clone(), state.inner().clone()).await?;
-    worker_sender
-        .send(WorkerCommand::StartFlagBatch {
-            task_id: task_id.clone(),
-            input_paths: input_paths.clone(),
-            settings,
-        })
-        .map_err(|error| format!("Failed to enqueue flag task: {error}"))?;
+    let command = WorkerCommand::StartFlagBatch {
+        task_id: task_id.clone(),
+        input_paths: input_paths.clone(),
+        settings,
+        profile: Some(profile),
+    };
+    dispatch_task_command(&app, state.inner(), command, "Failed to enqueue flag task").await?;
 
     Ok(BatchStartedResponse {
         batch_id: task_id,
@@ -447,6 +692,14 @@ pub async fn start_cut_job(
     app: AppHandle,
     state: State<'_, AppState>,
     request: StartCutJobRequest,
+) -> Result<CutJobStartedResponse, String> {
+    start_cut_job_inner(&app, state.inner(), request).await
+}
+
+async fn start_cut_job_inner(
+    app: &AppHandle,
+    state: &AppState,
+    request: StartCutJobRequest,
 ) -> Result<CutJobStartedResponse, String> {
     ensure_supported_cut_output_mode(&request.output_mode)?;
     if request.ranges.is_empty() {
@@ -463,10 +716,17 @@ pub async fn start_cut_job(
             status: TaskStatus::Queued,
             jobs: create_task_jobs(&input_paths),
             summary: None,
+            created_at_epoch_seconds: now_epoch_seconds(),
+            parameters: Some(RunParameters::Cut {
+                video_path: request.video_path.clone(),
+                ranges: request.ranges.clone(),
+                output_mode: request.output_mode.clone(),
+            }),
+            parent_task_id: None,
         })
         .await;
 
-    let worker_sender = ensure_worker_sender(app.clone(), state.inner().clone()).await?;
+    let worker_sender = ensure_worker_sender(app.clone(), state.clone()).await?;
     worker_sender
         .send(WorkerCommand::StartCutJob {
             task_id: task_id.clone(),
@@ -482,6 +742,215 @@ pub async fn start_cut_job(
     })
 }
 
+/// Plans each video of a cut batch from its sibling `.analysis.json` with the
+/// same planner as `plan_cuts_from_analysis`.
+async fn plan_cut_batch_jobs(
+    app: &AppHandle,
+    input_paths: &[String],
+    plan: &CutPlanOptions,
+    output_mode: &str,
+) -> Result<Vec<PlannedCutJob>, String> {
+    let ffmpeg_executable = resolve_ffmpeg_executable(app)?;
+    let mut jobs = Vec::with_capacity(input_paths.len());
+    for video_path in input_paths {
+        let analysis_path = Path::new(video_path).with_extension("analysis.json");
+        jobs.push(plan_cut_job(&ffmpeg_executable, video_path, &analysis_path, plan, output_mode).await);
+    }
+    Ok(jobs)
+}
+
+/// Cuts every video in `input_dir` that has an `.analysis.json` sidecar, one
+/// task job per video, planning each video's ranges from its own sidecar.
+#[tauri::command]
+pub async fn start_cut_batch(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    request: StartCutBatchRequest,
+) -> Result<BatchStartedResponse, String> {
+    ensure_supported_cut_output_mode(&request.output_mode)?;
+    ensure_valid_cut_plan_options(&request.plan)?;
+    let allowed_extensions = request
+        .allowed_extensions
+        .unwrap_or_else(|| vec![".mp4".to_string(), ".mov".to_string()]);
+    let input_paths = discover_video_items(Path::new(&request.input_dir), &allowed_extensions)?
+        .into_iter()
+        .filter(|item| item.has_analysis)
+        .map(|item| item.path)
+        .collect::<Vec<_>>();
+    if input_paths.is_empty() {
+        return Err("No videos with an .analysis.json sidecar were found in the selected directory.".to_string());
+    }
+
+    // Start the worker first so the runtime's ffmpeg is in place for the probes.
+    let worker_sender = ensure_worker_sender(app.clone(), state.inner().clone()).await?;
+    let jobs = plan_cut_batch_jobs(&app, &input_paths, &request.plan, &request.output_mode).await?;
+    let task_id = Uuid::new_v4().to_string();
+
+    state
+        .insert_task(TaskState {
+            task_id: task_id.clone(),
+            task_kind: TaskKind::Cut,
+            status: TaskStatus::Queued,
+            jobs: create_task_jobs(&input_paths),
+            summary: None,
+            created_at_epoch_seconds: now_epoch_seconds(),
+            parameters: Some(RunParameters::CutBatch {
+                plan: request.plan.clone(),
+                output_mode: request.output_mode.clone(),
+            }),
+            parent_task_id: None,
+        })
+        .await;
+
+    if let Err(error) = worker_sender.send(WorkerCommand::StartCutBatch {
+        task_id: task_id.clone(),
+        jobs,
+        output_mode: request.output_mode,
+    }) {
+        state.remove_task(&task_id).await;
+        return Err(format!("Failed to enqueue cut task: {error}"));
+    }
+
+    Ok(BatchStartedResponse {
+        batch_id: task_id,
+        file_count: input_paths.len(),
+        input_paths,
+    })
+}
+
+/// Turns the flagged segments of a video's `.analysis.json` into the ranges
+/// `start_cut_job` keeps, optionally starting the cut job right away.
+#[tauri::command]
+pub async fn plan_cuts_from_analysis(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    request: PlanCutsRequest,
+) -> Result<CutPlanResponse, String> {
+    ensure_supported_cut_output_mode(&request.output_mode)?;
+    let analysis_path = request.analysis_path.clone().unwrap_or_else(|| {
+        Path::new(&request.video_path)
+            .with_extension("analysis.json")
+            .to_string_lossy()
+            .to_string()
+    });
+    let (_, document) = read_analysis_sidecar(&analysis_path).await?;
+    ensure_valid_cut_plan_options(&request.plan)?;
+    let duration_seconds = match request.duration_seconds {
+        Some(duration_seconds) if !duration_seconds.is_finite() || duration_seconds <= 0.0 => {
+            return Err("Video duration must be a positive number of seconds.".to_string());
+        }
+        Some(duration_seconds) => duration_seconds,
+        None => probe_duration_seconds(&resolve_ffmpeg_executable(&app)?, &request.video_path).await?,
+    };
+    let (keep_ranges, removed_ranges) = plan_cuts(&document, duration_seconds, &request.plan);
+    let redacting_audio = is_audio_redaction_mode(&request.output_mode);
+    let ranges = if redacting_audio {
+        removed_ranges.clone()
+    } else {
+        keep_ranges
+    };
+
+    if !request.start_cut_job {
+        return Ok(CutPlanResponse {
+            ranges,
+            removed_ranges,
+            task_id: None,
+        });
+    }
+
+    if removed_ranges.is_empty() {
+        return Err("No flagged segments match the cut plan.".to_string());
+    }
+    if ranges.is_empty() && !redacting_audio {
+        return Err("Nothing would remain after removing the flagged segments.".to_string());
+    }
+    let started = start_cut_job_inner(
+        &app,
+        state.inner(),
+        StartCutJobRequest {
+            video_path: request.video_path,
+            ranges: ranges.clone(),
+            output_mode: request.output_mode,
+        },
+    )
+    .await?;
+
+    Ok(CutPlanResponse {
+        ranges,
+        removed_ranges,
+        task_id: Some(started.task_id),
+    })
+}
+
+/// Runs every file through `stages` in order, each stage starting from the
+/// previous stage's output, as a single task of kind `pipeline`.
+#[tauri::command]
+pub async fn start_pipeline(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    request: StartPipelineRequest,
+) -> Result<BatchStartedResponse, String> {
+    ensure_valid_pipeline_stages(&request.stages)?;
+    let allowed_extensions = request
+        .allowed_extensions
+        .unwrap_or_else(|| vec![".mp4".to_string(), ".mov".to_string()]);
+    let input_paths = resolve_input_paths(
+        request.input_dir.as_deref(),
+        request.input_paths.as_ref(),
+        &allowed_extensions,
+        "No .mp4/.mov files were selected.",
+    )?;
+
+    let (profile, settings) = match request.stages.iter().find(|stage| matches!(stage, PipelineStage::Flag { .. })) {
+        Some(PipelineStage::Flag {
+            engine,
+            analysis_strategy,
+            profile,
+        }) => {
+            let (profile, mut settings) = read_profile_settings(&app, profile.as_deref())?;
+            apply_moderation_overrides(&mut settings, engine.clone(), analysis_strategy.clone())?;
+            (Some(profile), settings)
+        }
+        _ => (None, read_or_initialize_moderation_settings(&app)?),
+    };
+    let worker_sender = ensure_worker_sender(app.clone(), state.inner().clone()).await?;
+    let task_id = Uuid::new_v4().to_string();
+
+    state
+        .insert_task(TaskState {
+            task_id: task_id.clone(),
+            task_kind: TaskKind::Pipeline,
+            status: TaskStatus::Queued,
+            jobs: create_task_jobs(&input_paths),
+            summary: None,
+            created_at_epoch_seconds: now_epoch_seconds(),
+            parameters: Some(RunParameters::Pipeline {
+                stages: request.stages.clone(),
+                settings: without_api_keys(&settings),
+                profile: profile.clone(),
+            }),
+            parent_task_id: None,
+        })
+        .await;
+
+    if let Err(error) = worker_sender.send(WorkerCommand::StartPipeline {
+        task_id: task_id.clone(),
+        input_paths: input_paths.clone(),
+        stages: request.stages,
+        settings,
+        profile,
+    }) {
+        state.remove_task(&task_id).await;
+        return Err(format!("Failed to enqueue pipeline task: {error}"));
+    }
+
+    Ok(BatchStartedResponse {
+        batch_id: task_id,
+        file_count: input_paths.len(),
+        input_paths,
+    })
+}
+
 #[tauri::command]
 pub async fn cancel_batch(
     state: State<'_, AppState>,
@@ -490,6 +959,7 @@ pub async fn cancel_batch(
     ensure_supported_cancel_mode(&request.mode)?;
 
     let worker_sender = require_worker_sender(state.worker_sender().await)?;
+    ensure_worker_supports_cancel_mode(&worker_sender, &request.mode)?;
 
     let accepted = worker_sender
         .send(WorkerCommand::CancelBatch {
@@ -510,7 +980,14 @@ pub async fn cancel_task(
     request: CancelTaskRequest,
 ) -> Result<TaskCancelAck, String> {
     ensure_supported_cancel_mode(&request.mode)?;
+    if state.cancel_native_task(&request.task_id).await {
+        return Ok(TaskCancelAck {
+            task_id: request.task_id,
+            accepted: true,
+        });
+    }
     let worker_sender = require_worker_sender(state.worker_sender().await)?;
+    ensure_worker_supports_cancel_mode(&worker_sender, &request.mode)?;
 
     let accepted = worker_sender
         .send(WorkerCommand::CancelTask {
@@ -525,6 +1002,334 @@ pub async fn cancel_task(
     })
 }
 
+async fn set_batch_paused(state: &AppState, batch_id: String, pause: bool) -> Result<BatchControlAck, String> {
+    let status = state.get_batch(&batch_id).await.map(|batch| batch.status);
+    ensure_batch_can_change_pause(&batch_id, status.as_ref(), pause)?;
+    let worker_sender = require_worker_sender(state.worker_sender().await)?;
+
+    let command = if pause {
+        WorkerCommand::PauseBatch {
+            batch_id: batch_id.clone(),
+        }
+    } else {
+        WorkerCommand::ResumeBatch {
+            batch_id: batch_id.clone(),
+        }
+    };
+    worker_sender.send(command)?;
+
+    Ok(BatchControlAck {
+        batch_id,
+        accepted: true,
+    })
+}
+
+async fn set_task_paused(state: &AppState, task_id: String, pause: bool) -> Result<TaskControlAck, String> {
+    let task = state.get_task(&task_id).await;
+    ensure_task_can_change_pause(&task_id, task.as_ref().map(|task| &task.status), pause)?;
+    if state.is_native_task(&task_id).await {
+        return Err(format!("Task {task_id} runs in the app and cannot be paused."));
+    }
+    ensure_task_has_pause_point(&task_id, task.as_ref().and_then(|task| task.parameters.as_ref()))?;
+    let worker_sender = require_worker_sender(state.worker_sender().await)?;
+
+    let command = if pause {
+        WorkerCommand::PauseTask {
+            task_id: task_id.clone(),
+        }
+    } else {
+        WorkerCommand::ResumeTask {
+            task_id: task_id.clone(),
+        }
+    };
+    worker_sender.send(command)?;
+
+    Ok(TaskControlAck {
+        task_id,
+        accepted: true,
+    })
+}
+
+fn batch_command_from_parameters(
+    batch_id: &str,
+    input_paths: Vec<String>,
+    parameters: RunParameters,
+) -> Result<WorkerCommand, String> {
+    let RunParameters::RemoveMusic {
+        output_dir,
+        compute_mode,
+    } = parameters
+    else {
+        return Err(format!("Batch {batch_id} has parameters for a different kind of run."));
+    };
+    std::fs::create_dir_all(&output_dir)
+        .map_err(|error| format!("Failed to create output directory: {error}"))?;
+
+    Ok(WorkerCommand::StartBatch {
+        batch_id: batch_id.to_string(),
+        input_paths,
+        output_dir,
+        compute_mode,
+    })
+}
+
+/// Re-runs the unfinished files of a batch that was interrupted by a crash or
+/// quit. Paused batches go through `resume_batch` instead.
+#[tauri::command]
+pub async fn restart_interrupted_batch(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    request: BatchControlRequest,
+) -> Result<BatchControlAck, String> {
+    let state = state.inner();
+    let batch_id = request.batch_id;
+    let previous = state.get_batch(&batch_id).await;
+    let (input_paths, parameters) = state.requeue_interrupted_batch(&batch_id).await?;
+    let sent = async {
+        let command = batch_command_from_parameters(&batch_id, input_paths, parameters)?;
+        let worker_sender = ensure_worker_sender(app.clone(), state.clone()).await?;
+        worker_sender
+            .send(command)
+            .map_err(|error| format!("Failed to enqueue restarted batch: {error}"))
+    }
+    .await;
+    if let Err(error) = sent {
+        if let Some(previous) = previous {
+            state.restore_interrupted_batch(previous).await;
+        }
+        return Err(error);
+    }
+
+    Ok(BatchControlAck {
+        batch_id,
+        accepted: true,
+    })
+}
+
+async fn task_command_from_parameters(
+    app: &AppHandle,
+    task_id: &str,
+    input_paths: Vec<String>,
+    parameters: RunParameters,
+) -> Result<WorkerCommand, String> {
+    match parameters {
+        RunParameters::Transcription { yap_mode } => Ok(WorkerCommand::StartTranscriptionBatch {
+            task_id: task_id.to_string(),
+            input_paths,
+            yap_mode,
+        }),
+        RunParameters::Flag { settings, profile } => Ok(WorkerCommand::StartFlagBatch {
+            task_id: task_id.to_string(),
+            input_paths,
+            settings: with_saved_api_keys(app, settings)?,
+            profile,
+        }),
+        RunParameters::Cut {
+            video_path,
+            ranges,
+            output_mode,
+        } => Ok(WorkerCommand::StartCutJob {
+            task_id: task_id.to_string(),
+            video_path,
+            ranges,
+            output_mode,
+        }),
+        RunParameters::CutBatch { plan, output_mode } => Ok(WorkerCommand::StartCutBatch {
+            task_id: task_id.to_string(),
+            jobs: plan_cut_batch_jobs(app, &input_paths, &plan, &output_mode).await?,
+            output_mode,
+        }),
+        RunParameters::Pipeline {
+            stages,
+            settings,
+            profile,
+        } => Ok(WorkerCommand::StartPipeline {
+            task_id: task_id.to_string(),
+            input_paths,
+            stages,
+            settings: with_saved_api_keys(app, settings)?,
+            profile,
+        }),
+        RunParameters::RemoveMusic { .. } => {
+            Err(format!("Task {task_id} has parameters for a different kind of run."))
+        }
+    }
+}
+
+/// Sends a task command to the worker, except blacklist flag tasks, which run
+/// natively in the app without starting the worker.
+async fn dispatch_task_command(
+    app: &AppHandle,
+    state: &AppState,
+    command: WorkerCommand,
+    enqueue_error: &str,
+) -> Result<(), String> {
+    if let WorkerCommand::StartFlagBatch {
+        task_id,
+        input_paths,
+        settings,
+        profile,
+    } = &command
+    {
+        if settings.engine.trim().eq_ignore_ascii_case(blacklist::ENGINE) {
+            blacklist::start_flag_task(
+                app.clone(),
+                state.clone(),
+                task_id.clone(),
+                input_paths.clone(),
+                settings.clone(),
+                profile.clone(),
+            )
+            .await;
+            return Ok(());
+        }
+    }
+
+    let worker_sender = ensure_worker_sender(app.clone(), state.clone()).await?;
+    worker_sender
+        .send(command)
+        .map_err(|error| format!("{enqueue_error}: {error}"))
+}
+
+/// Re-runs the unfinished files of a task that was interrupted by a crash or
+/// quit. Paused tasks go through `resume_task` instead.
+#[tauri::command]
+pub async fn restart_interrupted_task(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    request: TaskControlRequest,
+) -> Result<TaskControlAck, String> {
+    let state = state.inner();
+    let task_id = request.task_id;
+    let previous = state.get_task(&task_id).await;
+    let (input_paths, parameters) = state.requeue_interrupted_task(&task_id).await?;
+    let sent = async {
+        let command = task_command_from_parameters(&app, &task_id, input_paths, parameters).await?;
+        dispatch_task_command(&app, state, command, "Failed to enqueue restarted task").await
+    }
+    .await;
+    if let Err(error) = sent {
+        if let Some(previous) = previous {
+            state.restore_interrupted_task(previous).await;
+        }
+        return Err(error);
+    }
+
+    Ok(TaskControlAck {
+        task_id,
+        accepted: true,
+    })
+}
+
+/// Starts a new run over some (or all failed) jobs of a finished batch or task,
+/// with the original run's settings and `parent_task_id` pointing back at it.
+#[tauri::command]
+pub async fn retry_jobs(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    request: RetryJobsRequest,
+) -> Result<RetryStartedResponse, String> {
+    let job_ids = request.job_ids.as_deref();
+
+    if state.get_batch(&request.id).await.is_some() {
+        let (input_paths, parameters) = state.batch_retry_inputs(&request.id, job_ids).await?;
+        let batch_id = Uuid::new_v4().to_string();
+        let command = batch_command_from_parameters(&batch_id, input_paths.clone(), parameters.clone())?;
+        let worker_sender = ensure_worker_sender(app.clone(), state.inner().clone()).await?;
+
+        state
+            .insert_batch(BatchState {
+                batch_id: batch_id.clone(),
+                status: BatchStatus::Queued,
+                jobs: create_batch_jobs(&input_paths),
+                summary: None,
+                created_at_epoch_seconds: now_epoch_seconds(),
+                parameters: Some(parameters),
+                parent_task_id: Some(request.id.clone()),
+            })
+            .await;
+
+        if let Err(error) = worker_sender.send(command) {
+            state.remove_batch(&batch_id).await;
+            return Err(format!("Failed to enqueue retry batch: {error}"));
+        }
+
+        return Ok(RetryStartedResponse {
+            batch_id: Some(batch_id),
+            task_id: None,
+            parent_task_id: request.id,
+            file_count: input_paths.len(),
+            input_paths,
+        });
+    }
+
+    let (task_kind, input_paths, parameters) = state.task_retry_inputs(&request.id, job_ids).await?;
+    let task_id = Uuid::new_v4().to_string();
+    let command = task_command_from_parameters(&app, &task_id, input_paths.clone(), parameters.clone()).await?;
+
+    state
+        .insert_task(TaskState {
+            task_id: task_id.clone(),
+            task_kind,
+            status: TaskStatus::Queued,
+            jobs: create_task_jobs(&input_paths),
+            summary: None,
+            created_at_epoch_seconds: now_epoch_seconds(),
+            parameters: Some(parameters),
+            parent_task_id: Some(request.id.clone()),
+        })
+        .await;
+
+    if let Err(error) = dispatch_task_command(&app, state.inner(), command, "Failed to enqueue retry task").await {
+        state.remove_task(&task_id).await;
+        return Err(error);
+    }
+
+    Ok(RetryStartedResponse {
+        batch_id: None,
+        task_id: Some(task_id),
+        parent_task_id: request.id,
+        file_count: input_paths.len(),
+        input_paths,
+    })
+}
+
+/// Pauses the batch once its current file finishes; the queue is kept so
+/// `resume_batch` continues with the next file.
+#[tauri::command]
+pub async fn pause_batch(
+    state: State<'_, AppState>,
+    request: BatchControlRequest,
+) -> Result<BatchControlAck, String> {
+    set_batch_paused(state.inner(), request.batch_id, true).await
+}
+
+/// Resumes a paused batch with its next file.
+#[tauri::command]
+pub async fn resume_batch(
+    state: State<'_, AppState>,
+    request: BatchControlRequest,
+) -> Result<BatchControlAck, String> {
+    set_batch_paused(state.inner(), request.batch_id, false).await
+}
+
+#[tauri::command]
+pub async fn pause_task(
+    state: State<'_, AppState>,
+    request: TaskControlRequest,
+) -> Result<TaskControlAck, String> {
+    set_task_paused(state.inner(), request.task_id, true).await
+}
+
+/// Resumes a paused task with its next file.
+#[tauri::command]
+pub async fn resume_task(
+    state: State<'_, AppState>,
+    request: TaskControlRequest,
+) -> Result<TaskControlAck, String> {
+    set_task_paused(state.inner(), request.task_id, false).await
+}
+
 #[tauri::command]
 pub async fn get_batch_state(
     state: State<'_, AppState>,
@@ -541,6 +1346,14 @@ pub async fn get_task_state(
     Ok(get_task_state_inner(state.inner(), &task_id).await)
 }
 
+#[tauri::command]
+pub async fn list_tasks(
+    state: State<'_, AppState>,
+    request: Option<ListTasksRequest>,
+) -> Result<Vec<TaskHistoryItem>, String> {
+    Ok(state.list_history(&request.unwrap_or_default()).await)
+}
+
 #[tauri::command]
 pub async fn list_videos(request: ListVideosRequest) -> Result<Vec<VideoListItem>, String> {
     let input_dir = Path::new(&request.input_dir);
@@ -568,13 +1381,163 @@ pub async fn save_moderation_settings(
     app: AppHandle,
     request: ModerationSettings,
 ) -> Result<SaveAck, String> {
+    blacklist::validate_rules(&request.rules)?;
     write_moderation_settings(&app, &request)?;
     Ok(SaveAck { success: true })
 }
 
+/// Runs unsaved moderation settings over sample text or a subtitle file,
+/// without saving them or starting a task.
 #[tauri::command]
-pub async fn read_text_file(path: String) -> Result<String, String> {
-    let validated_path = validate_read_text_file_path(&path)?;
+pub async fn test_moderation_rules(
+    app: AppHandle,
+    request: TestModerationRulesRequest,
+) -> Result<RuleTestReport, String> {
+    blacklist::validate_rules(&request.settings.rules)?;
+    let lines = match (request.text, request.subtitle_path) {
+        (Some(text), None) => RuleTestLine::from_text(&text),
+        (None, Some(subtitle_path)) => {
+            let (validated_path, content) = read_sidecar_text(&subtitle_path).await?;
+            let format = subtitle_format_of(&validated_path)?;
+            parse_subtitles(&content, format).iter().map(RuleTestLine::from_cue).collect()
+        }
+        _ => return Err("Provide either text or a subtitle path to test the rules against.".to_string()),
+    };
+
+    Ok(blacklist::test_rules(lines, &request.settings, &ProfanityMatcher::load(&app)))
+}
+
+#[tauri::command]
+pub async fn list_moderation_profiles(app: AppHandle) -> Result<Vec<ModerationProfileSummary>, String> {
+    let default_profile = default_moderation_profile(read_or_initialize_moderation_settings(&app)?);
+    let mut summaries = vec![default_profile.summary()];
+    summaries.extend(profiles::list_profiles(&moderation_profiles_dir(&app)?));
+    Ok(summaries)
+}
+
+#[tauri::command]
+pub async fn get_moderation_profile(app: AppHandle, id: String) -> Result<ModerationProfile, String> {
+    read_moderation_profile(&app, &id)
+}
+
+/// Saving the default profile is the same as `save_moderation_settings`;
+/// other profiles are stored without API keys.
+#[tauri::command]
+pub async fn save_moderation_profile(
+    app: AppHandle,
+    request: SaveModerationProfileRequest,
+) -> Result<SaveAck, String> {
+    blacklist::validate_rules(&request.settings.rules)?;
+    write_moderation_profile(&app, &request.id, &request.settings)?;
+    Ok(SaveAck { success: true })
+}
+
+#[tauri::command]
+pub async fn create_moderation_profile(
+    app: AppHandle,
+    request: CreateModerationProfileRequest,
+) -> Result<ModerationProfile, String> {
+    let settings = request.settings.unwrap_or_else(default_moderation_settings);
+    blacklist::validate_rules(&settings.rules)?;
+    profiles::create_profile(&moderation_profiles_dir(&app)?, &request.name, without_api_keys(&settings))
+}
+
+#[tauri::command]
+pub async fn clone_moderation_profile(
+    app: AppHandle,
+    request: CloneModerationProfileRequest,
+) -> Result<ModerationProfile, String> {
+    let source = read_moderation_profile(&app, &request.source_id)?;
+    profiles::create_profile(
+        &moderation_profiles_dir(&app)?,
+        &request.name,
+        without_api_keys(&source.settings),
+    )
+}
+
+#[tauri::command]
+pub async fn delete_moderation_profile(app: AppHandle, id: String) -> Result<SaveAck, String> {
+    profiles::delete_profile(&moderation_profiles_dir(&app)?, &id)?;
+    Ok(SaveAck { success: true })
+}
+
+fn validate_rule_pack_path(path: &str) -> Result<PathBuf, String> {
+    if path.trim().is_empty() {
+        return Err("File path is required.".to_string());
+    }
+    let requested = PathBuf::from(path);
+    if !requested.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("json")) {
+        return Err("Rule packs must be .json files.".to_string());
+    }
+    Ok(requested)
+}
+
+/// Writes the rules and profanity words of a moderation profile to a rule
+/// pack; API keys and the other settings stay behind.
+#[tauri::command]
+pub async fn export_moderation_rules(
+    app: AppHandle,
+    request: ExportModerationRulesRequest,
+) -> Result<ExportModerationRulesResponse, String> {
+    let output_path = validate_rule_pack_path(&request.output_path)?;
+    if output_path.is_dir() {
+        return Err(format!("Path is not a file: {}", output_path.display()));
+    }
+    if output_path.exists() && !request.overwrite {
+        return Err(format!("Rule pack already exists: {}", output_path.display()));
+    }
+
+    let profile = read_moderation_profile(&app, request.profile.as_deref().unwrap_or(DEFAULT_PROFILE_ID))?;
+    let pack = rule_packs::export_rule_pack(&profile.name, &profile.settings, format_created_at(SystemTime::now()));
+    let content =
+        serde_json::to_string_pretty(&pack).map_err(|error| format!("Failed serializing rule pack: {error}"))?;
+    tokio_fs::write(&output_path, content)
+        .await
+        .map_err(|error| format!("Failed writing rule pack {}: {error}", output_path.display()))?;
+
+    Ok(ExportModerationRulesResponse {
+        output_path: output_path.to_string_lossy().to_string(),
+        rule_count: pack.rules.len(),
+        profanity_word_count: pack.profanity_words.len(),
+    })
+}
+
+#[tauri::command]
+pub async fn import_moderation_rules(
+    app: AppHandle,
+    request: ImportModerationRulesRequest,
+) -> Result<RuleImportReport, String> {
+    let path = validate_rule_pack_path(&request.path)?;
+    let metadata = tokio_fs::metadata(&path)
+        .await
+        .map_err(|error| format!("Failed reading file metadata {}: {error}", path.display()))?;
+    if !metadata.is_file() {
+        return Err(format!("Path is not a file: {}", path.display()));
+    }
+    if metadata.len() > MAX_READ_TEXT_FILE_BYTES {
+        return Err(format!(
+            "File is too large to read safely (max {} bytes): {}",
+            MAX_READ_TEXT_FILE_BYTES,
+            path.display()
+        ));
+    }
+    let content = tokio_fs::read_to_string(&path)
+        .await
+        .map_err(|error| format!("Failed reading rule pack {}: {error}", path.display()))?;
+    let pack = rule_packs::parse_rule_pack(&content)?;
+    blacklist::validate_rules(&pack.rules)?;
+
+    let id = request.profile.as_deref().unwrap_or(DEFAULT_PROFILE_ID);
+    let mut settings = read_moderation_profile(&app, id)?.settings;
+    let report = rule_packs::import_rule_pack(&mut settings, pack, request.strategy);
+    if !request.dry_run {
+        write_moderation_profile(&app, id, &settings)?;
+    }
+    Ok(report)
+}
+
+async fn read_sidecar_text(path: &str) -> Result<(PathBuf, String), String> {
+    let validated_path = validate_read_text_file_path(path)?;
     let metadata = tokio_fs::metadata(&validated_path)
         .await
         .map_err(|error| format!("Failed reading file metadata {}: {error}", validated_path.display()))?;
@@ -586,9 +1549,124 @@ pub async fn read_text_file(path: String) -> Result<String, String> {
         ));
     }
 
-    tokio_fs::read_to_string(&validated_path)
+    let content = tokio_fs::read_to_string(&validated_path)
+        .await
+        .map_err(|error| format!("Failed reading file {}: {error}", validated_path.display()))?;
+    Ok((validated_path, content))
+}
+
+#[tauri::command]
+pub async fn read_text_file(path: String) -> Result<String, String> {
+    let (_, content) = read_sidecar_text(&path).await?;
+    Ok(content)
+}
+
+fn subtitle_format_of(path: &Path) -> Result<SubtitleFormat, String> {
+    SubtitleFormat::from_path(path).ok_or_else(|| format!("Not a subtitle file: {}", path.display()))
+}
+
+/// Parses `.srt`, `.vtt`, `.ass` and `.ssa` sidecars into the same cues.
+#[tauri::command]
+pub async fn parse_srt_file(path: String) -> Result<Vec<SubtitleCue>, String> {
+    let (validated_path, content) = read_sidecar_text(&path).await?;
+    let format = subtitle_format_of(&validated_path)?;
+    Ok(parse_subtitles(&content, format))
+}
+
+async fn read_analysis_sidecar(path: &str) -> Result<(PathBuf, AnalysisDocument), String> {
+    let (validated_path, content) = read_sidecar_text(path).await?;
+    let file_name = validated_path.file_name().and_then(|value| value.to_str()).unwrap_or_default();
+    if analysis_file_stem(file_name).is_none() {
+        return Err(format!("Not an .analysis.json sidecar: {}", validated_path.display()));
+    }
+
+    let document = parse_analysis_document(&content)
+        .map_err(|error| format!("Invalid analysis sidecar {}: {error}", validated_path.display()))?;
+    Ok((validated_path, document))
+}
+
+#[tauri::command]
+pub async fn load_analysis(path: String) -> Result<AnalysisDocument, String> {
+    let (_, document) = read_analysis_sidecar(&path).await?;
+    Ok(document)
+}
+
+#[tauri::command]
+pub async fn review_flagged_item(request: ReviewFlaggedItemRequest) -> Result<AnalysisDocument, String> {
+    let (validated_path, mut document) = read_analysis_sidecar(&request.analysis_path).await?;
+    let edit = FlagEdit {
+        start_time: request.start_time,
+        end_time: request.end_time,
+        priority: request.priority,
+    };
+    apply_review(&mut document, request.index, request.decision, &edit, now_epoch_seconds())?;
+    write_analysis_document(&validated_path, &document)?;
+    Ok(document)
+}
+
+async fn read_analysis_sidecars(paths: &[String]) -> Result<Vec<AnalysisDocument>, String> {
+    let mut documents = Vec::with_capacity(paths.len());
+    for path in paths {
+        let (_, document) = read_analysis_sidecar(path).await?;
+        documents.push(document);
+    }
+    Ok(documents)
+}
+
+#[tauri::command]
+pub async fn merge_analyses(request: MergeAnalysesRequest) -> Result<AnalysisDocument, String> {
+    let documents = read_analysis_sidecars(&request.analysis_paths).await?;
+    let merged = analysis::merge_analyses(&documents, request.mode)?;
+    if let Some(output_path) = request.output_path.as_deref() {
+        write_analysis_document(&validate_write_analysis_path(output_path)?, &merged)?;
+    }
+    Ok(merged)
+}
+
+#[tauri::command]
+pub async fn diff_analyses(request: DiffAnalysesRequest) -> Result<AnalysisDiff, String> {
+    let documents = read_analysis_sidecars(&request.analysis_paths).await?;
+    analysis::diff_analyses(&documents)
+}
+
+#[tauri::command]
+pub async fn write_analysis_file(request: WriteAnalysisFileRequest) -> Result<SaveAck, String> {
+    let path = validate_write_analysis_path(&request.path)?;
+    write_analysis_document(&path, &request.document)?;
+    Ok(SaveAck { success: true })
+}
+
+#[tauri::command]
+pub async fn convert_subtitle_file(request: ConvertSubtitleRequest) -> Result<ConvertSubtitleResponse, String> {
+    let target_format = SubtitleFormat::from_extension(&request.target_format)
+        .ok_or_else(|| "Unsupported subtitle format. Use srt, vtt or ass.".to_string())?;
+    let (validated_path, content) = read_sidecar_text(&request.path).await?;
+    let source_format = subtitle_format_of(&validated_path)?;
+    if source_format == target_format {
+        return Err(format!(
+            "Subtitle file is already .{}: {}",
+            target_format.extension(),
+            validated_path.display()
+        ));
+    }
+
+    let cues = parse_subtitles(&content, source_format);
+    if cues.is_empty() {
+        return Err(format!("No subtitle cues found in {}", validated_path.display()));
+    }
+
+    let output_path = validated_path.with_extension(target_format.extension());
+    if output_path.exists() && !request.overwrite {
+        return Err(format!("Subtitle file already exists: {}", output_path.display()));
+    }
+    tokio_fs::write(&output_path, serialize_subtitles(&cues, target_format))
         .await
-        .map_err(|error| format!("Failed reading file {}: {error}", validated_path.display()))
+        .map_err(|error| format!("Failed writing subtitle file {}: {error}", output_path.display()))?;
+
+    Ok(ConvertSubtitleResponse {
+        output_path: output_path.to_string_lossy().to_string(),
+        cue_count: cues.len(),
+    })
 }
 
 #[tauri::command]
@@ -612,13 +1690,16 @@ pub async fn open_folder_picker(app: AppHandle) -> Result<Option<String>, String
 #[cfg(test)]
 mod tests {
     use super::{
-        create_task_jobs, default_moderation_settings, ensure_supported_cancel_mode,
+        create_task_jobs, default_moderation_settings, ensure_batch_can_change_pause,
+        ensure_supported_cancel_mode, ensure_task_can_change_pause, ensure_task_has_pause_point,
         ensure_supported_cut_output_mode, ensure_supported_output_mode, ensure_supported_yap_mode,
+        ensure_valid_pipeline_stages,
         get_batch_state_inner, get_task_state_inner, require_worker_sender,
-        validate_read_text_file_path,
+        validate_read_text_file_path, validate_write_analysis_path, without_api_keys,
     };
     use crate::state::AppState;
-    use uuid::Uuid;
+    use crate::ids::unique_temp_dir;
+    use crate::types::{BatchStatus, CutPlanOptions, PipelineStage, RunParameters, TaskStatus};
 
     #[test]
     fn should_reject_unsupported_output_mode() {
@@ -632,6 +1713,12 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn should_accept_mute_and_bleep_cut_output_modes() {
+        assert!(ensure_supported_cut_output_mode("mute").is_ok());
+        assert!(ensure_supported_cut_output_mode("bleep").is_ok());
+    }
+
     #[test]
     fn should_reject_unsupported_yap_mode() {
         let result = ensure_supported_yap_mode("manual");
@@ -640,7 +1727,7 @@ mod tests {
 
     #[test]
     fn should_allow_reading_srt_sidecars() {
-        let base_dir = std::env::temp_dir().join(format!("al-iyaal-read-sidecar-{}", Uuid::new_v4()));
+        let base_dir = unique_temp_dir("read-sidecar");
         std::fs::create_dir_all(&base_dir).unwrap();
         let path = base_dir.join("episode.srt");
         std::fs::write(&path, "1").unwrap();
@@ -652,16 +1739,37 @@ mod tests {
         std::fs::remove_dir_all(base_dir).unwrap();
     }
 
+    #[test]
+    fn should_only_write_analysis_sidecars_next_to_media() {
+        let base_dir = unique_temp_dir("write-sidecar");
+        std::fs::create_dir_all(&base_dir).unwrap();
+        std::fs::write(base_dir.join("episode.mp4"), "video").unwrap();
+        let path_of = |file_name: &str| base_dir.join(file_name).to_string_lossy().to_string();
+
+        let validated = validate_write_analysis_path(&path_of("episode.analysis.json"));
+        let orphan = validate_write_analysis_path(&path_of("missing.analysis.json"));
+        let wrong_name = validate_write_analysis_path(&path_of("episode.json"));
+
+        assert_eq!(
+            validated.unwrap(),
+            base_dir.canonicalize().unwrap().join("episode.analysis.json")
+        );
+        assert!(orphan.unwrap_err().contains("No video or subtitle file"));
+        assert!(wrong_name.unwrap_err().contains("Only .analysis.json"));
+
+        std::fs::remove_dir_all(base_dir).unwrap();
+    }
+
     #[test]
     fn should_reject_non_sidecar_files_for_read_text_file() {
-        let base_dir = std::env::temp_dir().join(format!("al-iyaal-read-sidecar-{}", Uuid::new_v4()));
+        let base_dir = unique_temp_dir("read-sidecar");
         std::fs::create_dir_all(&base_dir).unwrap();
         let path = base_dir.join("notes.txt");
         std::fs::write(&path, "secret").unwrap();
 
         let error = validate_read_text_file_path(path.to_string_lossy().as_ref()).unwrap_err();
 
-        assert!(error.contains(".srt and .analysis.json"));
+        assert!(error.contains(".ssa and .analysis.json"));
 
         std::fs::remove_dir_all(base_dir).unwrap();
     }
@@ -689,10 +1797,64 @@ mod tests {
 
     #[test]
     fn should_reject_unsupported_cancel_mode() {
-        let result = ensure_supported_cancel_mode("immediate");
+        let result = ensure_supported_cancel_mode("abort_everything");
         assert!(result.is_err());
     }
 
+    #[test]
+    fn should_accept_immediate_cancel_mode() {
+        assert!(ensure_supported_cancel_mode("immediate").is_ok());
+        assert!(ensure_supported_cancel_mode("stop_after_current").is_ok());
+    }
+
+    #[test]
+    fn should_strip_api_keys_from_stored_settings() {
+        let mut settings = default_moderation_settings();
+        settings.google_api_key = "secret".to_string();
+        settings.amazon_nova_api_key = "secret".to_string();
+
+        let stored = without_api_keys(&settings);
+
+        assert!(stored.google_api_key.is_empty());
+        assert!(stored.amazon_nova_api_key.is_empty());
+        assert_eq!(stored.rules, settings.rules);
+    }
+
+    #[test]
+    fn should_only_pause_active_batches() {
+        assert!(ensure_batch_can_change_pause("b", Some(&BatchStatus::Running), true).is_ok());
+        assert!(ensure_batch_can_change_pause("b", Some(&BatchStatus::Paused), false).is_ok());
+        assert!(ensure_batch_can_change_pause("b", Some(&BatchStatus::Paused), true).is_err());
+        assert!(ensure_batch_can_change_pause("b", Some(&BatchStatus::Completed), false).is_err());
+        assert!(ensure_batch_can_change_pause("b", None, true).is_err());
+        assert!(ensure_batch_can_change_pause("b", Some(&BatchStatus::Interrupted), false).is_err());
+    }
+
+    #[test]
+    fn should_only_pause_active_tasks() {
+        assert!(ensure_task_can_change_pause("t", Some(&TaskStatus::Queued), true).is_ok());
+        assert!(ensure_task_can_change_pause("t", Some(&TaskStatus::Cancelled), true).is_err());
+        assert!(ensure_task_can_change_pause("t", Some(&TaskStatus::Paused), true).is_err());
+        assert!(ensure_task_can_change_pause("t", Some(&TaskStatus::Interrupted), false).is_err());
+    }
+
+    #[test]
+    fn should_reject_pausing_single_cut_jobs() {
+        let cut = RunParameters::Cut {
+            video_path: "/tmp/a.mov".to_string(),
+            ranges: Vec::new(),
+            output_mode: "mute".to_string(),
+        };
+        let cut_batch = RunParameters::CutBatch {
+            plan: CutPlanOptions::default(),
+            output_mode: "mute".to_string(),
+        };
+
+        assert!(ensure_task_has_pause_point("t", Some(&cut)).is_err());
+        assert!(ensure_task_has_pause_point("t", Some(&cut_batch)).is_ok());
+        assert!(ensure_task_has_pause_point("t", None).is_ok());
+    }
+
     #[test]
     fn should_create_task_jobs_with_empty_logs() {
         let jobs = create_task_jobs(&["/tmp/a.mov".to_string()]);
@@ -706,4 +1868,38 @@ mod tests {
         assert!(!settings.rules.is_empty());
         assert_eq!(settings.rules[0].priority, "high");
     }
+
+    #[test]
+    fn should_accept_pipeline_stages_in_order() {
+        let stages: Vec<PipelineStage> = serde_json::from_value(serde_json::json!([
+            { "stage": "transcribe" },
+            { "stage": "flag", "engine": "blacklist" },
+            { "stage": "cut", "minPriority": "medium", "paddingBeforeSeconds": 0.5 },
+        ]))
+        .expect("stages should deserialize");
+
+        assert!(ensure_valid_pipeline_stages(&stages).is_ok());
+    }
+
+    #[test]
+    fn should_reject_out_of_order_or_empty_pipelines() {
+        let stages: Vec<PipelineStage> = serde_json::from_value(serde_json::json!([
+            { "stage": "flag" },
+            { "stage": "transcribe" },
+        ]))
+        .expect("stages should deserialize");
+
+        assert!(ensure_valid_pipeline_stages(&stages).is_err());
+        assert!(ensure_valid_pipeline_stages(&[]).is_err());
+    }
+
+    #[test]
+    fn should_reject_unsupported_pipeline_stage_options() {
+        let stages: Vec<PipelineStage> = serde_json::from_value(serde_json::json!([
+            { "stage": "cut", "minPriority": "urgent" },
+        ]))
+        .expect("stages should deserialize");
+
+        assert!(ensure_valid_pipeline_stages(&stages).is_err());
+    }
 }
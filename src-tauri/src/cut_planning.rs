@@ -0,0 +1,287 @@
+use std::path::Path;
+
+use tokio::process::Command;
+
+use crate::{
+    analysis::{read_analysis_document, AnalysisDocument, FlagPriority, FlaggedItem, ReviewDecision},
+    protocol::is_audio_redaction_mode,
+    types::{CutPlanOptions, CutRange, PlannedCutJob},
+};
+
+/// Keep segments shorter than this are dropped rather than sliced.
+const MIN_KEEP_SECONDS: f64 = 0.05;
+
+pub fn ensure_valid_cut_plan_options(options: &CutPlanOptions) -> Result<(), String> {
+    if FlagPriority::parse(&options.min_priority).is_none() {
+        return Err("Unsupported minimum priority. Use high, medium or low.".to_string());
+    }
+    let paddings = [options.padding_before_seconds, options.padding_after_seconds];
+    if paddings.iter().any(|padding| !padding.is_finite() || *padding < 0.0) {
+        return Err("Cut padding must be zero or more seconds.".to_string());
+    }
+
+    Ok(())
+}
+
+/// Confirmed items are always cut and dismissed ones never; the rest (edited
+/// items with their edited values) go through the priority and category filters.
+fn matches_options(segment: &FlaggedItem, options: &CutPlanOptions) -> bool {
+    match segment.review_decision() {
+        Some(ReviewDecision::Confirmed) => return true,
+        Some(ReviewDecision::Dismissed) => return false,
+        Some(ReviewDecision::Edited) | None => {}
+    }
+    let Some(min_priority) = FlagPriority::parse(&options.min_priority) else {
+        return false;
+    };
+
+    segment.priority <= min_priority
+        && options
+            .categories
+            .as_ref()
+            .is_none_or(|categories| categories.contains(&segment.category))
+}
+
+/// Padded, merged `(start, end)` seconds of the segments to remove, clamped to
+/// `[0, duration_seconds]` and sorted by start.
+fn plan_removed_ranges(
+    segments: &[FlaggedItem],
+    duration_seconds: f64,
+    options: &CutPlanOptions,
+) -> Vec<(f64, f64)> {
+    let mut intervals = segments
+        .iter()
+        .filter(|segment| matches_options(segment, options))
+        .map(|segment| {
+            (
+                (segment.start_time - options.padding_before_seconds).max(0.0),
+                (segment.end_time + options.padding_after_seconds).min(duration_seconds),
+            )
+        })
+        .filter(|(start, end)| end > start)
+        .collect::<Vec<_>>();
+    intervals.sort_by(|left, right| left.0.total_cmp(&right.0));
+
+    let mut merged: Vec<(f64, f64)> = Vec::new();
+    for (start, end) in intervals {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+/// Cut jobs keep the ranges they are given, so the kept ranges are the
+/// complement of the removed ones.
+fn plan_keep_ranges(removed: &[(f64, f64)], duration_seconds: f64) -> Vec<(f64, f64)> {
+    let mut keep = Vec::new();
+    let mut cursor = 0.0_f64;
+    for &(start, end) in removed.iter().chain([(duration_seconds, duration_seconds)].iter()) {
+        if start - cursor >= MIN_KEEP_SECONDS {
+            keep.push((cursor, start));
+        }
+        cursor = cursor.max(end);
+    }
+    keep
+}
+
+fn format_timestamp(seconds: f64) -> String {
+    let total_millis = (seconds.max(0.0) * 1000.0).round() as u64;
+    let hours = total_millis / 3_600_000;
+    let minutes = (total_millis % 3_600_000) / 60_000;
+    let millis = total_millis % 60_000;
+    if hours > 0 {
+        return format!("{hours}:{minutes:02}:{:02}.{:03}", millis / 1000, millis % 1000);
+    }
+    format!("{minutes}:{:02}.{:03}", millis / 1000, millis % 1000)
+}
+
+fn to_cut_ranges(ranges: &[(f64, f64)]) -> Vec<CutRange> {
+    ranges
+        .iter()
+        .map(|(start, end)| CutRange {
+            start: format_timestamp(*start),
+            end: format_timestamp(*end),
+        })
+        .collect()
+}
+
+/// The `(keep, removed)` ranges for a video of `duration_seconds` whose
+/// flagged segments are listed in `document`.
+pub fn plan_cuts(
+    document: &AnalysisDocument,
+    duration_seconds: f64,
+    options: &CutPlanOptions,
+) -> (Vec<CutRange>, Vec<CutRange>) {
+    let removed = plan_removed_ranges(&document.flagged, duration_seconds, options);
+    let keep = plan_keep_ranges(&removed, duration_seconds);
+    (to_cut_ranges(&keep), to_cut_ranges(&removed))
+}
+
+/// Seconds from the `Duration: HH:MM:SS.ss` line of an ffmpeg input header.
+fn parse_ffmpeg_duration(stderr: &str) -> Option<f64> {
+    let (_, rest) = stderr.split_once("Duration:")?;
+    let value = rest.trim_start().split(',').next()?.trim();
+    let mut parts = value.splitn(3, ':');
+    let hours = parts.next()?.parse::<u64>().ok()?;
+    let minutes = parts.next()?.parse::<u64>().ok()?;
+    let seconds = parts.next()?.parse::<f64>().ok()?;
+    Some((hours * 3600 + minutes * 60) as f64 + seconds)
+}
+
+pub async fn probe_duration_seconds(ffmpeg_executable: &Path, video_path: &str) -> Result<f64, String> {
+    // `ffmpeg -i` without an output exits non-zero but still prints the input header.
+    let output = Command::new(ffmpeg_executable)
+        .args(["-hide_banner", "-i", video_path])
+        .output()
+        .await
+        .map_err(|error| format!("Failed to run {}: {error}", ffmpeg_executable.display()))?;
+    parse_ffmpeg_duration(&String::from_utf8_lossy(&output.stderr))
+        .filter(|duration_seconds| *duration_seconds > 0.0)
+        .ok_or_else(|| format!("Could not determine the duration of {video_path}"))
+}
+
+/// Plans one video's cut from its sidecar. A failure is carried on the job
+/// so the worker fails that video alone.
+pub async fn plan_cut_job(
+    ffmpeg_executable: &Path,
+    video_path: &str,
+    analysis_path: &Path,
+    options: &CutPlanOptions,
+    output_mode: &str,
+) -> PlannedCutJob {
+    let planned = async {
+        let document = read_analysis_document(analysis_path)?;
+        let duration_seconds = probe_duration_seconds(ffmpeg_executable, video_path).await?;
+        Ok::<_, String>(plan_cuts(&document, duration_seconds, options))
+    }
+    .await;
+    match planned {
+        Ok((keep_ranges, removed_ranges)) => PlannedCutJob {
+            video_path: video_path.to_string(),
+            ranges: if is_audio_redaction_mode(output_mode) {
+                removed_ranges.clone()
+            } else {
+                keep_ranges
+            },
+            removed_ranges,
+            error: None,
+        },
+        Err(error) => PlannedCutJob {
+            video_path: video_path.to_string(),
+            ranges: Vec::new(),
+            removed_ranges: Vec::new(),
+            error: Some(error),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        analysis::{FlagPriority, FlagReview, FlaggedItem, ReviewDecision},
+        types::CutPlanOptions,
+    };
+
+    use super::{format_timestamp, parse_ffmpeg_duration, plan_keep_ranges, plan_removed_ranges};
+
+    fn segment(start_time: f64, end_time: f64, priority: &str, category: &str) -> FlaggedItem {
+        FlaggedItem {
+            start_time,
+            end_time,
+            text: String::new(),
+            reason: String::new(),
+            priority: FlagPriority::parse(priority).unwrap(),
+            category: category.to_string(),
+            rule_id: "rule".to_string(),
+            review: None,
+            engines: Vec::new(),
+        }
+    }
+
+    fn review(decision: ReviewDecision) -> FlagReview {
+        FlagReview {
+            decision,
+            reviewed_at_epoch_seconds: 1,
+            original: None,
+        }
+    }
+
+    #[test]
+    fn should_filter_pad_and_merge_removed_ranges() {
+        let segments = vec![
+            segment(10.0, 12.0, "high", "music"),
+            segment(12.5, 14.0, "medium", "music"),
+            segment(30.0, 31.0, "low", "other"),
+            segment(58.0, 61.0, "high", "other"),
+        ];
+        let options = CutPlanOptions {
+            min_priority: "medium".to_string(),
+            padding_before_seconds: 0.5,
+            padding_after_seconds: 0.25,
+            ..CutPlanOptions::default()
+        };
+
+        let removed = plan_removed_ranges(&segments, 60.0, &options);
+
+        assert_eq!(removed, vec![(9.5, 14.25), (57.5, 60.0)]);
+    }
+
+    #[test]
+    fn should_filter_removed_ranges_by_category() {
+        let segments = vec![segment(1.0, 2.0, "high", "music"), segment(5.0, 6.0, "high", "other")];
+        let options = CutPlanOptions {
+            categories: Some(vec!["other".to_string()]),
+            ..CutPlanOptions::default()
+        };
+
+        assert_eq!(plan_removed_ranges(&segments, 10.0, &options), vec![(5.0, 6.0)]);
+    }
+
+    #[test]
+    fn should_merge_adjacent_ranges() {
+        let segments = vec![segment(1.0, 2.0, "high", "music"), segment(2.0, 3.0, "high", "music")];
+
+        assert_eq!(
+            plan_removed_ranges(&segments, 10.0, &CutPlanOptions::default()),
+            vec![(1.0, 3.0)]
+        );
+    }
+
+    #[test]
+    fn should_keep_the_complement_of_removed_ranges() {
+        assert_eq!(
+            plan_keep_ranges(&[(0.0, 2.0), (5.0, 6.0)], 10.0),
+            vec![(2.0, 5.0), (6.0, 10.0)]
+        );
+        assert!(plan_keep_ranges(&[(0.0, 10.0)], 10.0).is_empty());
+    }
+
+    #[test]
+    fn should_format_timestamps_for_cut_ranges() {
+        assert_eq!(format_timestamp(62.5), "1:02.500");
+        assert_eq!(format_timestamp(3723.25), "1:02:03.250");
+    }
+
+    #[test]
+    fn should_apply_review_decisions_when_planning() {
+        let mut confirmed = segment(1.0, 2.0, "low", "other");
+        let mut dismissed = segment(5.0, 6.0, "high", "other");
+        confirmed.review = Some(review(ReviewDecision::Confirmed));
+        dismissed.review = Some(review(ReviewDecision::Dismissed));
+
+        assert_eq!(
+            plan_removed_ranges(&[confirmed, dismissed], 10.0, &CutPlanOptions::default()),
+            vec![(1.0, 2.0)]
+        );
+    }
+
+    #[test]
+    fn should_parse_duration_from_ffmpeg_header() {
+        let stderr = "Input #0, mov,mp4\n  Duration: 00:01:02.50, start: 0.000000, bitrate: 512 kb/s\n";
+
+        assert_eq!(parse_ffmpeg_duration(stderr), Some(62.5));
+        assert_eq!(parse_ffmpeg_duration("Duration: N/A, bitrate: N/A"), None);
+    }
+}
@@ -3,7 +3,14 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use crate::types::{SrtListItem, VideoListItem};
+use crate::{
+    analysis::analysis_file_stem,
+    subtitles::SUBTITLE_EXTENSIONS,
+    types::{SrtListItem, VideoListItem},
+};
+
+/// Video containers whose `.analysis.json` sidecars the app may write.
+pub const VIDEO_EXTENSIONS: [&str; 6] = ["mp4", "mov", "m4v", "mkv", "webm", "avi"];
 
 pub fn collect_media_files(input_dir: &Path, allowed_extensions: &[String]) -> Result<Vec<PathBuf>, String> {
     if !input_dir.is_dir() {
@@ -38,13 +45,53 @@ pub fn build_output_dir(input_dir: &Path) -> PathBuf {
     input_dir.join("audio_replaced")
 }
 
+/// The first existing `.srt`, `.vtt`, `.ass` or `.ssa` sidecar of a video, in
+/// that order of preference.
+pub fn find_subtitle_sidecar(video_path: &Path) -> Option<PathBuf> {
+    SUBTITLE_EXTENSIONS
+        .iter()
+        .map(|extension| video_path.with_extension(extension))
+        .find(|path| path.is_file())
+}
+
+/// The video or subtitle file an `.analysis.json` (or engine-specific
+/// `.analysis.<engine>.json`) path belongs to, if one exists.
+pub fn find_analysis_source(analysis_path: &Path) -> Option<PathBuf> {
+    let stem = analysis_file_stem(analysis_path.file_name()?.to_str()?)?;
+    VIDEO_EXTENSIONS
+        .iter()
+        .chain(SUBTITLE_EXTENSIONS.iter())
+        .flat_map(|extension| [extension.to_string(), extension.to_ascii_uppercase()])
+        .map(|extension| analysis_path.with_file_name(format!("{stem}.{extension}")))
+        .find(|path| path.is_file())
+}
+
+/// Engine-specific `<stem>.analysis.<engine>.json` files among `file_names`.
+fn engine_analysis_paths(video_path: &Path, file_names: &[String]) -> Vec<String> {
+    let Some(stem) = video_path.file_stem().and_then(|value| value.to_str()) else {
+        return Vec::new();
+    };
+    let default_name = format!("{stem}.analysis.json");
+    file_names
+        .iter()
+        .filter(|name| **name != default_name && analysis_file_stem(name) == Some(stem))
+        .map(|name| video_path.with_file_name(name).to_string_lossy().to_string())
+        .collect()
+}
+
 pub fn discover_video_items(input_dir: &Path, allowed_extensions: &[String]) -> Result<Vec<VideoListItem>, String> {
     let files = collect_media_files(input_dir, allowed_extensions)?;
+    let analysis_extension = [".json".to_string()];
+    let mut analysis_file_names = collect_media_files(input_dir, &analysis_extension)?
+        .into_iter()
+        .filter_map(|path| path.file_name().and_then(|value| value.to_str()).map(str::to_string))
+        .collect::<Vec<_>>();
+    analysis_file_names.retain(|name| analysis_file_stem(name).is_some());
 
     let mut videos = files
         .into_iter()
         .map(|path| {
-            let srt_path = path.with_extension("srt");
+            let srt_path = find_subtitle_sidecar(&path);
             let analysis_path = path.with_extension("analysis.json");
             let file_name = path
                 .file_name()
@@ -55,14 +102,13 @@ pub fn discover_video_items(input_dir: &Path, allowed_extensions: &[String]) ->
             VideoListItem {
                 file_name,
                 path: path.to_string_lossy().to_string(),
-                srt_path: srt_path
-                    .exists()
-                    .then(|| srt_path.to_string_lossy().to_string()),
+                has_srt: srt_path.is_some(),
+                srt_path: srt_path.map(|srt_path| srt_path.to_string_lossy().to_string()),
                 analysis_path: analysis_path
                     .exists()
                     .then(|| analysis_path.to_string_lossy().to_string()),
-                has_srt: srt_path.exists(),
                 has_analysis: analysis_path.exists(),
+                engine_analysis_paths: engine_analysis_paths(&path, &analysis_file_names),
             }
         })
         .collect::<Vec<_>>();
@@ -72,7 +118,10 @@ pub fn discover_video_items(input_dir: &Path, allowed_extensions: &[String]) ->
 }
 
 pub fn discover_srt_items(input_dir: &Path) -> Result<Vec<SrtListItem>, String> {
-    let allowed_extensions = vec![".srt".to_string()];
+    let allowed_extensions = SUBTITLE_EXTENSIONS
+        .iter()
+        .map(|extension| format!(".{extension}"))
+        .collect::<Vec<_>>();
     let files = collect_media_files(input_dir, &allowed_extensions)?;
 
     let mut srt_files = files
@@ -102,13 +151,50 @@ pub fn discover_srt_items(input_dir: &Path) -> Result<Vec<SrtListItem>, String>
 
 #[cfg(test)]
 mod tests {
-    use std::path::Path;
+    use std::{fs, path::Path};
+
+    use crate::ids::unique_temp_dir;
 
-    use super::build_output_dir;
+    use super::{build_output_dir, discover_srt_items, discover_video_items};
 
     #[test]
     fn should_build_audio_replaced_output_dir() {
         let path = build_output_dir(Path::new("/tmp/example"));
         assert_eq!(path.to_string_lossy(), "/tmp/example/audio_replaced");
     }
+
+    #[test]
+    fn should_discover_vtt_and_ass_subtitle_sidecars() {
+        let base_dir = unique_temp_dir("subtitles");
+        fs::create_dir_all(&base_dir).unwrap();
+        for file_name in [
+            "a.mp4",
+            "a.vtt",
+            "a.analysis.json",
+            "a.analysis.gemini.json",
+            "b.mp4",
+            "b.ass",
+            "b.srt",
+            "c.mp4",
+            "notes.txt",
+        ] {
+            fs::write(base_dir.join(file_name), "").unwrap();
+        }
+
+        let videos = discover_video_items(&base_dir, &[".mp4".to_string()]).unwrap();
+        let subtitles = discover_srt_items(&base_dir).unwrap();
+        fs::remove_dir_all(&base_dir).unwrap();
+
+        let srt_names = videos
+            .iter()
+            .map(|video| video.srt_path.as_deref().map(|path| Path::new(path).file_name().unwrap().to_owned()))
+            .collect::<Vec<_>>();
+        assert_eq!(srt_names, vec![Some("a.vtt".into()), Some("b.srt".into()), None]);
+        assert_eq!(videos[0].engine_analysis_paths, vec![base_dir.join("a.analysis.gemini.json").to_string_lossy()]);
+        assert!(videos[1].engine_analysis_paths.is_empty());
+        assert_eq!(
+            subtitles.iter().map(|item| item.file_name.as_str()).collect::<Vec<_>>(),
+            vec!["a.vtt", "b.ass", "b.srt"]
+        );
+    }
 }
@@ -0,0 +1,336 @@
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use crate::{
+    state::{now_epoch_seconds, AppState},
+    types::{BatchState, BatchStatus, JobStatus, TaskJobStatus, TaskState, TaskStatus},
+};
+
+const HISTORY_FLUSH_INTERVAL: Duration = Duration::from_secs(2);
+/// Progress and log updates alone are flushed this often.
+const HISTORY_PROGRESS_FLUSH_INTERVAL: Duration = Duration::from_secs(30);
+const MAX_HISTORY_RUNS: usize = 200;
+const INTERRUPTED_JOB_ERROR: &str = "Interrupted when the app closed before this job finished.";
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct HistoryStore {
+    batches: Vec<BatchState>,
+    tasks: Vec<TaskState>,
+}
+
+fn history_store_path_from_dir(base_dir: &Path) -> PathBuf {
+    base_dir.join("history/runs.json")
+}
+
+fn history_store_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|error| format!("Failed to resolve history app data directory: {error}"))?;
+    Ok(history_store_path_from_dir(&app_data_dir))
+}
+
+fn read_store(path: &Path) -> Result<HistoryStore, String> {
+    if !path.exists() {
+        return Ok(HistoryStore::default());
+    }
+
+    let content = fs::read_to_string(path)
+        .map_err(|error| format!("Failed reading history store {}: {error}", path.display()))?;
+    serde_json::from_str(&content)
+        .map_err(|error| format!("Invalid history store {}: {error}", path.display()))
+}
+
+/// Reads the store, moving an unreadable one aside to
+/// `runs.json.corrupt-<epoch seconds>` so the next write cannot destroy it.
+/// Fails only when the file can be neither read nor moved.
+fn read_or_quarantine_store(path: &Path) -> Result<HistoryStore, String> {
+    let error = match read_store(path) {
+        Ok(store) => return Ok(store),
+        Err(error) => error,
+    };
+    let quarantine_path = path.with_file_name(format!(
+        "{}.corrupt-{}",
+        path.file_name().and_then(|name| name.to_str()).unwrap_or("runs.json"),
+        now_epoch_seconds()
+    ));
+    fs::rename(path, &quarantine_path).map_err(|rename_error| {
+        format!(
+            "{error}; failed moving it to {}: {rename_error}",
+            quarantine_path.display()
+        )
+    })?;
+    eprintln!("{error}; moved it to {}", quarantine_path.display());
+    Ok(HistoryStore::default())
+}
+
+fn write_store(path: &Path, store: &HistoryStore) -> Result<(), String> {
+    let Some(parent) = path.parent() else {
+        return Err(format!(
+            "Failed determining parent directory for history store {}",
+            path.display()
+        ));
+    };
+    fs::create_dir_all(parent)
+        .map_err(|error| format!("Failed creating history directory {}: {error}", parent.display()))?;
+
+    let content = serde_json::to_string(store)
+        .map_err(|error| format!("Failed serializing history store: {error}"))?;
+    let temp_path = parent.join(".runs.json.tmp");
+    let mut temp_file = fs::File::create(&temp_path)
+        .map_err(|error| format!("Failed creating temp history store {}: {error}", temp_path.display()))?;
+    temp_file
+        .write_all(content.as_bytes())
+        .and_then(|_| temp_file.sync_all())
+        .map_err(|error| format!("Failed writing temp history store {}: {error}", temp_path.display()))?;
+    drop(temp_file);
+
+    fs::rename(&temp_path, path)
+        .map_err(|error| format!("Failed renaming history store {}: {error}", path.display()))
+}
+
+/// Runs that were still active when the previous session ended can never
+/// finish: the worker that owned them is gone.
+fn mark_interrupted(store: &mut HistoryStore) {
+    for batch in store.batches.iter_mut().filter(|batch| batch.status.is_active()) {
+        batch.status = BatchStatus::Interrupted;
+        for job in batch.jobs.iter_mut().filter(|job| job.status == JobStatus::Running) {
+            job.status = JobStatus::Interrupted;
+            job.error = Some(INTERRUPTED_JOB_ERROR.to_string());
+        }
+    }
+
+    for task in store.tasks.iter_mut().filter(|task| task.status.is_active()) {
+        task.status = TaskStatus::Interrupted;
+        for job in task.jobs.iter_mut().filter(|job| job.status == TaskJobStatus::Running) {
+            job.status = TaskJobStatus::Interrupted;
+            job.error = Some(INTERRUPTED_JOB_ERROR.to_string());
+        }
+    }
+}
+
+/// Keeps the newest `MAX_HISTORY_RUNS` runs across batches and tasks.
+fn prune(store: &mut HistoryStore) {
+    let total_runs = store.batches.len() + store.tasks.len();
+    if total_runs <= MAX_HISTORY_RUNS {
+        return;
+    }
+
+    let mut created_at = store
+        .batches
+        .iter()
+        .map(|batch| batch.created_at_epoch_seconds)
+        .chain(store.tasks.iter().map(|task| task.created_at_epoch_seconds))
+        .collect::<Vec<_>>();
+    created_at.sort_unstable_by(|left, right| right.cmp(left));
+    let cutoff = created_at[MAX_HISTORY_RUNS - 1];
+
+    store
+        .batches
+        .retain(|batch| batch.created_at_epoch_seconds >= cutoff || batch.status.is_active());
+    store
+        .tasks
+        .retain(|task| task.created_at_epoch_seconds >= cutoff || task.status.is_active());
+}
+
+fn snapshot_store(batches: Vec<BatchState>, tasks: Vec<TaskState>) -> HistoryStore {
+    let mut store = HistoryStore { batches, tasks };
+    store.batches.sort_by(|left, right| left.batch_id.cmp(&right.batch_id));
+    store.tasks.sort_by(|left, right| left.task_id.cmp(&right.task_id));
+    prune(&mut store);
+    store
+}
+
+/// Loads the previous session's runs into `state`, marking unfinished ones as
+/// interrupted, and rewrites the store so the interruption is recorded. An
+/// error means the existing store could not be preserved, so the history
+/// writer must not run.
+pub async fn restore_history(app: &AppHandle, state: &AppState) -> Result<(), String> {
+    let path = history_store_path(app)?;
+    let mut store = read_or_quarantine_store(&path)?;
+    mark_interrupted(&mut store);
+    state.restore_history(store.batches.clone(), store.tasks.clone()).await;
+    if let Err(error) = write_store(&path, &store) {
+        eprintln!("history write error: {error}");
+        state.mark_history_dirty();
+    }
+    Ok(())
+}
+
+pub async fn flush_history(app: &AppHandle, state: &AppState) -> Result<(), String> {
+    let path = history_store_path(app)?;
+    let (batches, tasks) = state.history_snapshot().await;
+    write_store(&path, &snapshot_store(batches, tasks))
+}
+
+pub fn spawn_history_writer(app: AppHandle, state: AppState) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(HISTORY_FLUSH_INTERVAL);
+        let mut last_flush = Instant::now();
+        loop {
+            interval.tick().await;
+            let status_changed = state.take_history_dirty();
+            if !status_changed && last_flush.elapsed() < HISTORY_PROGRESS_FLUSH_INTERVAL {
+                continue;
+            }
+            let progress_changed = state.take_history_progress_dirty();
+            if !status_changed && !progress_changed {
+                continue;
+            }
+            last_flush = Instant::now();
+            if let Err(error) = flush_history(&app, &state).await {
+                eprintln!("history write error: {error}");
+                state.mark_history_dirty();
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ids::unique_temp_dir;
+    use crate::types::{
+        BatchState, BatchStatus, JobRecord, JobStatus, TaskJobRecord, TaskJobStatus, TaskKind,
+        TaskState, TaskStatus,
+    };
+
+    use super::{
+        history_store_path_from_dir, mark_interrupted, read_or_quarantine_store, read_store, snapshot_store,
+        write_store, HistoryStore, MAX_HISTORY_RUNS,
+    };
+
+    fn seed_batch(batch_id: &str, status: BatchStatus, created_at_epoch_seconds: u64) -> BatchState {
+        BatchState {
+            batch_id: batch_id.to_string(),
+            status,
+            jobs: vec![
+                JobRecord {
+                    job_id: "job-a".to_string(),
+                    file_name: "a.mov".to_string(),
+                    input_path: "/tmp/a.mov".to_string(),
+                    output_path: None,
+                    status: JobStatus::Running,
+                    progress_pct: 40,
+                    error: None,
+                },
+                JobRecord {
+                    job_id: "job-b".to_string(),
+                    file_name: "b.mov".to_string(),
+                    input_path: "/tmp/b.mov".to_string(),
+                    output_path: None,
+                    status: JobStatus::Queued,
+                    progress_pct: 0,
+                    error: None,
+                },
+            ],
+            summary: None,
+            created_at_epoch_seconds,
+            parameters: None,
+            parent_task_id: None,
+        }
+    }
+
+    fn seed_task(task_id: &str, status: TaskStatus) -> TaskState {
+        TaskState {
+            task_id: task_id.to_string(),
+            task_kind: TaskKind::Flag,
+            status,
+            jobs: vec![TaskJobRecord {
+                job_id: "job-a".to_string(),
+                file_name: "a.mov".to_string(),
+                input_path: "/tmp/a.mov".to_string(),
+                output_path: Some("/tmp/a.analysis.json".to_string()),
+                status: TaskJobStatus::Completed,
+                progress_pct: 100,
+                error: None,
+                logs: vec!["Flagged 2 subtitle item(s).".to_string()],
+                artifacts: Some(serde_json::json!({ "flaggedCount": 2 })),
+                stage: None,
+            }],
+            summary: None,
+            created_at_epoch_seconds: 10,
+            parameters: None,
+            parent_task_id: None,
+        }
+    }
+
+    #[test]
+    fn should_round_trip_logs_and_artifacts() {
+        let base_dir = unique_temp_dir("history");
+        let path = history_store_path_from_dir(&base_dir);
+        let store = HistoryStore {
+            batches: vec![seed_batch("batch-1", BatchStatus::Completed, 5)],
+            tasks: vec![seed_task("task-1", TaskStatus::Completed)],
+        };
+
+        write_store(&path, &store).expect("history should be written");
+        let restored = read_store(&path).expect("history should be read");
+        assert_eq!(restored, store);
+
+        let _ = std::fs::remove_dir_all(base_dir);
+    }
+
+    #[test]
+    fn should_mark_unfinished_runs_as_interrupted() {
+        let mut store = HistoryStore {
+            batches: vec![seed_batch("batch-1", BatchStatus::Running, 5)],
+            tasks: vec![seed_task("task-1", TaskStatus::Completed)],
+        };
+
+        mark_interrupted(&mut store);
+
+        let batch = &store.batches[0];
+        assert_eq!(batch.status, BatchStatus::Interrupted);
+        assert_eq!(batch.jobs[0].status, JobStatus::Interrupted);
+        assert!(batch.jobs[0].error.is_some());
+        assert_eq!(batch.jobs[1].status, JobStatus::Queued);
+        assert_eq!(store.tasks[0].status, TaskStatus::Completed);
+    }
+
+    #[test]
+    fn should_prune_oldest_finished_runs() {
+        let batches = (0..MAX_HISTORY_RUNS as u64 + 5)
+            .map(|index| seed_batch(&format!("batch-{index:03}"), BatchStatus::Completed, index))
+            .collect::<Vec<_>>();
+
+        let store = snapshot_store(batches, Vec::new());
+
+        assert_eq!(store.batches.len(), MAX_HISTORY_RUNS);
+        assert!(store.batches.iter().all(|batch| batch.created_at_epoch_seconds >= 5));
+    }
+
+    #[test]
+    fn should_read_missing_history_as_empty() {
+        let path = history_store_path_from_dir(&unique_temp_dir("history"));
+        assert_eq!(read_store(&path).expect("missing history is empty"), HistoryStore::default());
+    }
+
+    #[test]
+    fn should_move_a_corrupt_store_aside_instead_of_losing_it() {
+        let base_dir = unique_temp_dir("history");
+        let path = history_store_path_from_dir(&base_dir);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "{ not json").unwrap();
+
+        let store = read_or_quarantine_store(&path).expect("corrupt history is moved aside");
+        let moved = std::fs::read_dir(path.parent().unwrap())
+            .unwrap()
+            .filter_map(Result::ok)
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .collect::<Vec<_>>();
+        let _ = std::fs::remove_dir_all(base_dir);
+
+        assert_eq!(store, HistoryStore::default());
+        assert!(!path.exists());
+        assert_eq!(moved.len(), 1);
+        assert!(moved[0].starts_with("runs.json.corrupt-"));
+    }
+}
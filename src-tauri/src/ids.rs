@@ -22,6 +22,12 @@ pub fn to_file_name(path: &str) -> String {
     path.rsplit('/').next().unwrap_or(path).to_string()
 }
 
+/// A fresh, not yet created directory under the system temp dir.
+#[cfg(test)]
+pub fn unique_temp_dir(label: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("al-iyaal-{label}-{}", uuid::Uuid::new_v4()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::{to_file_name, to_job_id};
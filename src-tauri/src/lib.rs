@@ -1,14 +1,26 @@
+mod analysis;
 mod analytics;
+mod blacklist;
 mod commands;
+mod cut_planning;
 mod file_discovery;
+mod history;
 mod ids;
+mod profiles;
 mod protocol;
+mod rule_packs;
 mod runtime;
+mod srt;
 mod state;
+mod subtitles;
+mod text_normalization;
 mod types;
 mod worker;
 
-use tauri::menu::{AboutMetadataBuilder, Menu, MenuItem, SubmenuBuilder};
+use tauri::{
+    menu::{AboutMetadataBuilder, Menu, MenuItem, SubmenuBuilder},
+    Manager,
+};
 use tauri_plugin_opener::OpenerExt;
 
 use state::AppState;
@@ -73,21 +85,57 @@ pub fn run() {
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_opener::init())
         .manage(AppState::new())
+        .setup(|app| {
+            let app_handle = app.handle().clone();
+            let state = app.state::<AppState>().inner().clone();
+            match tauri::async_runtime::block_on(history::restore_history(&app_handle, &state)) {
+                Ok(()) => history::spawn_history_writer(app_handle, state),
+                Err(error) => eprintln!("history restore error: {error}; run history will not be saved this session"),
+            }
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             commands::start_batch,
             commands::start_transcription_batch,
             commands::start_flag_batch,
             commands::start_cut_job,
+            commands::start_cut_batch,
+            commands::start_pipeline,
+            commands::plan_cuts_from_analysis,
             commands::cancel_batch,
             commands::cancel_task,
+            commands::pause_batch,
+            commands::resume_batch,
+            commands::pause_task,
+            commands::resume_task,
+            commands::restart_interrupted_batch,
+            commands::restart_interrupted_task,
+            commands::retry_jobs,
             commands::get_batch_state,
             commands::get_task_state,
+            commands::list_tasks,
             commands::list_videos,
             commands::list_srt_files,
             commands::get_analytics_snapshot,
             commands::get_moderation_settings,
             commands::save_moderation_settings,
+            commands::test_moderation_rules,
+            commands::list_moderation_profiles,
+            commands::get_moderation_profile,
+            commands::save_moderation_profile,
+            commands::create_moderation_profile,
+            commands::clone_moderation_profile,
+            commands::delete_moderation_profile,
+            commands::export_moderation_rules,
+            commands::import_moderation_rules,
             commands::read_text_file,
+            commands::parse_srt_file,
+            commands::load_analysis,
+            commands::review_flagged_item,
+            commands::write_analysis_file,
+            commands::merge_analyses,
+            commands::diff_analyses,
+            commands::convert_subtitle_file,
             commands::open_folder_picker,
         ])
         .run(tauri::generate_context!())
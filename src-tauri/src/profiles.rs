@@ -0,0 +1,213 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::ModerationSettings;
+
+/// The profile stored in `settings/moderation.json`, which also holds the API
+/// keys every profile runs with.
+pub const DEFAULT_PROFILE_ID: &str = "default";
+pub const DEFAULT_PROFILE_NAME: &str = "Default";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ModerationProfile {
+    /// Derived from the name when the profile is created; also its file stem.
+    pub id: String,
+    pub name: String,
+    pub settings: ModerationSettings,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ModerationProfileSummary {
+    pub id: String,
+    pub name: String,
+    pub engine: String,
+}
+
+/// The profile a flag run's settings came from, recorded in its analysis
+/// sidecars. The name is kept too since profiles can be deleted.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileRef {
+    pub id: String,
+    pub name: String,
+}
+
+impl ModerationProfile {
+    pub fn reference(&self) -> ProfileRef {
+        ProfileRef {
+            id: self.id.clone(),
+            name: self.name.clone(),
+        }
+    }
+
+    pub fn summary(&self) -> ModerationProfileSummary {
+        ModerationProfileSummary {
+            id: self.id.clone(),
+            name: self.name.clone(),
+            engine: self.settings.engine.clone(),
+        }
+    }
+}
+
+/// Lowercase letters and digits of `name`, with every other run of characters
+/// turned into a single `-`.
+pub fn profile_id(name: &str) -> Result<String, String> {
+    let id = name
+        .to_lowercase()
+        .split(|character: char| !character.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    if id.is_empty() {
+        return Err("Profile name must contain a letter or digit.".to_string());
+    }
+    Ok(id)
+}
+
+fn profile_path(profiles_dir: &Path, id: &str) -> Result<PathBuf, String> {
+    if profile_id(id).ok().as_deref() != Some(id) {
+        return Err(format!("Invalid moderation profile id: {id}"));
+    }
+    Ok(profiles_dir.join(format!("{id}.json")))
+}
+
+pub fn read_profile(profiles_dir: &Path, id: &str) -> Result<ModerationProfile, String> {
+    let path = profile_path(profiles_dir, id)?;
+    if !path.is_file() {
+        return Err(format!("Moderation profile not found: {id}"));
+    }
+    let content = fs::read_to_string(&path)
+        .map_err(|error| format!("Failed reading moderation profile {}: {error}", path.display()))?;
+    let profile: ModerationProfile = serde_json::from_str(&content)
+        .map_err(|error| format!("Invalid moderation profile {}: {error}", path.display()))?;
+    Ok(ModerationProfile {
+        id: id.to_string(),
+        ..profile
+    })
+}
+
+/// Callers strip API keys first; profiles run with the default profile's keys.
+pub fn write_profile(profiles_dir: &Path, profile: &ModerationProfile) -> Result<(), String> {
+    let path = profile_path(profiles_dir, &profile.id)?;
+    fs::create_dir_all(profiles_dir).map_err(|error| {
+        format!(
+            "Failed creating moderation profiles directory {}: {error}",
+            profiles_dir.display()
+        )
+    })?;
+    let content = serde_json::to_string_pretty(profile)
+        .map_err(|error| format!("Failed serializing moderation profile: {error}"))?;
+    fs::write(&path, content)
+        .map_err(|error| format!("Failed writing moderation profile {}: {error}", path.display()))
+}
+
+/// Creates a profile named `name`, failing when its id is taken.
+pub fn create_profile(
+    profiles_dir: &Path,
+    name: &str,
+    settings: ModerationSettings,
+) -> Result<ModerationProfile, String> {
+    let name = name.trim();
+    let id = profile_id(name)?;
+    if id == DEFAULT_PROFILE_ID || profile_path(profiles_dir, &id)?.exists() {
+        return Err(format!("A moderation profile named {name:?} already exists."));
+    }
+
+    let profile = ModerationProfile {
+        id,
+        name: name.to_string(),
+        settings,
+    };
+    write_profile(profiles_dir, &profile)?;
+    Ok(profile)
+}
+
+pub fn delete_profile(profiles_dir: &Path, id: &str) -> Result<(), String> {
+    if id == DEFAULT_PROFILE_ID {
+        return Err("The default moderation profile cannot be deleted.".to_string());
+    }
+    let path = profile_path(profiles_dir, id)?;
+    if !path.is_file() {
+        return Err(format!("Moderation profile not found: {id}"));
+    }
+    fs::remove_file(&path)
+        .map_err(|error| format!("Failed deleting moderation profile {}: {error}", path.display()))
+}
+
+/// Profiles other than the default, sorted by name. Unreadable files are skipped.
+pub fn list_profiles(profiles_dir: &Path) -> Vec<ModerationProfileSummary> {
+    let Ok(entries) = fs::read_dir(profiles_dir) else {
+        return Vec::new();
+    };
+    let mut profiles = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+        .filter_map(|path| path.file_stem()?.to_str().map(str::to_string))
+        .filter_map(|id| read_profile(profiles_dir, &id).ok())
+        .map(|profile| profile.summary())
+        .collect::<Vec<_>>();
+    profiles.sort_by(|left, right| left.name.cmp(&right.name).then(left.id.cmp(&right.id)));
+    profiles
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::{ids::unique_temp_dir, types::ModerationSettings};
+
+    use super::{create_profile, delete_profile, list_profiles, profile_id, read_profile};
+
+    fn settings(engine: &str) -> ModerationSettings {
+        ModerationSettings {
+            engine: engine.to_string(),
+            ..ModerationSettings::for_tests(Vec::new(), &[])
+        }
+    }
+
+    #[test]
+    fn should_derive_profile_ids_from_names() {
+        assert_eq!(profile_id(" Under 6 (strict) ").unwrap(), "under-6-strict");
+        assert_eq!(profile_id("Teens").unwrap(), "teens");
+        assert!(profile_id("--").is_err());
+    }
+
+    #[test]
+    fn should_create_list_and_delete_profiles() {
+        let profiles_dir = unique_temp_dir("profiles");
+
+        create_profile(&profiles_dir, "Teens", settings("gemini")).unwrap();
+        create_profile(&profiles_dir, "Under 6", settings("blacklist")).unwrap();
+        let duplicate = create_profile(&profiles_dir, "under 6", settings("blacklist"));
+        let reserved = create_profile(&profiles_dir, "Default", settings("blacklist"));
+        let listed = list_profiles(&profiles_dir)
+            .into_iter()
+            .map(|profile| (profile.id, profile.engine))
+            .collect::<Vec<_>>();
+        let under_six = read_profile(&profiles_dir, "under-6").unwrap();
+        delete_profile(&profiles_dir, "teens").unwrap();
+        let remaining = list_profiles(&profiles_dir).len();
+        let escaped = read_profile(&profiles_dir, "../moderation");
+        fs::remove_dir_all(&profiles_dir).unwrap();
+
+        assert!(duplicate.is_err());
+        assert!(reserved.is_err());
+        assert_eq!(
+            listed,
+            vec![
+                ("teens".to_string(), "gemini".to_string()),
+                ("under-6".to_string(), "blacklist".to_string())
+            ]
+        );
+        assert_eq!(under_six.name, "Under 6");
+        assert_eq!(remaining, 1);
+        assert_eq!(escaped.unwrap_err(), "Invalid moderation profile id: ../moderation");
+    }
+}
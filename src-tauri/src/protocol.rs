@@ -1,9 +1,20 @@
 use serde::{Deserialize, Serialize};
 
+use crate::profiles::ProfileRef;
 use crate::types::{
-    BatchEvent, BatchSummary, CutRange, ModerationSettings, TaskEvent, TaskKind, TaskSummary,
+    BatchEvent, BatchSummary, CutRange, ModerationSettings, PipelineStage, PipelineStageKind, PlannedCutJob,
+    TaskEvent, TaskKind, TaskSummary,
 };
 
+/// Bumped whenever the stdio message shapes change in a way an older worker
+/// (or host) cannot understand. Must match `PROTOCOL_VERSION` in the worker.
+pub const WORKER_PROTOCOL_VERSION: u32 = 1;
+
+/// `mute` and `bleep` silence the given ranges instead of keeping them.
+pub fn is_audio_redaction_mode(output_mode: &str) -> bool {
+    matches!(output_mode, "mute" | "bleep")
+}
+
 #[derive(Debug, Clone)]
 pub enum WorkerCommand {
     StartBatch {
@@ -21,6 +32,8 @@ pub enum WorkerCommand {
         task_id: String,
         input_paths: Vec<String>,
         settings: ModerationSettings,
+        /// Moderation profile recorded in the analysis sidecars.
+        profile: Option<ProfileRef>,
     },
     StartCutJob {
         task_id: String,
@@ -28,6 +41,19 @@ pub enum WorkerCommand {
         ranges: Vec<CutRange>,
         output_mode: String,
     },
+    StartCutBatch {
+        task_id: String,
+        jobs: Vec<PlannedCutJob>,
+        output_mode: String,
+    },
+    StartPipeline {
+        task_id: String,
+        input_paths: Vec<String>,
+        stages: Vec<PipelineStage>,
+        settings: ModerationSettings,
+        /// Moderation profile the flag stage records in the analysis sidecars.
+        profile: Option<ProfileRef>,
+    },
     CancelBatch {
         batch_id: String,
         mode: String,
@@ -36,6 +62,26 @@ pub enum WorkerCommand {
         task_id: String,
         mode: String,
     },
+    PauseBatch {
+        batch_id: String,
+    },
+    ResumeBatch {
+        batch_id: String,
+    },
+    PauseTask {
+        task_id: String,
+    },
+    ResumeTask {
+        task_id: String,
+    },
+    /// Answers a pipeline's `cut_plan_needed` with the host-planned cut.
+    ProvideCutPlan {
+        task_id: String,
+        job: PlannedCutJob,
+    },
+    Ping {
+        ping_id: String,
+    },
 }
 
 #[derive(Debug, Serialize)]
@@ -65,6 +111,8 @@ enum WorkerCommandMessage<'a> {
         #[serde(rename = "inputPaths")]
         input_paths: &'a [String],
         settings: &'a ModerationSettings,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        profile: Option<&'a ProfileRef>,
     },
     StartCutJob {
         #[serde(rename = "taskId")]
@@ -75,6 +123,23 @@ enum WorkerCommandMessage<'a> {
         #[serde(rename = "outputMode")]
         output_mode: &'a str,
     },
+    StartCutBatch {
+        #[serde(rename = "taskId")]
+        task_id: &'a str,
+        jobs: &'a [PlannedCutJob],
+        #[serde(rename = "outputMode")]
+        output_mode: &'a str,
+    },
+    StartPipeline {
+        #[serde(rename = "taskId")]
+        task_id: &'a str,
+        #[serde(rename = "inputPaths")]
+        input_paths: &'a [String],
+        stages: &'a [PipelineStage],
+        settings: &'a ModerationSettings,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        profile: Option<&'a ProfileRef>,
+    },
     CancelBatch {
         #[serde(rename = "batchId")]
         batch_id: &'a str,
@@ -85,9 +150,80 @@ enum WorkerCommandMessage<'a> {
         task_id: &'a str,
         mode: &'a str,
     },
+    PauseBatch {
+        #[serde(rename = "batchId")]
+        batch_id: &'a str,
+    },
+    ResumeBatch {
+        #[serde(rename = "batchId")]
+        batch_id: &'a str,
+    },
+    PauseTask {
+        #[serde(rename = "taskId")]
+        task_id: &'a str,
+    },
+    ResumeTask {
+        #[serde(rename = "taskId")]
+        task_id: &'a str,
+    },
+    ProvideCutPlan {
+        #[serde(rename = "taskId")]
+        task_id: &'a str,
+        job: &'a PlannedCutJob,
+    },
+    Ping {
+        #[serde(rename = "pingId")]
+        ping_id: &'a str,
+    },
 }
 
 impl WorkerCommand {
+    /// The wire `type` of the command, as advertised in the worker's `hello`.
+    pub fn command_type(&self) -> &'static str {
+        match self {
+            WorkerCommand::StartBatch { .. } => "start_batch",
+            WorkerCommand::StartTranscriptionBatch { .. } => "start_transcription_batch",
+            WorkerCommand::StartFlagBatch { .. } => "start_flag_batch",
+            WorkerCommand::StartCutJob { .. } => "start_cut_job",
+            WorkerCommand::StartCutBatch { .. } => "start_cut_batch",
+            WorkerCommand::StartPipeline { .. } => "start_pipeline",
+            WorkerCommand::CancelBatch { .. } => "cancel_batch",
+            WorkerCommand::CancelTask { .. } => "cancel_task",
+            WorkerCommand::PauseBatch { .. } => "pause_batch",
+            WorkerCommand::ResumeBatch { .. } => "resume_batch",
+            WorkerCommand::PauseTask { .. } => "pause_task",
+            WorkerCommand::ResumeTask { .. } => "resume_task",
+            WorkerCommand::ProvideCutPlan { .. } => "provide_cut_plan",
+            WorkerCommand::Ping { .. } => "ping",
+        }
+    }
+
+    /// `hello` capabilities beyond the command itself that the worker must
+    /// advertise to honour this command, since older workers ignore the fields.
+    pub fn required_features(&self) -> Vec<&'static str> {
+        let (records_profile, redacts_audio) = match self {
+            WorkerCommand::StartFlagBatch { profile, .. } => (profile.is_some(), false),
+            WorkerCommand::StartCutJob { output_mode, .. } | WorkerCommand::StartCutBatch { output_mode, .. } => {
+                (false, is_audio_redaction_mode(output_mode))
+            }
+            WorkerCommand::StartPipeline { stages, profile, .. } => (
+                profile.is_some(),
+                stages.iter().any(|stage| {
+                    matches!(stage, PipelineStage::Cut { output_mode, .. } if is_audio_redaction_mode(output_mode))
+                }),
+            ),
+            _ => (false, false),
+        };
+        let mut features = Vec::new();
+        if records_profile {
+            features.push("flag_profiles");
+        }
+        if redacts_audio {
+            features.push("audio_redaction");
+        }
+        features
+    }
+
     pub fn to_json_line(&self) -> Result<String, String> {
         let payload = match self {
             WorkerCommand::StartBatch {
@@ -114,10 +250,12 @@ impl WorkerCommand {
                 task_id,
                 input_paths,
                 settings,
+                profile,
             } => WorkerCommandMessage::StartFlagBatch {
                 task_id,
                 input_paths,
                 settings,
+                profile: profile.as_ref(),
             },
             WorkerCommand::StartCutJob {
                 task_id,
@@ -130,6 +268,28 @@ impl WorkerCommand {
                 ranges,
                 output_mode,
             },
+            WorkerCommand::StartCutBatch {
+                task_id,
+                jobs,
+                output_mode,
+            } => WorkerCommandMessage::StartCutBatch {
+                task_id,
+                jobs,
+                output_mode,
+            },
+            WorkerCommand::StartPipeline {
+                task_id,
+                input_paths,
+                stages,
+                settings,
+                profile,
+            } => WorkerCommandMessage::StartPipeline {
+                task_id,
+                input_paths,
+                stages,
+                settings,
+                profile: profile.as_ref(),
+            },
             WorkerCommand::CancelBatch { batch_id, mode } => WorkerCommandMessage::CancelBatch {
                 batch_id,
                 mode,
@@ -137,6 +297,12 @@ impl WorkerCommand {
             WorkerCommand::CancelTask { task_id, mode } => {
                 WorkerCommandMessage::CancelTask { task_id, mode }
             }
+            WorkerCommand::PauseBatch { batch_id } => WorkerCommandMessage::PauseBatch { batch_id },
+            WorkerCommand::ResumeBatch { batch_id } => WorkerCommandMessage::ResumeBatch { batch_id },
+            WorkerCommand::PauseTask { task_id } => WorkerCommandMessage::PauseTask { task_id },
+            WorkerCommand::ResumeTask { task_id } => WorkerCommandMessage::ResumeTask { task_id },
+            WorkerCommand::ProvideCutPlan { task_id, job } => WorkerCommandMessage::ProvideCutPlan { task_id, job },
+            WorkerCommand::Ping { ping_id } => WorkerCommandMessage::Ping { ping_id },
         };
 
         serde_json::to_string(&payload)
@@ -184,6 +350,39 @@ pub enum WorkerEvent {
         job_id: String,
         error: String,
     },
+    JobCancelled {
+        #[serde(rename = "batchId")]
+        batch_id: Option<String>,
+        #[serde(rename = "taskId")]
+        task_id: Option<String>,
+        #[serde(rename = "taskKind")]
+        task_kind: Option<String>,
+        #[serde(rename = "jobId")]
+        job_id: String,
+    },
+    /// A job that was running when the worker died. Only the host synthesizes
+    /// it, from `AppState::orphaned_work_events`.
+    JobInterrupted {
+        #[serde(rename = "batchId")]
+        batch_id: Option<String>,
+        #[serde(rename = "taskId")]
+        task_id: Option<String>,
+        #[serde(rename = "taskKind")]
+        task_kind: Option<String>,
+        #[serde(rename = "jobId")]
+        job_id: String,
+        error: String,
+    },
+    /// A pipeline job entered its next stage.
+    JobStage {
+        #[serde(rename = "taskId")]
+        task_id: String,
+        #[serde(rename = "taskKind")]
+        task_kind: String,
+        #[serde(rename = "jobId")]
+        job_id: String,
+        stage: PipelineStageKind,
+    },
     BatchDone {
         #[serde(rename = "batchId")]
         batch_id: String,
@@ -196,6 +395,26 @@ pub enum WorkerEvent {
         task_kind: String,
         summary: TaskSummary,
     },
+    BatchPaused {
+        #[serde(rename = "batchId")]
+        batch_id: String,
+    },
+    BatchResumed {
+        #[serde(rename = "batchId")]
+        batch_id: String,
+    },
+    TaskPaused {
+        #[serde(rename = "taskId")]
+        task_id: String,
+        #[serde(rename = "taskKind")]
+        task_kind: String,
+    },
+    TaskResumed {
+        #[serde(rename = "taskId")]
+        task_id: String,
+        #[serde(rename = "taskKind")]
+        task_kind: String,
+    },
     JobLog {
         #[serde(rename = "batchId")]
         batch_id: Option<String>,
@@ -212,17 +431,80 @@ pub enum WorkerEvent {
         status: String,
         message: String,
     },
+    /// A flag run replaced a sidecar, keeping the previous one at
+    /// `previous_analysis_path` for the host to carry its reviews over.
+    AnalysisReplaced {
+        #[serde(rename = "taskId")]
+        task_id: String,
+        #[serde(rename = "taskKind")]
+        task_kind: String,
+        #[serde(rename = "jobId")]
+        job_id: String,
+        #[serde(rename = "analysisPath")]
+        analysis_path: String,
+        #[serde(rename = "engineAnalysisPath")]
+        engine_analysis_path: String,
+        #[serde(rename = "previousAnalysisPath")]
+        previous_analysis_path: String,
+    },
+    /// A pipeline's cut stage waits for the host to plan its cut.
+    CutPlanNeeded {
+        #[serde(rename = "taskId")]
+        task_id: String,
+        #[serde(rename = "videoPath")]
+        video_path: String,
+        #[serde(rename = "analysisPath")]
+        analysis_path: String,
+    },
+    Pong {
+        #[serde(rename = "pingId")]
+        ping_id: String,
+    },
+    Hello {
+        #[serde(rename = "protocolVersion")]
+        protocol_version: u32,
+        #[serde(rename = "workerVersion")]
+        worker_version: String,
+        capabilities: Vec<String>,
+    },
+}
+
+impl WorkerEvent {
+    /// Whether the event reports on a specific job, i.e. proves the running
+    /// job is still making progress rather than just that the worker is alive.
+    pub fn is_job_activity(&self) -> bool {
+        matches!(
+            self,
+            WorkerEvent::JobProgress { .. }
+                | WorkerEvent::JobDone { .. }
+                | WorkerEvent::JobError { .. }
+                | WorkerEvent::JobCancelled { .. }
+                | WorkerEvent::JobStage { .. }
+                | WorkerEvent::JobLog { .. }
+        )
+    }
 }
 
 pub fn parse_worker_event(line: &str) -> Result<WorkerEvent, String> {
     serde_json::from_str(line).map_err(|error| format!("Failed to parse worker event: {error}"))
 }
 
+pub fn ensure_compatible_worker(protocol_version: u32, worker_version: &str) -> Result<(), String> {
+    if protocol_version != WORKER_PROTOCOL_VERSION {
+        return Err(format!(
+            "Worker {worker_version} speaks protocol version {protocol_version}, but this app requires version {WORKER_PROTOCOL_VERSION}. Reinstall the app to update the bundled worker."
+        ));
+    }
+
+    Ok(())
+}
+
 fn parse_task_kind(task_kind: &str) -> Option<TaskKind> {
     match task_kind {
         "transcription" => Some(TaskKind::Transcription),
         "flag" => Some(TaskKind::Flag),
         "cut" => Some(TaskKind::Cut),
+        "pipeline" => Some(TaskKind::Pipeline),
         _ => None,
     }
 }
@@ -261,9 +543,22 @@ pub fn to_frontend_batch_event(event: &WorkerEvent) -> Option<BatchEvent> {
             error,
             ..
         } => batch_id.as_ref().map(|batch_id| BatchEvent::job_error(batch_id, job_id, error)),
+        WorkerEvent::JobCancelled { batch_id, job_id, .. } => batch_id
+            .as_ref()
+            .map(|batch_id| BatchEvent::job_cancelled(batch_id, job_id)),
+        WorkerEvent::JobInterrupted {
+            batch_id,
+            job_id,
+            error,
+            ..
+        } => batch_id
+            .as_ref()
+            .map(|batch_id| BatchEvent::job_interrupted(batch_id, job_id, error)),
         WorkerEvent::BatchDone { batch_id, summary } => {
             Some(BatchEvent::batch_done(batch_id, summary.clone()))
         }
+        WorkerEvent::BatchPaused { batch_id } => Some(BatchEvent::batch_paused(batch_id)),
+        WorkerEvent::BatchResumed { batch_id } => Some(BatchEvent::batch_resumed(batch_id)),
         WorkerEvent::JobLog {
             batch_id,
             job_id,
@@ -275,7 +570,14 @@ pub fn to_frontend_batch_event(event: &WorkerEvent) -> Option<BatchEvent> {
             .map(|batch_id| BatchEvent::job_log(batch_id, job_id, message, stream.clone().unwrap_or_else(|| "stdout".to_string()))),
         WorkerEvent::WorkerStatus { status, message } => to_worker_status(status)
             .map(|status_kind| BatchEvent::worker_status(status_kind, message)),
-        WorkerEvent::TaskDone { .. } => None,
+        WorkerEvent::JobStage { .. }
+        | WorkerEvent::TaskDone { .. }
+        | WorkerEvent::TaskPaused { .. }
+        | WorkerEvent::TaskResumed { .. }
+        | WorkerEvent::AnalysisReplaced { .. }
+        | WorkerEvent::CutPlanNeeded { .. }
+        | WorkerEvent::Pong { .. }
+        | WorkerEvent::Hello { .. } => None,
     }
 }
 
@@ -326,11 +628,44 @@ pub fn to_frontend_task_event(event: &WorkerEvent) -> Option<TaskEvent> {
             let task_kind = parse_task_kind(task_kind.as_deref().unwrap_or_default())?;
             Some(TaskEvent::job_error(task_id, task_kind, job_id, error))
         }
+        WorkerEvent::JobCancelled {
+            task_id,
+            task_kind,
+            job_id,
+            ..
+        } => {
+            let task_id = task_id.as_ref()?;
+            let task_kind = parse_task_kind(task_kind.as_deref().unwrap_or_default())?;
+            Some(TaskEvent::job_cancelled(task_id, task_kind, job_id))
+        }
+        WorkerEvent::JobInterrupted {
+            task_id,
+            task_kind,
+            job_id,
+            error,
+            ..
+        } => {
+            let task_id = task_id.as_ref()?;
+            let task_kind = parse_task_kind(task_kind.as_deref().unwrap_or_default())?;
+            Some(TaskEvent::job_interrupted(task_id, task_kind, job_id, error))
+        }
+        WorkerEvent::JobStage {
+            task_id,
+            task_kind,
+            job_id,
+            stage,
+        } => parse_task_kind(task_kind).map(|task_kind| TaskEvent::job_stage(task_id, task_kind, job_id, *stage)),
         WorkerEvent::TaskDone {
             task_id,
             task_kind,
             summary,
         } => parse_task_kind(task_kind).map(|task_kind| TaskEvent::task_done(task_id, task_kind, summary.clone())),
+        WorkerEvent::TaskPaused { task_id, task_kind } => {
+            parse_task_kind(task_kind).map(|task_kind| TaskEvent::task_paused(task_id, task_kind))
+        }
+        WorkerEvent::TaskResumed { task_id, task_kind } => {
+            parse_task_kind(task_kind).map(|task_kind| TaskEvent::task_resumed(task_id, task_kind))
+        }
         WorkerEvent::JobLog {
             task_id,
             task_kind,
@@ -351,15 +686,27 @@ pub fn to_frontend_task_event(event: &WorkerEvent) -> Option<TaskEvent> {
         }
         WorkerEvent::WorkerStatus { status, message } => to_worker_status(status)
             .map(|status_kind| TaskEvent::worker_status(status_kind, message)),
-        WorkerEvent::BatchDone { .. } => None,
+        WorkerEvent::BatchDone { .. }
+        | WorkerEvent::BatchPaused { .. }
+        | WorkerEvent::BatchResumed { .. }
+        | WorkerEvent::AnalysisReplaced { .. }
+        | WorkerEvent::CutPlanNeeded { .. }
+        | WorkerEvent::Pong { .. }
+        | WorkerEvent::Hello { .. } => None,
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::types::TaskEvent;
+    use crate::{
+        profiles::ProfileRef,
+        types::{CutRange, PlannedCutJob, TaskEvent},
+    };
 
-    use super::{parse_worker_event, to_frontend_task_event, WorkerCommand};
+    use super::{
+        ensure_compatible_worker, parse_worker_event, to_frontend_task_event, WorkerCommand,
+        WORKER_PROTOCOL_VERSION,
+    };
 
     #[test]
     fn should_serialize_cancel_batch_command() {
@@ -374,6 +721,171 @@ mod tests {
         assert!(line.contains("\"batchId\":\"batch-1\""));
     }
 
+    #[test]
+    fn should_serialize_ping_command() {
+        let line = WorkerCommand::Ping {
+            ping_id: "ping-7".to_string(),
+        }
+        .to_json_line()
+        .expect("command serialization should succeed");
+
+        assert_eq!(line, "{\"type\":\"ping\",\"pingId\":\"ping-7\"}\n");
+    }
+
+    #[test]
+    fn should_serialize_pause_and_resume_commands() {
+        let pause = WorkerCommand::PauseBatch {
+            batch_id: "batch-1".to_string(),
+        }
+        .to_json_line()
+        .expect("command serialization should succeed");
+        let resume = WorkerCommand::ResumeTask {
+            task_id: "task-1".to_string(),
+        }
+        .to_json_line()
+        .expect("command serialization should succeed");
+
+        assert_eq!(pause, "{\"type\":\"pause_batch\",\"batchId\":\"batch-1\"}\n");
+        assert_eq!(resume, "{\"type\":\"resume_task\",\"taskId\":\"task-1\"}\n");
+    }
+
+    #[test]
+    fn should_map_task_paused_event_to_frontend() {
+        let event = parse_worker_event(r#"{"type":"task_paused","taskId":"task-1","taskKind":"flag"}"#)
+            .expect("worker event should parse");
+
+        let mapped = to_frontend_task_event(&event).expect("task event should map");
+        let json = serde_json::to_string(&mapped).expect("task event should serialize");
+        assert!(json.contains("\"type\":\"task_paused\""));
+        assert!(super::to_frontend_batch_event(&event).is_none());
+    }
+
+    #[test]
+    fn should_parse_pong_event_without_frontend_mapping() {
+        let event = parse_worker_event(r#"{"type":"pong","pingId":"ping-7"}"#)
+            .expect("worker event should parse");
+
+        assert!(matches!(&event, super::WorkerEvent::Pong { ping_id } if ping_id == "ping-7"));
+        assert!(!event.is_job_activity());
+        assert!(super::to_frontend_batch_event(&event).is_none());
+        assert!(to_frontend_task_event(&event).is_none());
+    }
+
+    #[test]
+    fn should_parse_replaced_analysis_without_frontend_mapping() {
+        let event = parse_worker_event(
+            r#"{"type":"analysis_replaced","taskId":"task-1","taskKind":"flag","jobId":"job-1","analysisPath":"/tmp/a.analysis.json","engineAnalysisPath":"/tmp/a.analysis.blacklist.json","previousAnalysisPath":"/tmp/a.analysis.json.bak"}"#,
+        )
+        .expect("worker event should parse");
+
+        assert!(matches!(
+            &event,
+            super::WorkerEvent::AnalysisReplaced { previous_analysis_path, .. }
+                if previous_analysis_path == "/tmp/a.analysis.json.bak"
+        ));
+        assert!(super::to_frontend_batch_event(&event).is_none());
+        assert!(to_frontend_task_event(&event).is_none());
+    }
+
+    #[test]
+    fn should_parse_cut_plan_request_without_frontend_mapping() {
+        let event = parse_worker_event(
+            r#"{"type":"cut_plan_needed","taskId":"task-1","videoPath":"/tmp/a.mp4","analysisPath":"/tmp/a.analysis.json"}"#,
+        )
+        .expect("worker event should parse");
+
+        assert!(matches!(
+            &event,
+            super::WorkerEvent::CutPlanNeeded { task_id, video_path, analysis_path }
+                if task_id == "task-1" && video_path == "/tmp/a.mp4" && analysis_path == "/tmp/a.analysis.json"
+        ));
+        assert!(super::to_frontend_batch_event(&event).is_none());
+        assert!(to_frontend_task_event(&event).is_none());
+    }
+
+    #[test]
+    fn should_report_the_wire_type_of_each_command() {
+        let commands = [
+            WorkerCommand::CancelTask {
+                task_id: "task-1".to_string(),
+                mode: "stop_after_current".to_string(),
+            },
+            WorkerCommand::Ping {
+                ping_id: "ping-1".to_string(),
+            },
+        ];
+
+        for command in commands {
+            let line = command.to_json_line().unwrap();
+            assert!(line.contains(&format!("\"type\":\"{}\"", command.command_type())));
+        }
+    }
+
+    #[test]
+    fn should_require_features_for_options_older_workers_ignore() {
+        let stages = |output_mode: &str| -> Vec<crate::types::PipelineStage> {
+            serde_json::from_value(serde_json::json!([{ "stage": "cut", "outputMode": output_mode }]))
+                .expect("stages should deserialize")
+        };
+        let default_profile = || {
+            Some(ProfileRef {
+                id: "default".to_string(),
+                name: "Default".to_string(),
+            })
+        };
+        let pipeline = |output_mode: &str, profile: Option<ProfileRef>| WorkerCommand::StartPipeline {
+            task_id: "task-1".to_string(),
+            input_paths: Vec::new(),
+            stages: stages(output_mode),
+            settings: crate::types::ModerationSettings::for_tests(Vec::new(), &[]),
+            profile,
+        };
+        let flag = |profile: Option<ProfileRef>| WorkerCommand::StartFlagBatch {
+            task_id: "task-1".to_string(),
+            input_paths: Vec::new(),
+            settings: crate::types::ModerationSettings::for_tests(Vec::new(), &[]),
+            profile,
+        };
+
+        assert_eq!(pipeline("mute", None).required_features(), vec!["audio_redaction"]);
+        assert!(pipeline("video_cleaned_default", None).required_features().is_empty());
+        assert_eq!(
+            pipeline("mute", default_profile()).required_features(),
+            vec!["flag_profiles", "audio_redaction"]
+        );
+        assert_eq!(flag(default_profile()).required_features(), vec!["flag_profiles"]);
+        assert!(flag(None).required_features().is_empty());
+    }
+
+    #[test]
+    fn should_parse_worker_hello() {
+        let event = parse_worker_event(
+            r#"{"type":"hello","protocolVersion":1,"workerVersion":"0.1.0","capabilities":["start_batch","ping"]}"#,
+        )
+        .expect("worker event should parse");
+
+        match event {
+            super::WorkerEvent::Hello {
+                protocol_version,
+                worker_version,
+                capabilities,
+            } => {
+                assert_eq!(protocol_version, 1);
+                assert_eq!(worker_version, "0.1.0");
+                assert_eq!(capabilities, vec!["start_batch", "ping"]);
+            }
+            _ => panic!("expected hello event"),
+        }
+    }
+
+    #[test]
+    fn should_reject_workers_speaking_another_protocol_version() {
+        assert!(ensure_compatible_worker(WORKER_PROTOCOL_VERSION, "0.1.0").is_ok());
+
+        let error = ensure_compatible_worker(WORKER_PROTOCOL_VERSION + 1, "9.0.0").unwrap_err();
+        assert!(error.contains("9.0.0"));
+    }
+
     #[test]
     fn should_parse_job_progress_event() {
         let line = r#"{"type":"job_progress","batchId":"batch-1","jobId":"job-1","progressPct":42.4}"#;
@@ -461,4 +973,108 @@ mod tests {
             _ => panic!("expected job log event"),
         }
     }
+
+    #[test]
+    fn should_serialize_pipeline_stages_with_their_options() {
+        let stages: Vec<crate::types::PipelineStage> = serde_json::from_value(serde_json::json!([
+            { "stage": "remove_music" },
+            { "stage": "cut", "minPriority": "medium" },
+        ]))
+        .expect("stages should deserialize");
+        let settings = crate::types::ModerationSettings::for_tests(Vec::new(), &[]);
+
+        let line = WorkerCommand::StartPipeline {
+            task_id: "task-1".to_string(),
+            input_paths: vec!["/tmp/a.mp4".to_string()],
+            stages,
+            settings,
+            profile: Some(ProfileRef {
+                id: "under-6".to_string(),
+                name: "Under 6".to_string(),
+            }),
+        }
+        .to_json_line()
+        .expect("command serialization should succeed");
+        let payload: serde_json::Value = serde_json::from_str(&line).expect("line should be json");
+
+        assert_eq!(payload["type"], "start_pipeline");
+        assert_eq!(payload["profile"], serde_json::json!({ "id": "under-6", "name": "Under 6" }));
+        assert_eq!(payload["stages"][0], serde_json::json!({ "stage": "remove_music", "computeMode": "auto" }));
+        assert_eq!(payload["stages"][1]["stage"], "cut");
+        assert_eq!(payload["stages"][1]["minPriority"], "medium");
+        assert_eq!(payload["stages"][1]["outputMode"], "video_cleaned_default");
+    }
+
+    #[test]
+    fn should_map_job_stage_to_task_event() {
+        let event = parse_worker_event(
+            r#"{"type":"job_stage","taskId":"task-1","taskKind":"pipeline","jobId":"job-1","stage":"transcribe"}"#,
+        )
+        .expect("worker event should parse");
+
+        assert!(event.is_job_activity());
+        match to_frontend_task_event(&event).expect("task event should map") {
+            TaskEvent::JobStage { task_kind, stage, .. } => {
+                assert_eq!(task_kind, crate::types::TaskKind::Pipeline);
+                assert_eq!(stage, crate::types::PipelineStageKind::Transcribe);
+            }
+            _ => panic!("expected job stage event"),
+        }
+    }
+
+    #[test]
+    fn should_serialize_planned_cut_batch_jobs() {
+        let command = WorkerCommand::StartCutBatch {
+            task_id: "task-1".to_string(),
+            jobs: vec![
+                PlannedCutJob {
+                    video_path: "/tmp/a.mp4".to_string(),
+                    ranges: vec![CutRange {
+                        start: "0:00.000".to_string(),
+                        end: "0:01.500".to_string(),
+                    }],
+                    removed_ranges: vec![CutRange {
+                        start: "0:01.500".to_string(),
+                        end: "0:03.000".to_string(),
+                    }],
+                    error: None,
+                },
+                PlannedCutJob {
+                    video_path: "/tmp/b.mp4".to_string(),
+                    ranges: Vec::new(),
+                    removed_ranges: Vec::new(),
+                    error: Some("Could not determine the duration of /tmp/b.mp4".to_string()),
+                },
+            ],
+            output_mode: "video_cleaned_default".to_string(),
+        };
+        let line = command.to_json_line().expect("command serialization should succeed");
+        let payload: serde_json::Value = serde_json::from_str(&line).expect("line should be json");
+
+        assert_eq!(payload["type"], "start_cut_batch");
+        assert_eq!(payload["jobs"][0]["videoPath"], "/tmp/a.mp4");
+        assert_eq!(payload["jobs"][0]["removedRanges"][0]["end"], "0:03.000");
+        assert!(payload["jobs"][0].get("error").is_none());
+        assert_eq!(payload["jobs"][1]["error"], "Could not determine the duration of /tmp/b.mp4");
+    }
+
+    #[test]
+    fn should_serialize_the_provided_cut_plan() {
+        let command = WorkerCommand::ProvideCutPlan {
+            task_id: "task-1".to_string(),
+            job: PlannedCutJob {
+                video_path: "/tmp/a.mp4".to_string(),
+                ranges: Vec::new(),
+                removed_ranges: Vec::new(),
+                error: Some("Task task-1 has no cut stage.".to_string()),
+            },
+        };
+        let line = command.to_json_line().expect("command serialization should succeed");
+        let payload: serde_json::Value = serde_json::from_str(&line).expect("line should be json");
+
+        assert_eq!(command.command_type(), "provide_cut_plan");
+        assert_eq!(payload["taskId"], "task-1");
+        assert_eq!(payload["job"]["videoPath"], "/tmp/a.mp4");
+        assert_eq!(payload["job"]["error"], "Task task-1 has no cut stage.");
+    }
 }
@@ -0,0 +1,366 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::{ModerationRule, ModerationSettings, RulePattern};
+
+/// Bumped whenever the pack layout changes incompatibly; packs from newer
+/// versions are rejected instead of half-imported.
+pub const RULE_PACK_VERSION: u32 = 1;
+
+/// The shareable part of moderation settings. API keys are never part of a
+/// pack, and unknown fields (such as keys pasted in by hand) are ignored.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RulePack {
+    pub pack_version: u32,
+    /// The profile the pack was exported from.
+    pub name: String,
+    pub exported_at: String,
+    pub rules: Vec<ModerationRule>,
+    #[serde(default)]
+    pub profanity_words: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleMergeStrategy {
+    /// The pack's rules and profanity words replace the current ones.
+    Replace,
+    /// Every pack rule is added; one whose id is taken by a different rule is
+    /// added under a new id.
+    Append,
+    /// One rule per id: a pack rule whose id is taken adds its patterns and
+    /// exclusions to the existing rule, which keeps its category, priority and
+    /// reason.
+    DedupeByRuleId,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictResolution {
+    Renamed,
+    Merged,
+}
+
+/// A pack rule whose id matched a different existing rule.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleConflict {
+    pub rule_id: String,
+    /// Fields that differ between the existing and the pack rule, e.g. `priority`.
+    pub fields: Vec<String>,
+    pub resolution: ConflictResolution,
+    /// The id the pack rule was added under when it was renamed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub imported_as: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleImportReport {
+    pub added_rule_ids: Vec<String>,
+    pub updated_rule_ids: Vec<String>,
+    pub removed_rule_ids: Vec<String>,
+    pub added_profanity_words: Vec<String>,
+    pub conflicts: Vec<RuleConflict>,
+}
+
+pub fn export_rule_pack(name: &str, settings: &ModerationSettings, exported_at: String) -> RulePack {
+    RulePack {
+        pack_version: RULE_PACK_VERSION,
+        name: name.to_string(),
+        exported_at,
+        rules: settings.rules.clone(),
+        profanity_words: settings.profanity_words.clone(),
+    }
+}
+
+pub fn parse_rule_pack(content: &str) -> Result<RulePack, String> {
+    let pack: RulePack =
+        serde_json::from_str(content).map_err(|error| format!("Invalid rule pack JSON: {error}"))?;
+    if pack.pack_version == 0 || pack.pack_version > RULE_PACK_VERSION {
+        return Err(format!(
+            "Unsupported rule pack version {} (this app reads up to version {RULE_PACK_VERSION}).",
+            pack.pack_version
+        ));
+    }
+    Ok(pack)
+}
+
+fn differing_fields(existing: &ModerationRule, imported: &ModerationRule) -> Vec<String> {
+    [
+        ("category", existing.category != imported.category),
+        ("priority", existing.priority != imported.priority),
+        ("reason", existing.reason != imported.reason),
+        ("patterns", existing.patterns != imported.patterns),
+        ("exclusions", existing.exclusions != imported.exclusions),
+    ]
+    .into_iter()
+    .filter(|(_, differs)| *differs)
+    .map(|(field, _)| field.to_string())
+    .collect()
+}
+
+fn unused_rule_id(rule_id: &str, rules: &[ModerationRule]) -> String {
+    let is_taken = |candidate: &str| rules.iter().any(|rule| rule.rule_id == candidate);
+    let mut candidate = format!("{rule_id}_imported");
+    let mut suffix = 2;
+    while is_taken(&candidate) {
+        candidate = format!("{rule_id}_imported_{suffix}");
+        suffix += 1;
+    }
+    candidate
+}
+
+/// Adds the patterns of `imported` missing from `existing`, returning whether any were.
+fn merge_patterns(existing: &mut Vec<RulePattern>, imported: &[RulePattern]) -> bool {
+    let mut changed = false;
+    for pattern in imported {
+        if !existing.contains(pattern) {
+            existing.push(pattern.clone());
+            changed = true;
+        }
+    }
+    changed
+}
+
+fn import_rules(
+    rules: &mut Vec<ModerationRule>,
+    pack_rules: Vec<ModerationRule>,
+    strategy: RuleMergeStrategy,
+    report: &mut RuleImportReport,
+) {
+    if strategy == RuleMergeStrategy::Replace {
+        report.removed_rule_ids = rules
+            .iter()
+            .filter(|rule| !pack_rules.iter().any(|imported| imported.rule_id == rule.rule_id))
+            .map(|rule| rule.rule_id.clone())
+            .collect();
+        for imported in &pack_rules {
+            match rules.iter().find(|rule| rule.rule_id == imported.rule_id) {
+                Some(rule) if rule == imported => {}
+                Some(_) => report.updated_rule_ids.push(imported.rule_id.clone()),
+                None => report.added_rule_ids.push(imported.rule_id.clone()),
+            }
+        }
+        *rules = pack_rules;
+        return;
+    }
+
+    for imported in pack_rules {
+        let Some(position) = rules.iter().position(|rule| rule.rule_id == imported.rule_id) else {
+            report.added_rule_ids.push(imported.rule_id.clone());
+            rules.push(imported);
+            continue;
+        };
+        let fields = differing_fields(&rules[position], &imported);
+        if fields.is_empty() {
+            continue;
+        }
+
+        if strategy == RuleMergeStrategy::Append {
+            let new_rule_id = unused_rule_id(&imported.rule_id, rules);
+            report.added_rule_ids.push(new_rule_id.clone());
+            report.conflicts.push(RuleConflict {
+                rule_id: imported.rule_id.clone(),
+                fields,
+                resolution: ConflictResolution::Renamed,
+                imported_as: Some(new_rule_id.clone()),
+            });
+            rules.push(ModerationRule {
+                rule_id: new_rule_id,
+                ..imported
+            });
+            continue;
+        }
+
+        let existing = &mut rules[position];
+        let added_patterns = merge_patterns(&mut existing.patterns, &imported.patterns);
+        let added_exclusions = merge_patterns(&mut existing.exclusions, &imported.exclusions);
+        if (added_patterns || added_exclusions) && !report.updated_rule_ids.contains(&imported.rule_id) {
+            report.updated_rule_ids.push(imported.rule_id.clone());
+        }
+        report.conflicts.push(RuleConflict {
+            rule_id: imported.rule_id,
+            fields,
+            resolution: ConflictResolution::Merged,
+            imported_as: None,
+        });
+    }
+}
+
+fn import_profanity_words(
+    words: &mut Vec<String>,
+    pack_words: Vec<String>,
+    strategy: RuleMergeStrategy,
+    report: &mut RuleImportReport,
+) {
+    let previous = if strategy == RuleMergeStrategy::Replace {
+        std::mem::take(words)
+    } else {
+        words.clone()
+    };
+    let is_known = |known: &[String], word: &str| {
+        let word = word.to_lowercase();
+        known.iter().any(|known| known.trim().to_lowercase() == word)
+    };
+
+    for word in pack_words {
+        let word = word.trim();
+        if word.is_empty() || is_known(words, word) {
+            continue;
+        }
+        if !is_known(&previous, word) {
+            report.added_profanity_words.push(word.to_string());
+        }
+        words.push(word.to_string());
+    }
+}
+
+/// Applies `pack` to `settings`, leaving everything but the rules and
+/// profanity words untouched.
+pub fn import_rule_pack(
+    settings: &mut ModerationSettings,
+    pack: RulePack,
+    strategy: RuleMergeStrategy,
+) -> RuleImportReport {
+    let mut report = RuleImportReport::default();
+    import_rules(&mut settings.rules, pack.rules, strategy, &mut report);
+    import_profanity_words(&mut settings.profanity_words, pack.profanity_words, strategy, &mut report);
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::{ModerationRule, ModerationSettings, PatternMatchType, RulePattern};
+
+    use super::{
+        export_rule_pack, import_rule_pack, parse_rule_pack, ConflictResolution, RuleConflict, RuleMergeStrategy,
+        RulePack,
+    };
+
+    fn rule(rule_id: &str, priority: &str, patterns: &[&str]) -> ModerationRule {
+        ModerationRule {
+            rule_id: rule_id.to_string(),
+            category: "custom".to_string(),
+            priority: priority.to_string(),
+            reason: "Matched moderation rule.".to_string(),
+            patterns: patterns.iter().map(|pattern| RulePattern::substring(pattern)).collect(),
+            exclusions: Vec::new(),
+        }
+    }
+
+    fn settings_with_api_key(rules: Vec<ModerationRule>, profanity_words: &[&str]) -> ModerationSettings {
+        ModerationSettings {
+            google_api_key: "google-key".to_string(),
+            ..ModerationSettings::for_tests(rules, profanity_words)
+        }
+    }
+
+    fn pack(rules: Vec<ModerationRule>, profanity_words: &[&str]) -> RulePack {
+        export_rule_pack("Shared", &settings_with_api_key(rules, profanity_words), "2026-10-17T00:00:00Z".to_string())
+    }
+
+    fn rule_ids(settings: &ModerationSettings) -> Vec<&str> {
+        settings.rules.iter().map(|rule| rule.rule_id.as_str()).collect()
+    }
+
+    #[test]
+    fn should_export_rules_and_words_without_api_keys() {
+        let exported = serde_json::to_value(pack(vec![rule("magic", "high", &["spell"])], &["dang"])).unwrap();
+
+        assert_eq!(exported["packVersion"], 1);
+        assert_eq!(exported["rules"][0]["ruleId"], "magic");
+        assert_eq!(exported["profanityWords"], serde_json::json!(["dang"]));
+        assert!(!exported.to_string().contains("google-key"));
+    }
+
+    #[test]
+    fn should_reject_packs_from_newer_versions_and_ignore_api_keys() {
+        let newer = parse_rule_pack(r#"{"packVersion": 2, "name": "x", "exportedAt": "", "rules": []}"#);
+        let with_keys = parse_rule_pack(
+            r#"{"packVersion": 1, "name": "x", "exportedAt": "", "rules": [], "googleApiKey": "stolen"}"#,
+        );
+
+        assert!(newer.unwrap_err().starts_with("Unsupported rule pack version 2"));
+        assert!(with_keys.unwrap().profanity_words.is_empty());
+    }
+
+    #[test]
+    fn should_replace_rules_and_words() {
+        let mut current =
+            settings_with_api_key(vec![rule("magic", "high", &["spell"]), rule("dating", "medium", &["date"])], &["dang"]);
+
+        let report = import_rule_pack(
+            &mut current,
+            pack(vec![rule("magic", "low", &["spell"]), rule("music", "low", &["song"])], &["heck"]),
+            RuleMergeStrategy::Replace,
+        );
+
+        assert_eq!(rule_ids(&current), vec!["magic", "music"]);
+        assert_eq!(current.profanity_words, vec!["heck"]);
+        assert_eq!(current.google_api_key, "google-key");
+        assert_eq!(report.added_rule_ids, vec!["music"]);
+        assert_eq!(report.updated_rule_ids, vec!["magic"]);
+        assert_eq!(report.removed_rule_ids, vec!["dating"]);
+        assert_eq!(report.added_profanity_words, vec!["heck"]);
+        assert!(report.conflicts.is_empty());
+    }
+
+    #[test]
+    fn should_append_conflicting_rules_under_new_ids() {
+        let mut current = ModerationSettings::for_tests(vec![rule("magic", "high", &["spell"]), rule("magic_imported", "low", &["x"])], &["Dang"]);
+
+        let report = import_rule_pack(
+            &mut current,
+            pack(
+                vec![rule("magic", "medium", &["spell"]), rule("music", "low", &["song"])],
+                &["dang", "heck"],
+            ),
+            RuleMergeStrategy::Append,
+        );
+
+        assert_eq!(rule_ids(&current), vec!["magic", "magic_imported", "magic_imported_2", "music"]);
+        assert_eq!(current.rules[2].priority, "medium");
+        assert_eq!(current.profanity_words, vec!["Dang", "heck"]);
+        assert_eq!(report.added_rule_ids, vec!["magic_imported_2", "music"]);
+        assert_eq!(
+            report.conflicts,
+            vec![RuleConflict {
+                rule_id: "magic".to_string(),
+                fields: vec!["priority".to_string()],
+                resolution: ConflictResolution::Renamed,
+                imported_as: Some("magic_imported_2".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn should_merge_patterns_of_rules_with_the_same_id() {
+        let mut current = ModerationSettings::for_tests(vec![rule("magic", "high", &["spell"]), rule("dating", "medium", &["date"])], &[]);
+        let mut imported_magic = rule("magic", "low", &["spell", "witchcraft"]);
+        imported_magic.exclusions = vec![RulePattern {
+            value: "spelling".to_string(),
+            match_type: PatternMatchType::WholeWord,
+        }];
+
+        let report = import_rule_pack(
+            &mut current,
+            pack(vec![imported_magic, rule("dating", "medium", &["date"])], &[]),
+            RuleMergeStrategy::DedupeByRuleId,
+        );
+
+        let magic = &current.rules[0];
+        assert_eq!(rule_ids(&current), vec!["magic", "dating"]);
+        assert_eq!(magic.priority, "high");
+        assert_eq!(
+            magic.patterns.iter().map(|pattern| pattern.value.as_str()).collect::<Vec<_>>(),
+            vec!["spell", "witchcraft"]
+        );
+        assert_eq!(magic.exclusions.len(), 1);
+        assert!(report.added_rule_ids.is_empty());
+        assert_eq!(report.updated_rule_ids, vec!["magic"]);
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(report.conflicts[0].fields, vec!["priority", "patterns", "exclusions"]);
+        assert_eq!(report.conflicts[0].resolution, ConflictResolution::Merged);
+    }
+}
@@ -16,12 +16,35 @@ pub struct RuntimePaths {
     pub yap_executable: PathBuf,
 }
 
-pub async fn ensure_runtime_ready(app: &AppHandle) -> Result<RuntimePaths, String> {
+fn resolve_runtime_dir(app: &AppHandle) -> Result<PathBuf, String> {
     let app_data_dir = app
         .path()
         .app_data_dir()
         .map_err(|error| format!("Failed to resolve app data directory: {error}"))?;
-    let runtime_dir = app_data_dir.join("runtime");
+    Ok(app_data_dir.join("runtime"))
+}
+
+fn ffmpeg_executable_in(runtime_dir: &Path) -> PathBuf {
+    env::var("AIYAAL_FFMPEG_PATH")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| {
+            let bundled = runtime_dir.join("bin/ffmpeg");
+            if bundled.exists() {
+                bundled
+            } else {
+                PathBuf::from("ffmpeg")
+            }
+        })
+}
+
+/// The ffmpeg the worker runs with, for host-side probes that should not wait
+/// for the Python runtime to be bootstrapped.
+pub fn resolve_ffmpeg_executable(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(ffmpeg_executable_in(&resolve_runtime_dir(app)?))
+}
+
+pub async fn ensure_runtime_ready(app: &AppHandle) -> Result<RuntimePaths, String> {
+    let runtime_dir = resolve_runtime_dir(app)?;
     fs::create_dir_all(&runtime_dir)
         .map_err(|error| format!("Failed to create runtime directory {}: {error}", runtime_dir.display()))?;
 
@@ -71,16 +94,7 @@ pub async fn ensure_runtime_ready(app: &AppHandle) -> Result<RuntimePaths, Strin
     .await
     .map_err(|error| format!("Failed waiting for Python dependency verification: {error}"))??;
 
-    let ffmpeg_executable = env::var("AIYAAL_FFMPEG_PATH")
-        .map(PathBuf::from)
-        .unwrap_or_else(|_| {
-            let bundled = runtime_dir.join("bin/ffmpeg");
-            if bundled.exists() {
-                bundled
-            } else {
-                PathBuf::from("ffmpeg")
-            }
-        });
+    let ffmpeg_executable = ffmpeg_executable_in(&runtime_dir);
 
     let yap_executable = env::var("AIYAAL_YAP_PATH")
         .map(PathBuf::from)
@@ -0,0 +1,204 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SubtitleCue {
+    pub index: u32,
+    pub start_time: f64,
+    pub end_time: f64,
+    pub text: String,
+}
+
+/// Parses `H:MM:SS,mmm` (or `.mmm`, with 1-3 millisecond digits) into seconds.
+fn parse_timestamp(value: &str) -> Option<f64> {
+    let (clock, fraction) = match value.trim().rsplit_once([',', '.']) {
+        Some((clock, fraction)) => (clock, fraction),
+        None => (value.trim(), "0"),
+    };
+    if fraction.is_empty() || fraction.len() > 3 || !fraction.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    let millis = fraction.parse::<u32>().ok()? * 10_u32.pow(3 - fraction.len() as u32);
+
+    let parts = clock
+        .split(':')
+        .map(|part| part.trim().parse::<u32>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    let (hours, minutes, seconds) = match parts.as_slice() {
+        [hours, minutes, seconds] => (*hours, *minutes, *seconds),
+        [minutes, seconds] => (0, *minutes, *seconds),
+        _ => return None,
+    };
+    if minutes >= 60 || seconds >= 60 {
+        return None;
+    }
+
+    Some(f64::from(hours) * 3600.0 + f64::from(minutes) * 60.0 + f64::from(seconds) + f64::from(millis) / 1000.0)
+}
+
+/// Parses a `start --> end` line, ignoring trailing cue settings.
+fn parse_timing_line(line: &str) -> Option<(f64, f64)> {
+    let (start, rest) = line.split_once("-->")?;
+    parse_time_range(start, rest.split_whitespace().next()?)
+}
+
+pub fn parse_time_range(start: &str, end: &str) -> Option<(f64, f64)> {
+    let start_time = parse_timestamp(start)?;
+    let end_time = parse_timestamp(end)?;
+    (end_time >= start_time).then_some((start_time, end_time))
+}
+
+pub fn format_timestamp(seconds: f64) -> String {
+    let total_millis = (seconds.max(0.0) * 1000.0).round() as u64;
+    let hours = total_millis / 3_600_000;
+    let minutes = (total_millis % 3_600_000) / 60_000;
+    let whole_seconds = (total_millis % 60_000) / 1000;
+    let millis = total_millis % 1000;
+    format!("{hours:02}:{minutes:02}:{whole_seconds:02},{millis:03}")
+}
+
+/// Groups lines into blocks separated by blank lines, counting lines that hold
+/// only spaces or tabs as blank.
+fn split_blocks(content: &str) -> Vec<Vec<&str>> {
+    let mut blocks = Vec::new();
+    let mut current = Vec::new();
+    for line in content.lines() {
+        if line.trim().is_empty() {
+            if !current.is_empty() {
+                blocks.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(line);
+        }
+    }
+    if !current.is_empty() {
+        blocks.push(current);
+    }
+    blocks
+}
+
+/// Tolerant SRT parser: accepts a BOM, CRLF/CR line endings, whitespace-only
+/// separator lines, missing or non-numeric indices and `.` or `,` before
+/// milliseconds. Blocks without a valid timing line are skipped; missing
+/// indices continue from the previous cue.
+pub fn parse_srt(content: &str) -> Vec<SubtitleCue> {
+    let normalized = content
+        .trim_start_matches('\u{feff}')
+        .replace("\r\n", "\n")
+        .replace('\r', "\n");
+
+    let mut cues: Vec<SubtitleCue> = Vec::new();
+    for lines in split_blocks(&normalized) {
+        let Some(timing_position) = lines.iter().position(|line| line.contains("-->")) else {
+            continue;
+        };
+        let Some((start_time, end_time)) = parse_timing_line(lines[timing_position]) else {
+            continue;
+        };
+
+        let previous_index = cues.last().map(|cue| cue.index).unwrap_or(0);
+        let index = timing_position
+            .checked_sub(1)
+            .and_then(|position| lines[position].trim().parse::<u32>().ok())
+            .unwrap_or(previous_index + 1);
+
+        cues.push(SubtitleCue {
+            index,
+            start_time,
+            end_time,
+            text: lines[timing_position + 1..].join("\n").trim().to_string(),
+        });
+    }
+
+    cues
+}
+
+pub fn serialize_srt(cues: &[SubtitleCue]) -> String {
+    cues.iter()
+        .map(|cue| {
+            format!(
+                "{}\n{} --> {}\n{}\n",
+                cue.index,
+                format_timestamp(cue.start_time),
+                format_timestamp(cue.end_time),
+                cue.text
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_timestamp, parse_srt, serialize_srt, SubtitleCue};
+
+    fn cue(index: u32, start_time: f64, end_time: f64, text: &str) -> SubtitleCue {
+        SubtitleCue {
+            index,
+            start_time,
+            end_time,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn should_parse_crlf_content_with_bom() {
+        let content = "\u{feff}1\r\n00:00:01,000 --> 00:00:02,500\r\nHello\r\nthere\r\n\r\n2\r\n00:01:00,000 --> 00:01:01,000\r\nBye\r\n";
+
+        assert_eq!(
+            parse_srt(content),
+            vec![cue(1, 1.0, 2.5, "Hello\nthere"), cue(2, 60.0, 61.0, "Bye")]
+        );
+    }
+
+    #[test]
+    fn should_number_cues_without_indices_and_accept_period_millis() {
+        let content = "00:00:01.5 --> 00:00:02.25\nFirst\n\n7\n00:00:03,000 --> 00:00:04,000\nSecond\n\n00:00:05.000 --> 00:00:06.000 align:start\nThird";
+
+        assert_eq!(
+            parse_srt(content),
+            vec![
+                cue(1, 1.5, 2.25, "First"),
+                cue(7, 3.0, 4.0, "Second"),
+                cue(8, 5.0, 6.0, "Third"),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_split_blocks_on_whitespace_only_lines() {
+        let content = "1\n00:00:01,000 --> 00:00:02,000\nFirst\n \n2\n00:00:03,000 --> 00:00:04,000\nSecond\n\t\n3\n00:00:05,000 --> 00:00:06,000\nThird";
+
+        assert_eq!(
+            parse_srt(content),
+            vec![
+                cue(1, 1.0, 2.0, "First"),
+                cue(2, 3.0, 4.0, "Second"),
+                cue(3, 5.0, 6.0, "Third"),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_skip_blocks_without_valid_timing() {
+        let content = "1\nnot a timing line\nText\n\n2\n00:00:09,000 --> 00:00:08,000\nBackwards\n\n3\n00:00:10,000 --> 00:00:11,000\nKept";
+
+        assert_eq!(parse_srt(content), vec![cue(3, 10.0, 11.0, "Kept")]);
+    }
+
+    #[test]
+    fn should_round_trip_serialized_cues() {
+        let cues = vec![cue(1, 1.0, 2.5, "Hello\nthere"), cue(2, 3661.001, 3662.0, "Bye")];
+
+        let content = serialize_srt(&cues);
+
+        assert!(content.contains("01:01:01,001 --> 01:01:02,000"));
+        assert_eq!(parse_srt(&content), cues);
+    }
+
+    #[test]
+    fn should_format_timestamps() {
+        assert_eq!(format_timestamp(62.5), "00:01:02,500");
+        assert_eq!(format_timestamp(-1.0), "00:00:00,000");
+    }
+}
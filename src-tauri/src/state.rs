@@ -1,21 +1,86 @@
 use std::{
-    collections::HashMap,
-    sync::Arc,
+    collections::{HashMap, HashSet},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 use tokio::sync::{mpsc, Mutex};
 
 use crate::{
-    protocol::WorkerEvent,
+    protocol::{WorkerCommand, WorkerEvent},
     types::{
-        BatchState, BatchStatus, JobStatus, TaskJobStatus, TaskKind, TaskState, TaskStatus,
+        AnalyticsTaskKind, BatchState, BatchStatus, BatchSummary, JobStatus, ListTasksRequest,
+        RunParameters, TaskHistoryItem, TaskJobStatus, TaskKind, TaskState, TaskStatus, TaskSummary,
     },
 };
 
-pub type WorkerSender = mpsc::UnboundedSender<crate::protocol::WorkerCommand>;
 const MAX_TASK_JOB_LOG_LINES: usize = 200;
 
+/// Channel to the running worker that only lets through commands the worker
+/// advertised in its `hello` handshake.
+#[derive(Clone)]
+pub struct WorkerSender {
+    sender: mpsc::UnboundedSender<WorkerCommand>,
+    worker_version: String,
+    capabilities: Arc<Vec<String>>,
+}
+
+impl WorkerSender {
+    pub fn new(
+        sender: mpsc::UnboundedSender<WorkerCommand>,
+        worker_version: String,
+        capabilities: Vec<String>,
+    ) -> Self {
+        Self {
+            sender,
+            worker_version,
+            capabilities: Arc::new(capabilities),
+        }
+    }
+
+    pub fn worker_version(&self) -> &str {
+        &self.worker_version
+    }
+
+    pub fn supports(&self, command_type: &str) -> bool {
+        self.capabilities.iter().any(|capability| capability == command_type)
+    }
+
+    pub fn send(&self, command: WorkerCommand) -> Result<(), String> {
+        let command_type = command.command_type();
+        if !self.supports(command_type) {
+            return Err(format!(
+                "The running worker ({}) does not support `{command_type}`. Reinstall the app to update the bundled worker.",
+                self.worker_version
+            ));
+        }
+        for feature in command.required_features() {
+            if !self.supports(feature) {
+                return Err(format!(
+                    "The running worker ({}) does not support `{feature}` for `{command_type}`. Reinstall the app to update the bundled worker.",
+                    self.worker_version
+                ));
+            }
+        }
+
+        self.sender
+            .send(command)
+            .map_err(|_| "Worker channel is closed.".to_string())
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.sender.is_closed()
+    }
+
+    /// A handle that does not keep the worker's stdin channel open.
+    pub fn downgrade(&self) -> mpsc::WeakUnboundedSender<WorkerCommand> {
+        self.sender.downgrade()
+    }
+}
+
 fn push_bounded_log(logs: &mut Vec<String>, message: String) {
     logs.push(message);
     if logs.len() > MAX_TASK_JOB_LOG_LINES {
@@ -30,10 +95,22 @@ pub struct AppState {
     pub tasks: Arc<Mutex<HashMap<String, TaskState>>>,
     pub batch_started_at: Arc<Mutex<HashMap<String, u64>>>,
     pub task_started_at: Arc<Mutex<HashMap<String, u64>>>,
+    /// Jobs a resumed batch or task completed before it was interrupted, which
+    /// its final summary also counts.
+    pub resumed_completed_jobs: Arc<Mutex<HashMap<String, usize>>>,
     pub worker_sender: Arc<Mutex<Option<WorkerSender>>>,
+    pub worker_start_lock: Arc<Mutex<()>>,
+    pub worker_restart_attempts: Arc<Mutex<u32>>,
+    /// Set whenever a batch, task or job changes status so the history writer
+    /// knows to flush.
+    pub history_dirty: Arc<AtomicBool>,
+    /// Set by progress and log updates, which the history writer flushes less often.
+    pub history_progress_dirty: Arc<AtomicBool>,
+    /// Cancel flags of tasks the host runs itself instead of the worker.
+    pub native_task_cancellations: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
 }
 
-fn now_epoch_seconds() -> u64 {
+pub fn now_epoch_seconds() -> u64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap_or(Duration::from_secs(0))
@@ -47,8 +124,277 @@ impl AppState {
             tasks: Arc::new(Mutex::new(HashMap::new())),
             batch_started_at: Arc::new(Mutex::new(HashMap::new())),
             task_started_at: Arc::new(Mutex::new(HashMap::new())),
+            resumed_completed_jobs: Arc::new(Mutex::new(HashMap::new())),
             worker_sender: Arc::new(Mutex::new(None)),
+            worker_start_lock: Arc::new(Mutex::new(())),
+            worker_restart_attempts: Arc::new(Mutex::new(0)),
+            history_dirty: Arc::new(AtomicBool::new(false)),
+            history_progress_dirty: Arc::new(AtomicBool::new(false)),
+            native_task_cancellations: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub fn mark_history_dirty(&self) {
+        self.history_dirty.store(true, Ordering::Release);
+    }
+
+    pub fn take_history_dirty(&self) -> bool {
+        self.history_dirty.swap(false, Ordering::AcqRel)
+    }
+
+    pub fn take_history_progress_dirty(&self) -> bool {
+        self.history_progress_dirty.swap(false, Ordering::AcqRel)
+    }
+
+    pub async fn history_snapshot(&self) -> (Vec<BatchState>, Vec<TaskState>) {
+        let batches = self.batches.lock().await.values().cloned().collect();
+        let tasks = self.tasks.lock().await.values().cloned().collect();
+        (batches, tasks)
+    }
+
+    /// Loads runs persisted by a previous session. Runs already known to this
+    /// session win, so a late restore never clobbers live state.
+    pub async fn restore_history(&self, restored_batches: Vec<BatchState>, restored_tasks: Vec<TaskState>) {
+        let mut batches = self.batches.lock().await;
+        for batch in restored_batches {
+            batches.entry(batch.batch_id.clone()).or_insert(batch);
+        }
+        drop(batches);
+
+        let mut tasks = self.tasks.lock().await;
+        for task in restored_tasks {
+            tasks.entry(task.task_id.clone()).or_insert(task);
+        }
+    }
+
+    /// Re-queues the jobs of an interrupted batch that never completed and
+    /// returns their input paths together with the batch's original parameters.
+    pub async fn requeue_interrupted_batch(&self, batch_id: &str) -> Result<(Vec<String>, RunParameters), String> {
+        let mut batches = self.batches.lock().await;
+        let Some(batch) = batches.get_mut(batch_id) else {
+            return Err(format!("Batch {batch_id} was not found."));
+        };
+        if batch.status != BatchStatus::Interrupted {
+            return Err(format!("Batch {batch_id} was not interrupted."));
+        }
+        let Some(parameters) = batch.parameters.clone() else {
+            return Err(format!(
+                "Batch {batch_id} did not record its original settings and cannot be resumed."
+            ));
+        };
+
+        let completed_count = batch.jobs.iter().filter(|job| job.status == JobStatus::Completed).count();
+        let mut input_paths = Vec::new();
+        for job in batch.jobs.iter_mut().filter(|job| job.status != JobStatus::Completed) {
+            job.status = JobStatus::Queued;
+            job.progress_pct = 0;
+            job.error = None;
+            input_paths.push(job.input_path.clone());
+        }
+        if input_paths.is_empty() {
+            return Err(format!("Batch {batch_id} has no unfinished files to resume."));
+        }
+        batch.status = BatchStatus::Queued;
+        batch.summary = None;
+        drop(batches);
+
+        self.batch_started_at
+            .lock()
+            .await
+            .insert(batch_id.to_string(), now_epoch_seconds());
+        self.resumed_completed_jobs
+            .lock()
+            .await
+            .insert(batch_id.to_string(), completed_count);
+        self.mark_history_dirty();
+        Ok((input_paths, parameters))
+    }
+
+    /// Puts back a batch whose resume could not be sent to the worker, so it
+    /// stays interrupted and can be resumed again.
+    pub async fn restore_interrupted_batch(&self, previous: BatchState) {
+        let batch_id = previous.batch_id.clone();
+        self.batches.lock().await.insert(batch_id.clone(), previous);
+        self.batch_started_at.lock().await.remove(&batch_id);
+        self.resumed_completed_jobs.lock().await.remove(&batch_id);
+        self.mark_history_dirty();
+    }
+
+    pub async fn requeue_interrupted_task(&self, task_id: &str) -> Result<(Vec<String>, RunParameters), String> {
+        let mut tasks = self.tasks.lock().await;
+        let Some(task) = tasks.get_mut(task_id) else {
+            return Err(format!("Task {task_id} was not found."));
+        };
+        if task.status != TaskStatus::Interrupted {
+            return Err(format!("Task {task_id} was not interrupted."));
+        }
+        let Some(parameters) = task.parameters.clone() else {
+            return Err(format!(
+                "Task {task_id} did not record its original settings and cannot be resumed."
+            ));
+        };
+
+        let completed_count = task.jobs.iter().filter(|job| job.status == TaskJobStatus::Completed).count();
+        let mut input_paths = Vec::new();
+        for job in task.jobs.iter_mut().filter(|job| job.status != TaskJobStatus::Completed) {
+            job.status = TaskJobStatus::Queued;
+            job.progress_pct = 0;
+            job.error = None;
+            input_paths.push(job.input_path.clone());
+        }
+        if input_paths.is_empty() {
+            return Err(format!("Task {task_id} has no unfinished files to resume."));
+        }
+        task.status = TaskStatus::Queued;
+        task.summary = None;
+        drop(tasks);
+
+        self.task_started_at
+            .lock()
+            .await
+            .insert(task_id.to_string(), now_epoch_seconds());
+        self.resumed_completed_jobs
+            .lock()
+            .await
+            .insert(task_id.to_string(), completed_count);
+        self.mark_history_dirty();
+        Ok((input_paths, parameters))
+    }
+
+    pub async fn restore_interrupted_task(&self, previous: TaskState) {
+        let task_id = previous.task_id.clone();
+        self.tasks.lock().await.insert(task_id.clone(), previous);
+        self.task_started_at.lock().await.remove(&task_id);
+        self.resumed_completed_jobs.lock().await.remove(&task_id);
+        self.mark_history_dirty();
+    }
+
+    async fn take_resumed_completed_jobs(&self, id: &str) -> usize {
+        self.resumed_completed_jobs.lock().await.remove(id).unwrap_or(0)
+    }
+
+    /// Input paths and parameters for a retry run of a finished batch.
+    pub async fn batch_retry_inputs(
+        &self,
+        batch_id: &str,
+        job_ids: Option<&[String]>,
+    ) -> Result<(Vec<String>, RunParameters), String> {
+        let batches = self.batches.lock().await;
+        let Some(batch) = batches.get(batch_id) else {
+            return Err(format!("Batch {batch_id} was not found."));
+        };
+        if batch.status.is_active() {
+            return Err(format!("Batch {batch_id} is still running. Retry once it finishes."));
         }
+        let Some(parameters) = batch.parameters.clone() else {
+            return Err(format!(
+                "Batch {batch_id} did not record its original settings and cannot be retried."
+            ));
+        };
+
+        let jobs = batch
+            .jobs
+            .iter()
+            .map(|job| {
+                let retryable = matches!(
+                    job.status,
+                    JobStatus::Failed | JobStatus::Cancelled | JobStatus::Interrupted
+                );
+                (job.job_id.as_str(), job.input_path.as_str(), retryable)
+            })
+            .collect::<Vec<_>>();
+        let input_paths = select_retry_inputs(&format!("Batch {batch_id}"), &jobs, job_ids)?;
+        Ok((input_paths, parameters))
+    }
+
+    /// Task kind, input paths and parameters for a retry run of a finished task.
+    pub async fn task_retry_inputs(
+        &self,
+        task_id: &str,
+        job_ids: Option<&[String]>,
+    ) -> Result<(TaskKind, Vec<String>, RunParameters), String> {
+        let tasks = self.tasks.lock().await;
+        let Some(task) = tasks.get(task_id) else {
+            return Err(format!("Task {task_id} was not found."));
+        };
+        if task.status.is_active() {
+            return Err(format!("Task {task_id} is still running. Retry once it finishes."));
+        }
+        let Some(parameters) = task.parameters.clone() else {
+            return Err(format!(
+                "Task {task_id} did not record its original settings and cannot be retried."
+            ));
+        };
+
+        let jobs = task
+            .jobs
+            .iter()
+            .map(|job| {
+                let retryable = matches!(
+                    job.status,
+                    TaskJobStatus::Failed | TaskJobStatus::Cancelled | TaskJobStatus::Interrupted
+                );
+                (job.job_id.as_str(), job.input_path.as_str(), retryable)
+            })
+            .collect::<Vec<_>>();
+        let input_paths = select_retry_inputs(&format!("Task {task_id}"), &jobs, job_ids)?;
+        Ok((task.task_kind.clone(), input_paths, parameters))
+    }
+
+    pub async fn list_history(&self, request: &ListTasksRequest) -> Vec<TaskHistoryItem> {
+        let mut items = Vec::new();
+
+        let batches = self.batches.lock().await;
+        items.extend(batches.values().map(|batch| TaskHistoryItem {
+            id: batch.batch_id.clone(),
+            kind: AnalyticsTaskKind::RemoveMusic,
+            status: batch_status_as_task_status(&batch.status),
+            job_count: batch.jobs.len(),
+            summary: batch.summary.as_ref().map(|summary| TaskSummary {
+                ok: summary.ok,
+                failed: summary.failed,
+                cancelled: summary.cancelled,
+                interrupted: summary.interrupted,
+            }),
+            created_at_epoch_seconds: batch.created_at_epoch_seconds,
+            parent_task_id: batch.parent_task_id.clone(),
+        }));
+        drop(batches);
+
+        let tasks = self.tasks.lock().await;
+        items.extend(tasks.values().map(|task| TaskHistoryItem {
+            id: task.task_id.clone(),
+            kind: match task.task_kind {
+                TaskKind::Transcription => AnalyticsTaskKind::Transcription,
+                TaskKind::Flag => AnalyticsTaskKind::Flag,
+                TaskKind::Cut => AnalyticsTaskKind::Cut,
+                TaskKind::Pipeline => AnalyticsTaskKind::Pipeline,
+            },
+            status: task.status.clone(),
+            job_count: task.jobs.len(),
+            summary: task.summary.clone(),
+            created_at_epoch_seconds: task.created_at_epoch_seconds,
+            parent_task_id: task.parent_task_id.clone(),
+        }));
+        drop(tasks);
+
+        items.retain(|item| {
+            request.kind.as_ref().is_none_or(|kind| *kind == item.kind)
+                && request.status.as_ref().is_none_or(|status| *status == item.status)
+                && request
+                    .since_epoch_seconds
+                    .is_none_or(|since| item.created_at_epoch_seconds >= since)
+                && request
+                    .until_epoch_seconds
+                    .is_none_or(|until| item.created_at_epoch_seconds <= until)
+        });
+        items.sort_by(|left, right| {
+            right
+                .created_at_epoch_seconds
+                .cmp(&left.created_at_epoch_seconds)
+                .then_with(|| left.id.cmp(&right.id))
+        });
+        items
     }
 
     pub async fn insert_batch(&self, batch: BatchState) {
@@ -56,11 +402,24 @@ impl AppState {
         let mut batches = self.batches.lock().await;
         batches.insert(batch_id.clone(), batch);
         drop(batches);
+        self.mark_history_dirty();
 
         let mut batch_started_at = self.batch_started_at.lock().await;
         batch_started_at.insert(batch_id, now_epoch_seconds());
     }
 
+    /// Drops a batch whose start failed before the worker received it, so it
+    /// never reaches the history.
+    pub async fn remove_batch(&self, batch_id: &str) {
+        let mut batches = self.batches.lock().await;
+        batches.remove(batch_id);
+        drop(batches);
+        self.mark_history_dirty();
+
+        let mut batch_started_at = self.batch_started_at.lock().await;
+        batch_started_at.remove(batch_id);
+    }
+
     pub async fn get_batch(&self, batch_id: &str) -> Option<BatchState> {
         let batches = self.batches.lock().await;
         batches.get(batch_id).cloned()
@@ -81,16 +440,60 @@ impl AppState {
         let mut tasks = self.tasks.lock().await;
         tasks.insert(task_id.clone(), task);
         drop(tasks);
+        self.mark_history_dirty();
 
         let mut task_started_at = self.task_started_at.lock().await;
         task_started_at.insert(task_id, now_epoch_seconds());
     }
 
+    /// Drops a task whose start failed before the worker received it, so it
+    /// never reaches the history.
+    pub async fn remove_task(&self, task_id: &str) {
+        let mut tasks = self.tasks.lock().await;
+        tasks.remove(task_id);
+        drop(tasks);
+        self.mark_history_dirty();
+
+        let mut task_started_at = self.task_started_at.lock().await;
+        task_started_at.remove(task_id);
+    }
+
     pub async fn get_task(&self, task_id: &str) -> Option<TaskState> {
         let tasks = self.tasks.lock().await;
         tasks.get(task_id).cloned()
     }
 
+    pub async fn register_native_task(&self, task_id: &str) -> Arc<AtomicBool> {
+        let cancel_requested = Arc::new(AtomicBool::new(false));
+        let mut cancellations = self.native_task_cancellations.lock().await;
+        cancellations.insert(task_id.to_string(), cancel_requested.clone());
+        cancel_requested
+    }
+
+    /// Returns false when `task_id` is not a running native task.
+    pub async fn cancel_native_task(&self, task_id: &str) -> bool {
+        let cancellations = self.native_task_cancellations.lock().await;
+        cancellations
+            .get(task_id)
+            .map(|cancel_requested| cancel_requested.store(true, Ordering::Release))
+            .is_some()
+    }
+
+    pub async fn finish_native_task(&self, task_id: &str) {
+        let mut cancellations = self.native_task_cancellations.lock().await;
+        cancellations.remove(task_id);
+    }
+
+    pub async fn is_native_task(&self, task_id: &str) -> bool {
+        let cancellations = self.native_task_cancellations.lock().await;
+        cancellations.contains_key(task_id)
+    }
+
+    async fn native_task_ids(&self) -> HashSet<String> {
+        let cancellations = self.native_task_cancellations.lock().await;
+        cancellations.keys().cloned().collect()
+    }
+
     pub async fn take_task_started_at(&self, task_id: &str) -> Option<u64> {
         let mut task_started_at = self.task_started_at.lock().await;
         task_started_at.remove(task_id)
@@ -106,7 +509,147 @@ impl AppState {
         worker_sender.clone()
     }
 
+    pub async fn next_worker_restart_attempt(&self) -> u32 {
+        let mut attempts = self.worker_restart_attempts.lock().await;
+        *attempts += 1;
+        *attempts
+    }
+
+    pub async fn reset_worker_restart_attempts(&self) {
+        let mut attempts = self.worker_restart_attempts.lock().await;
+        *attempts = 0;
+    }
+
+    pub async fn running_job_labels(&self) -> Vec<String> {
+        let mut labels = Vec::new();
+
+        let batches = self.batches.lock().await;
+        for batch in batches.values().filter(|batch| batch.status != BatchStatus::Paused) {
+            for job in batch.jobs.iter().filter(|job| job.status == JobStatus::Running) {
+                labels.push(format!("{} (batch {})", job.file_name, batch.batch_id));
+            }
+        }
+        drop(batches);
+
+        // Native tasks run in the host, so they are never waiting on the worker.
+        let native_task_ids = self.native_task_ids().await;
+        let tasks = self.tasks.lock().await;
+        for task in tasks
+            .values()
+            .filter(|task| task.status != TaskStatus::Paused && !native_task_ids.contains(&task.task_id))
+        {
+            for job in task.jobs.iter().filter(|job| job.status == TaskJobStatus::Running) {
+                labels.push(format!(
+                    "{} ({} task {})",
+                    job.file_name,
+                    task_kind_name(&task.task_kind),
+                    task.task_id
+                ));
+            }
+        }
+
+        labels.sort();
+        labels
+    }
+
+    /// Builds the events that close out every batch/task the worker was still
+    /// responsible for when it died: running jobs are interrupted with
+    /// `reason`, queued jobs are interrupted, and each run gets a terminal
+    /// `*_done` summary counting both as interrupted so the run can be resumed.
+    /// Files completed before a resume are left out of `ok`; applying the
+    /// `*_done` event adds them back like it does for worker summaries.
+    pub async fn orphaned_work_events(&self, reason: &str) -> Vec<WorkerEvent> {
+        let mut events = Vec::new();
+        let resumed_completed_jobs = self.resumed_completed_jobs.lock().await.clone();
+
+        let batches = self.batches.lock().await;
+        for batch in batches
+            .values()
+            .filter(|batch| batch.status.is_active())
+        {
+            let mut summary = BatchSummary::default();
+            for job in &batch.jobs {
+                match job.status {
+                    JobStatus::Running => {
+                        summary.interrupted += 1;
+                        events.push(WorkerEvent::JobInterrupted {
+                            batch_id: Some(batch.batch_id.clone()),
+                            task_id: None,
+                            task_kind: None,
+                            job_id: job.job_id.clone(),
+                            error: reason.to_string(),
+                        });
+                    }
+                    JobStatus::Completed => summary.ok += 1,
+                    JobStatus::Failed => summary.failed += 1,
+                    JobStatus::Interrupted | JobStatus::Queued => summary.interrupted += 1,
+                    JobStatus::Cancelled => summary.cancelled += 1,
+                }
+            }
+            if let Some(completed_before) = resumed_completed_jobs.get(&batch.batch_id) {
+                summary.ok = summary.ok.saturating_sub(*completed_before);
+            }
+            events.push(WorkerEvent::BatchDone {
+                batch_id: batch.batch_id.clone(),
+                summary,
+            });
+        }
+        drop(batches);
+
+        // Native tasks keep running without the worker and close themselves out.
+        let native_task_ids = self.native_task_ids().await;
+        let tasks = self.tasks.lock().await;
+        for task in tasks
+            .values()
+            .filter(|task| task.status.is_active() && !native_task_ids.contains(&task.task_id))
+        {
+            let task_kind = task_kind_name(&task.task_kind);
+            let mut summary = TaskSummary::default();
+            for job in &task.jobs {
+                match job.status {
+                    TaskJobStatus::Running => {
+                        summary.interrupted += 1;
+                        events.push(WorkerEvent::JobInterrupted {
+                            batch_id: None,
+                            task_id: Some(task.task_id.clone()),
+                            task_kind: Some(task_kind.to_string()),
+                            job_id: job.job_id.clone(),
+                            error: reason.to_string(),
+                        });
+                    }
+                    TaskJobStatus::Completed => summary.ok += 1,
+                    TaskJobStatus::Failed => summary.failed += 1,
+                    TaskJobStatus::Interrupted | TaskJobStatus::Queued => summary.interrupted += 1,
+                    TaskJobStatus::Cancelled => summary.cancelled += 1,
+                }
+            }
+            if let Some(completed_before) = resumed_completed_jobs.get(&task.task_id) {
+                summary.ok = summary.ok.saturating_sub(*completed_before);
+            }
+            events.push(WorkerEvent::TaskDone {
+                task_id: task.task_id.clone(),
+                task_kind: task_kind.to_string(),
+                summary,
+            });
+        }
+
+        events
+    }
+
     pub async fn apply_worker_event(&self, event: &WorkerEvent) {
+        match event {
+            WorkerEvent::WorkerStatus { .. }
+            | WorkerEvent::AnalysisReplaced { .. }
+            | WorkerEvent::CutPlanNeeded { .. }
+            | WorkerEvent::Pong { .. }
+            | WorkerEvent::Hello { .. } => {}
+            // A job's first progress event also changes its status; that arm marks the history dirty.
+            WorkerEvent::JobProgress { .. } | WorkerEvent::JobLog { .. } => {
+                self.history_progress_dirty.store(true, Ordering::Release);
+            }
+            _ => self.mark_history_dirty(),
+        }
+
         match event {
             WorkerEvent::JobProgress {
                 batch_id,
@@ -120,6 +663,9 @@ impl AppState {
                     if let Some(batch) = batches.get_mut(batch_id) {
                         batch.status = BatchStatus::Running;
                         if let Some(job) = batch.jobs.iter_mut().find(|job| job.job_id == *job_id) {
+                            if job.status != JobStatus::Running {
+                                self.mark_history_dirty();
+                            }
                             job.status = JobStatus::Running;
                             job.progress_pct = progress_pct.round().clamp(0.0, 100.0) as u8;
                         }
@@ -134,6 +680,9 @@ impl AppState {
                     if let Some(task) = tasks.get_mut(task_id) {
                         task.status = TaskStatus::Running;
                         if let Some(job) = task.jobs.iter_mut().find(|job| job.job_id == *job_id) {
+                            if job.status != TaskJobStatus::Running {
+                                self.mark_history_dirty();
+                            }
                             job.status = TaskJobStatus::Running;
                             job.progress_pct = progress_pct.round().clamp(0.0, 100.0) as u8;
                         }
@@ -207,18 +756,133 @@ impl AppState {
                     }
                 }
             }
+            WorkerEvent::JobCancelled {
+                batch_id,
+                task_id,
+                task_kind,
+                job_id,
+            } => {
+                if let Some(batch_id) = batch_id {
+                    let mut batches = self.batches.lock().await;
+                    if let Some(batch) = batches.get_mut(batch_id) {
+                        if let Some(job) = batch.jobs.iter_mut().find(|job| job.job_id == *job_id) {
+                            job.status = JobStatus::Cancelled;
+                        }
+                    }
+                }
+
+                if let (Some(task_id), Some(task_kind)) = (task_id, task_kind) {
+                    let Some(_task_kind) = parse_task_kind(task_kind) else {
+                        return;
+                    };
+                    let mut tasks = self.tasks.lock().await;
+                    if let Some(task) = tasks.get_mut(task_id) {
+                        if let Some(job) = task.jobs.iter_mut().find(|job| job.job_id == *job_id) {
+                            job.status = TaskJobStatus::Cancelled;
+                        }
+                    }
+                }
+            }
+            WorkerEvent::JobInterrupted {
+                batch_id,
+                task_id,
+                task_kind,
+                job_id,
+                error,
+            } => {
+                if let Some(batch_id) = batch_id {
+                    let mut batches = self.batches.lock().await;
+                    if let Some(batch) = batches.get_mut(batch_id) {
+                        if let Some(job) = batch.jobs.iter_mut().find(|job| job.job_id == *job_id) {
+                            job.status = JobStatus::Interrupted;
+                            job.error = Some(error.clone());
+                        }
+                    }
+                }
+
+                if let (Some(task_id), Some(task_kind)) = (task_id, task_kind) {
+                    let Some(_task_kind) = parse_task_kind(task_kind) else {
+                        return;
+                    };
+                    let mut tasks = self.tasks.lock().await;
+                    if let Some(task) = tasks.get_mut(task_id) {
+                        if let Some(job) = task.jobs.iter_mut().find(|job| job.job_id == *job_id) {
+                            job.status = TaskJobStatus::Interrupted;
+                            job.error = Some(error.clone());
+                        }
+                    }
+                }
+            }
+            WorkerEvent::JobStage {
+                task_id,
+                task_kind,
+                job_id,
+                stage,
+            } => {
+                let Some(_task_kind) = parse_task_kind(task_kind) else {
+                    return;
+                };
+                let mut tasks = self.tasks.lock().await;
+                if let Some(task) = tasks.get_mut(task_id) {
+                    if let Some(job) = task.jobs.iter_mut().find(|job| job.job_id == *job_id) {
+                        job.stage = Some(*stage);
+                    }
+                }
+            }
+            WorkerEvent::BatchPaused { batch_id } => {
+                let mut batches = self.batches.lock().await;
+                if let Some(batch) = batches.get_mut(batch_id) {
+                    if matches!(batch.status, BatchStatus::Queued | BatchStatus::Running) {
+                        batch.status = BatchStatus::Paused;
+                    }
+                }
+            }
+            WorkerEvent::BatchResumed { batch_id } => {
+                let mut batches = self.batches.lock().await;
+                if let Some(batch) = batches.get_mut(batch_id) {
+                    if batch.status == BatchStatus::Paused {
+                        batch.status = BatchStatus::Running;
+                    }
+                }
+            }
+            WorkerEvent::TaskPaused { task_id, .. } => {
+                let mut tasks = self.tasks.lock().await;
+                if let Some(task) = tasks.get_mut(task_id) {
+                    if matches!(task.status, TaskStatus::Queued | TaskStatus::Running) {
+                        task.status = TaskStatus::Paused;
+                    }
+                }
+            }
+            WorkerEvent::TaskResumed { task_id, .. } => {
+                let mut tasks = self.tasks.lock().await;
+                if let Some(task) = tasks.get_mut(task_id) {
+                    if task.status == TaskStatus::Paused {
+                        task.status = TaskStatus::Running;
+                    }
+                }
+            }
             WorkerEvent::BatchDone { batch_id, summary } => {
+                let completed_before = self.take_resumed_completed_jobs(batch_id).await;
                 let mut batches = self.batches.lock().await;
                 if let Some(batch) = batches.get_mut(batch_id) {
-                    batch.summary = Some(summary.clone());
-                    batch.status = if summary.cancelled > 0 {
+                    batch.summary = Some(BatchSummary {
+                        ok: summary.ok + completed_before,
+                        ..summary.clone()
+                    });
+                    batch.status = if summary.interrupted > 0 {
+                        BatchStatus::Interrupted
+                    } else if summary.cancelled > 0 {
                         BatchStatus::Cancelled
                     } else {
                         BatchStatus::Completed
                     };
                     for job in &mut batch.jobs {
                         if job.status == JobStatus::Queued {
-                            job.status = JobStatus::Cancelled;
+                            job.status = if summary.interrupted > 0 {
+                                JobStatus::Interrupted
+                            } else {
+                                JobStatus::Cancelled
+                            };
                         } else if job.status == JobStatus::Running {
                             job.status = JobStatus::Failed;
                             if job.error.is_none() {
@@ -237,17 +901,27 @@ impl AppState {
                 let Some(_task_kind) = parse_task_kind(task_kind) else {
                     return;
                 };
+                let completed_before = self.take_resumed_completed_jobs(task_id).await;
                 let mut tasks = self.tasks.lock().await;
                 if let Some(task) = tasks.get_mut(task_id) {
-                    task.summary = Some(summary.clone());
-                    task.status = if summary.cancelled > 0 {
+                    task.summary = Some(TaskSummary {
+                        ok: summary.ok + completed_before,
+                        ..summary.clone()
+                    });
+                    task.status = if summary.interrupted > 0 {
+                        TaskStatus::Interrupted
+                    } else if summary.cancelled > 0 {
                         TaskStatus::Cancelled
                     } else {
                         TaskStatus::Completed
                     };
                     for job in &mut task.jobs {
                         if job.status == TaskJobStatus::Queued {
-                            job.status = TaskJobStatus::Cancelled;
+                            job.status = if summary.interrupted > 0 {
+                                TaskJobStatus::Interrupted
+                            } else {
+                                TaskJobStatus::Cancelled
+                            };
                         } else if job.status == TaskJobStatus::Running {
                             job.status = TaskJobStatus::Failed;
                             if job.error.is_none() {
@@ -277,7 +951,11 @@ impl AppState {
                     }
                 }
             }
-            WorkerEvent::WorkerStatus { .. } => {}
+            WorkerEvent::WorkerStatus { .. }
+            | WorkerEvent::AnalysisReplaced { .. }
+            | WorkerEvent::CutPlanNeeded { .. }
+            | WorkerEvent::Pong { .. }
+            | WorkerEvent::Hello { .. } => {}
         }
     }
 }
@@ -287,21 +965,82 @@ fn parse_task_kind(value: &str) -> Option<TaskKind> {
         "transcription" => Some(TaskKind::Transcription),
         "flag" => Some(TaskKind::Flag),
         "cut" => Some(TaskKind::Cut),
+        "pipeline" => Some(TaskKind::Pipeline),
         _ => None,
     }
 }
 
+/// Picks the input paths to retry from `(job_id, input_path, retryable)`
+/// triples: the requested jobs, or every retryable job when none are named.
+fn select_retry_inputs(
+    run_label: &str,
+    jobs: &[(&str, &str, bool)],
+    job_ids: Option<&[String]>,
+) -> Result<Vec<String>, String> {
+    let input_paths = match job_ids {
+        None => jobs
+            .iter()
+            .filter(|(_, _, retryable)| *retryable)
+            .map(|(_, input_path, _)| input_path.to_string())
+            .collect::<Vec<_>>(),
+        Some(job_ids) => {
+            let mut input_paths = Vec::new();
+            for job_id in job_ids {
+                let Some((_, input_path, retryable)) = jobs.iter().find(|(id, _, _)| id == job_id) else {
+                    return Err(format!("{run_label} has no job {job_id}."));
+                };
+                if !retryable {
+                    return Err(format!("Job {job_id} in {run_label} did not fail and cannot be retried."));
+                }
+                if !input_paths.iter().any(|path| path == input_path) {
+                    input_paths.push(input_path.to_string());
+                }
+            }
+            input_paths
+        }
+    };
+
+    if input_paths.is_empty() {
+        return Err(format!("{run_label} has no failed jobs to retry."));
+    }
+    Ok(input_paths)
+}
+
+fn batch_status_as_task_status(status: &BatchStatus) -> TaskStatus {
+    match status {
+        BatchStatus::Queued => TaskStatus::Queued,
+        BatchStatus::Running => TaskStatus::Running,
+        BatchStatus::Paused => TaskStatus::Paused,
+        BatchStatus::Completed => TaskStatus::Completed,
+        BatchStatus::Cancelled => TaskStatus::Cancelled,
+        BatchStatus::Interrupted => TaskStatus::Interrupted,
+    }
+}
+
+fn task_kind_name(task_kind: &TaskKind) -> &'static str {
+    match task_kind {
+        TaskKind::Transcription => "transcription",
+        TaskKind::Flag => "flag",
+        TaskKind::Cut => "cut",
+        TaskKind::Pipeline => "pipeline",
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use tokio::sync::mpsc;
+
     use crate::{
-        protocol::WorkerEvent,
+        protocol::{WorkerCommand, WorkerEvent},
         types::{
-            BatchState, BatchStatus, JobRecord, JobStatus, TaskJobRecord, TaskJobStatus,
-            TaskState, TaskSummary,
+            AnalyticsTaskKind, BatchState, BatchStatus, BatchSummary, JobRecord, JobStatus,
+            ListTasksRequest, RunParameters, TaskJobRecord, TaskJobStatus, TaskState, TaskSummary,
         },
     };
 
-    use super::{push_bounded_log, AppState, MAX_TASK_JOB_LOG_LINES};
+    use super::{
+        push_bounded_log, select_retry_inputs, AppState, WorkerSender, MAX_TASK_JOB_LOG_LINES,
+    };
 
     fn seed_batch() -> BatchState {
         BatchState {
@@ -328,6 +1067,9 @@ mod tests {
                 },
             ],
             summary: None,
+            created_at_epoch_seconds: 0,
+            parameters: None,
+            parent_task_id: None,
         }
     }
 
@@ -346,9 +1088,268 @@ mod tests {
                 progress_pct: 0,
                 error: None,
                 logs: Vec::new(),
+                stage: None,
             }],
             summary: None,
+            created_at_epoch_seconds: 0,
+            parameters: None,
+            parent_task_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn should_list_history_newest_first_with_filters() {
+        let state = AppState::new();
+        let mut batch = seed_batch();
+        batch.created_at_epoch_seconds = 100;
+        batch.status = BatchStatus::Completed;
+        let mut task = seed_task();
+        task.created_at_epoch_seconds = 200;
+        state.restore_history(vec![batch], vec![task]).await;
+
+        let all = state.list_history(&ListTasksRequest::default()).await;
+        assert_eq!(
+            all.iter().map(|item| item.id.as_str()).collect::<Vec<_>>(),
+            vec!["task-1", "batch-1"]
+        );
+
+        let remove_music = state
+            .list_history(&ListTasksRequest {
+                kind: Some(AnalyticsTaskKind::RemoveMusic),
+                status: Some(crate::types::TaskStatus::Completed),
+                ..ListTasksRequest::default()
+            })
+            .await;
+        assert_eq!(remove_music.len(), 1);
+        assert_eq!(remove_music[0].id, "batch-1");
+
+        let recent = state
+            .list_history(&ListTasksRequest {
+                since_epoch_seconds: Some(150),
+                ..ListTasksRequest::default()
+            })
+            .await;
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].id, "task-1");
+    }
+
+    #[tokio::test]
+    async fn should_requeue_only_unfinished_jobs_of_interrupted_batch() {
+        let state = AppState::new();
+        let mut batch = seed_batch();
+        batch.status = BatchStatus::Interrupted;
+        batch.jobs[0].status = JobStatus::Completed;
+        batch.jobs[1].status = JobStatus::Interrupted;
+        batch.jobs[1].error = Some("Interrupted".to_string());
+        batch.parameters = Some(RunParameters::RemoveMusic {
+            output_dir: "/tmp/out".to_string(),
+            compute_mode: "auto".to_string(),
+        });
+        state.restore_history(vec![batch], Vec::new()).await;
+
+        let (input_paths, parameters) = state
+            .requeue_interrupted_batch("batch-1")
+            .await
+            .expect("interrupted batch should be requeued");
+
+        assert_eq!(input_paths, vec!["/tmp/b.mp4".to_string()]);
+        assert!(matches!(parameters, RunParameters::RemoveMusic { .. }));
+        let batch = state.get_batch("batch-1").await.unwrap();
+        assert_eq!(batch.status, BatchStatus::Queued);
+        assert_eq!(batch.jobs[0].status, JobStatus::Completed);
+        assert_eq!(batch.jobs[1].status, JobStatus::Queued);
+        assert!(batch.jobs[1].error.is_none());
+    }
+
+    fn interrupted_batch() -> BatchState {
+        let mut batch = seed_batch();
+        batch.status = BatchStatus::Interrupted;
+        batch.jobs[0].status = JobStatus::Completed;
+        batch.jobs[1].status = JobStatus::Interrupted;
+        batch.parameters = Some(RunParameters::RemoveMusic {
+            output_dir: "/tmp/out".to_string(),
+            compute_mode: "auto".to_string(),
+        });
+        batch
+    }
+
+    #[tokio::test]
+    async fn should_keep_a_batch_resumable_when_its_resume_fails() {
+        let state = AppState::new();
+        state.restore_history(vec![interrupted_batch()], Vec::new()).await;
+
+        let previous = state.get_batch("batch-1").await.unwrap();
+        state.requeue_interrupted_batch("batch-1").await.unwrap();
+        state.restore_interrupted_batch(previous).await;
+
+        assert_eq!(state.get_batch("batch-1").await.unwrap(), interrupted_batch());
+        assert!(state.take_batch_started_at("batch-1").await.is_none());
+        assert!(state.requeue_interrupted_batch("batch-1").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_count_jobs_completed_before_the_interruption_in_the_summary() {
+        let state = AppState::new();
+        state.restore_history(vec![interrupted_batch()], Vec::new()).await;
+        state.requeue_interrupted_batch("batch-1").await.unwrap();
+
+        state
+            .apply_worker_event(&WorkerEvent::BatchDone {
+                batch_id: "batch-1".to_string(),
+                summary: BatchSummary {
+                    ok: 1,
+                    failed: 0,
+                    cancelled: 0,
+                    interrupted: 0,
+                },
+            })
+            .await;
+
+        let batch = state.get_batch("batch-1").await.unwrap();
+        assert_eq!(batch.status, BatchStatus::Completed);
+        assert_eq!(batch.summary.unwrap().ok, 2);
+    }
+
+    #[tokio::test]
+    async fn should_count_jobs_completed_before_a_resume_once_when_the_worker_dies() {
+        let state = AppState::new();
+        state.restore_history(vec![interrupted_batch()], Vec::new()).await;
+        state.requeue_interrupted_batch("batch-1").await.unwrap();
+
+        let events = state.orphaned_work_events("Worker process exited unexpectedly").await;
+        for event in &events {
+            state.apply_worker_event(event).await;
         }
+
+        let batch = state.get_batch("batch-1").await.unwrap();
+        assert_eq!(batch.status, BatchStatus::Interrupted);
+        let summary = batch.summary.unwrap();
+        assert_eq!(summary.ok, 1);
+        assert_eq!(summary.interrupted, 1);
+    }
+
+    #[tokio::test]
+    async fn should_refuse_to_requeue_tasks_that_were_not_interrupted() {
+        let state = AppState::new();
+        let mut task = seed_task();
+        task.parameters = Some(RunParameters::Transcription {
+            yap_mode: "auto".to_string(),
+        });
+        state.insert_task(task).await;
+        assert!(state.requeue_interrupted_task("task-1").await.is_err());
+
+        let mut task = seed_task();
+        task.task_id = "task-2".to_string();
+        task.status = crate::types::TaskStatus::Interrupted;
+        state.restore_history(Vec::new(), vec![task]).await;
+        let error = state
+            .requeue_interrupted_task("task-2")
+            .await
+            .expect_err("tasks without parameters cannot be resumed");
+        assert!(error.contains("original settings"));
+    }
+
+    #[test]
+    fn should_select_failed_jobs_for_retry() {
+        let jobs = [
+            ("job-a", "/tmp/a.srt", false),
+            ("job-b", "/tmp/b.srt", true),
+            ("job-c", "/tmp/c.srt", true),
+        ];
+
+        assert_eq!(
+            select_retry_inputs("Task t", &jobs, None).unwrap(),
+            vec!["/tmp/b.srt".to_string(), "/tmp/c.srt".to_string()]
+        );
+        assert_eq!(
+            select_retry_inputs("Task t", &jobs, Some(&["job-c".to_string()])).unwrap(),
+            vec!["/tmp/c.srt".to_string()]
+        );
+        assert!(select_retry_inputs("Task t", &jobs, Some(&["job-a".to_string()])).is_err());
+        assert!(select_retry_inputs("Task t", &jobs, Some(&["job-z".to_string()])).is_err());
+        assert!(select_retry_inputs("Task t", &jobs[..1], None).is_err());
+    }
+
+    #[tokio::test]
+    async fn should_refuse_to_retry_active_batches() {
+        let state = AppState::new();
+        let mut batch = seed_batch();
+        batch.parameters = Some(RunParameters::RemoveMusic {
+            output_dir: "/tmp/out".to_string(),
+            compute_mode: "auto".to_string(),
+        });
+        state.insert_batch(batch).await;
+
+        let error = state.batch_retry_inputs("batch-1", None).await.unwrap_err();
+        assert!(error.contains("still running"));
+    }
+
+    #[tokio::test]
+    async fn should_mark_history_dirty_on_changes() {
+        let state = AppState::new();
+        assert!(!state.take_history_dirty());
+
+        state.insert_batch(seed_batch()).await;
+        assert!(state.take_history_dirty());
+        assert!(!state.take_history_dirty());
+
+        let progress = |progress_pct| WorkerEvent::JobProgress {
+            batch_id: Some("batch-1".to_string()),
+            task_id: None,
+            task_kind: None,
+            job_id: "job-a".to_string(),
+            progress_pct,
+        };
+        state.apply_worker_event(&progress(10.0)).await;
+        assert!(state.take_history_dirty());
+        state.apply_worker_event(&progress(20.0)).await;
+        assert!(!state.take_history_dirty());
+        assert!(state.take_history_progress_dirty());
+    }
+
+    #[tokio::test]
+    async fn should_track_paused_batches_and_keep_them_active() {
+        let state = AppState::new();
+        state.insert_batch(seed_batch()).await;
+        state
+            .apply_worker_event(&WorkerEvent::JobDone {
+                batch_id: Some("batch-1".to_string()),
+                task_id: None,
+                task_kind: None,
+                job_id: "job-a".to_string(),
+                output_path: Some("/tmp/out/a.mov".to_string()),
+                artifacts: None,
+            })
+            .await;
+        state
+            .apply_worker_event(&WorkerEvent::BatchPaused {
+                batch_id: "batch-1".to_string(),
+            })
+            .await;
+
+        let batch = state.get_batch("batch-1").await.unwrap();
+        assert_eq!(batch.status, BatchStatus::Paused);
+        assert!(batch.status.is_active());
+        assert_eq!(batch.jobs[1].status, JobStatus::Queued);
+
+        state
+            .apply_worker_event(&WorkerEvent::BatchResumed {
+                batch_id: "batch-1".to_string(),
+            })
+            .await;
+        let batch = state.get_batch("batch-1").await.unwrap();
+        assert_eq!(batch.status, BatchStatus::Running);
+    }
+
+    #[tokio::test]
+    async fn should_not_report_paused_task_jobs_as_running() {
+        let state = AppState::new();
+        let mut task = seed_task();
+        task.status = crate::types::TaskStatus::Paused;
+        task.jobs[0].status = TaskJobStatus::Running;
+        state.insert_task(task).await;
+
+        assert!(state.running_job_labels().await.is_empty());
     }
 
     #[tokio::test]
@@ -394,6 +1395,7 @@ mod tests {
                 summary: TaskSummary {
                     cancelled: 0,
                     failed: 0,
+                    interrupted: 0,
                     ok: 1,
                 },
             })
@@ -415,6 +1417,248 @@ mod tests {
         assert!(state.take_task_started_at("task-1").await.is_some());
     }
 
+    #[tokio::test]
+    async fn should_forget_a_removed_batch() {
+        let state = AppState::new();
+        state.insert_batch(seed_batch()).await;
+
+        state.remove_batch("batch-1").await;
+
+        assert!(state.get_batch("batch-1").await.is_none());
+        assert!(state.take_batch_started_at("batch-1").await.is_none());
+        assert!(state.history_snapshot().await.0.is_empty());
+    }
+
+    #[tokio::test]
+    async fn should_forget_a_removed_task() {
+        let state = AppState::new();
+        state.insert_task(seed_task()).await;
+
+        state.remove_task("task-1").await;
+
+        assert!(state.get_task("task-1").await.is_none());
+        assert!(state.take_task_started_at("task-1").await.is_none());
+        assert!(state.history_snapshot().await.1.is_empty());
+    }
+
+    #[tokio::test]
+    async fn should_close_out_orphaned_work_when_the_worker_dies() {
+        let state = AppState::new();
+        let mut batch = seed_batch();
+        batch.parameters = Some(RunParameters::RemoveMusic {
+            output_dir: "/tmp/out".to_string(),
+            compute_mode: "auto".to_string(),
+        });
+        state.insert_batch(batch).await;
+        state.insert_task(seed_task()).await;
+
+        state
+            .apply_worker_event(&WorkerEvent::JobProgress {
+                batch_id: Some("batch-1".to_string()),
+                task_id: None,
+                task_kind: None,
+                job_id: "job-a".to_string(),
+                progress_pct: 50.0,
+            })
+            .await;
+        state
+            .apply_worker_event(&WorkerEvent::JobProgress {
+                batch_id: None,
+                task_id: Some("task-1".to_string()),
+                task_kind: Some("transcription".to_string()),
+                job_id: "job-a".to_string(),
+                progress_pct: 10.0,
+            })
+            .await;
+
+        let events = state.orphaned_work_events("Worker process exited unexpectedly").await;
+        for event in &events {
+            state.apply_worker_event(event).await;
+        }
+
+        let batch = state.get_batch("batch-1").await.unwrap();
+        assert_eq!(batch.status, BatchStatus::Interrupted);
+        assert_eq!(batch.jobs[0].status, JobStatus::Interrupted);
+        assert_eq!(
+            batch.jobs[0].error.as_deref(),
+            Some("Worker process exited unexpectedly")
+        );
+        assert_eq!(batch.jobs[1].status, JobStatus::Interrupted);
+        let summary = batch.summary.unwrap();
+        assert_eq!(
+            (summary.ok, summary.failed, summary.cancelled, summary.interrupted),
+            (0, 0, 0, 2)
+        );
+
+        let task = state.get_task("task-1").await.unwrap();
+        assert_eq!(task.status, crate::types::TaskStatus::Interrupted);
+        assert_eq!(task.jobs[0].status, TaskJobStatus::Interrupted);
+        assert_eq!(task.jobs[0].error.as_deref(), Some("Worker process exited unexpectedly"));
+        assert_eq!(task.summary.unwrap().interrupted, 1);
+
+        assert!(state.orphaned_work_events("again").await.is_empty());
+        let (input_paths, _) = state
+            .requeue_interrupted_batch("batch-1")
+            .await
+            .expect("a batch the worker died on should be resumable");
+        assert_eq!(input_paths.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn should_count_a_job_interrupted_mid_run_as_interrupted() {
+        let state = AppState::new();
+        let mut batch = seed_batch();
+        batch.jobs.truncate(1);
+        state.insert_batch(batch).await;
+        state.insert_task(seed_task()).await;
+
+        for (batch_id, task_id, task_kind) in [
+            (Some("batch-1".to_string()), None, None),
+            (None, Some("task-1".to_string()), Some("transcription".to_string())),
+        ] {
+            state
+                .apply_worker_event(&WorkerEvent::JobProgress {
+                    batch_id: batch_id.clone(),
+                    task_id: task_id.clone(),
+                    task_kind: task_kind.clone(),
+                    job_id: "job-a".to_string(),
+                    progress_pct: 40.0,
+                })
+                .await;
+            state
+                .apply_worker_event(&WorkerEvent::JobInterrupted {
+                    batch_id,
+                    task_id,
+                    task_kind,
+                    job_id: "job-a".to_string(),
+                    error: "Worker stopped responding".to_string(),
+                })
+                .await;
+        }
+
+        let events = state.orphaned_work_events("Worker process exited unexpectedly").await;
+        for event in &events {
+            state.apply_worker_event(event).await;
+        }
+
+        let batch_summary = state.get_batch("batch-1").await.unwrap().summary.unwrap();
+        assert_eq!((batch_summary.interrupted, batch_summary.failed), (1, 0));
+        let task_summary = state.get_task("task-1").await.unwrap().summary.unwrap();
+        assert_eq!((task_summary.interrupted, task_summary.failed), (1, 0));
+    }
+
+    #[tokio::test]
+    async fn should_keep_hard_cancelled_jobs_cancelled_and_count_them() {
+        let state = AppState::new();
+        state.insert_batch(seed_batch()).await;
+        state
+            .apply_worker_event(&WorkerEvent::JobProgress {
+                batch_id: Some("batch-1".to_string()),
+                task_id: None,
+                task_kind: None,
+                job_id: "job-a".to_string(),
+                progress_pct: 50.0,
+            })
+            .await;
+        state
+            .apply_worker_event(&WorkerEvent::JobCancelled {
+                batch_id: Some("batch-1".to_string()),
+                task_id: None,
+                task_kind: None,
+                job_id: "job-a".to_string(),
+            })
+            .await;
+
+        let events = state.orphaned_work_events("Worker process exited unexpectedly").await;
+        for event in &events {
+            state.apply_worker_event(event).await;
+        }
+
+        let batch = state.get_batch("batch-1").await.unwrap();
+        assert_eq!(batch.status, BatchStatus::Interrupted);
+        assert_eq!(batch.jobs[0].status, JobStatus::Cancelled);
+        assert!(batch.jobs[0].error.is_none());
+        assert_eq!(batch.jobs[1].status, JobStatus::Interrupted);
+        let summary = batch.summary.unwrap();
+        assert_eq!(
+            (summary.ok, summary.failed, summary.cancelled, summary.interrupted),
+            (0, 0, 1, 1)
+        );
+    }
+
+    #[tokio::test]
+    async fn should_leave_native_tasks_alone_when_the_worker_dies() {
+        let state = AppState::new();
+        state.insert_task(seed_task()).await;
+        state.register_native_task("task-1").await;
+        state
+            .apply_worker_event(&WorkerEvent::JobProgress {
+                batch_id: None,
+                task_id: Some("task-1".to_string()),
+                task_kind: Some("transcription".to_string()),
+                job_id: "job-a".to_string(),
+                progress_pct: 50.0,
+            })
+            .await;
+
+        assert!(state.running_job_labels().await.is_empty());
+        assert!(state.orphaned_work_events("Worker process exited unexpectedly").await.is_empty());
+
+        state.finish_native_task("task-1").await;
+        assert_eq!(state.running_job_labels().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn should_count_worker_restart_attempts_until_reset() {
+        let state = AppState::new();
+        assert_eq!(state.next_worker_restart_attempt().await, 1);
+        assert_eq!(state.next_worker_restart_attempt().await, 2);
+
+        state.reset_worker_restart_attempts().await;
+        assert_eq!(state.next_worker_restart_attempt().await, 1);
+    }
+
+    #[test]
+    fn should_refuse_commands_the_worker_did_not_advertise() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let sender = WorkerSender::new(tx, "0.1.0".to_string(), vec!["cancel_task".to_string()]);
+
+        let error = sender
+            .send(WorkerCommand::Ping {
+                ping_id: "ping-1".to_string(),
+            })
+            .unwrap_err();
+        assert!(error.contains("`ping`"));
+        assert!(rx.try_recv().is_err());
+
+        sender
+            .send(WorkerCommand::CancelTask {
+                task_id: "task-1".to_string(),
+                mode: "stop_after_current".to_string(),
+            })
+            .unwrap();
+        assert!(matches!(rx.try_recv(), Ok(WorkerCommand::CancelTask { .. })));
+    }
+
+    #[test]
+    fn should_refuse_command_options_the_worker_did_not_advertise() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let sender = WorkerSender::new(tx, "0.1.0".to_string(), vec!["start_cut_job".to_string()]);
+        let cut_job = |output_mode: &str| WorkerCommand::StartCutJob {
+            task_id: "task-1".to_string(),
+            video_path: "/tmp/a.mp4".to_string(),
+            ranges: Vec::new(),
+            output_mode: output_mode.to_string(),
+        };
+
+        let error = sender.send(cut_job("bleep")).unwrap_err();
+        assert!(error.contains("`audio_redaction`"));
+        assert!(rx.try_recv().is_err());
+
+        sender.send(cut_job("video_cleaned_default")).unwrap();
+        assert!(matches!(rx.try_recv(), Ok(WorkerCommand::StartCutJob { .. })));
+    }
+
     #[test]
     fn should_cap_task_job_logs_to_recent_entries() {
         let mut logs = Vec::new();
@@ -0,0 +1,306 @@
+use std::path::Path;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::srt::{parse_srt, serialize_srt, SubtitleCue};
+
+pub const SUBTITLE_EXTENSIONS: [&str; 4] = ["srt", "vtt", "ass", "ssa"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubtitleFormat {
+    Srt,
+    Vtt,
+    Ass,
+}
+
+impl SubtitleFormat {
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.trim_start_matches('.').to_ascii_lowercase().as_str() {
+            "srt" => Some(Self::Srt),
+            "vtt" => Some(Self::Vtt),
+            "ass" | "ssa" => Some(Self::Ass),
+            _ => None,
+        }
+    }
+
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()
+            .and_then(|value| value.to_str())
+            .and_then(Self::from_extension)
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Srt => "srt",
+            Self::Vtt => "vtt",
+            Self::Ass => "ass",
+        }
+    }
+}
+
+/// WebVTT cue tags (`<b>`, `<i>`, `<u>`, `<c.class>`, `<v Speaker>`,
+/// `<lang en>`, `<ruby>`, `<rt>`) and inline timestamps like `<00:01.500>`.
+static VTT_TAG: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"</?(?:b|i|u|c|v|lang|ruby|rt)(?:[.\s][^<>]*)?>|<(?:\d+:)?\d{2}:\d{2}\.\d{3}>")
+        .expect("WebVTT tag pattern should compile")
+});
+
+/// Drops ASS `{\i1}` override blocks between `open` and `close`.
+fn strip_markup(text: &str, open: char, close: char) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut depth = 0_usize;
+    for character in text.chars() {
+        if character == open {
+            depth += 1;
+        } else if character == close && depth > 0 {
+            depth -= 1;
+        } else if depth == 0 {
+            stripped.push(character);
+        }
+    }
+    stripped
+}
+
+/// Strips recognized cue tags only, so a literal `<` in the text survives, and
+/// decodes the escapes WebVTT requires for `<`, `>` and `&`.
+fn vtt_text_to_plain(text: &str) -> String {
+    VTT_TAG
+        .replace_all(text, "")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+        .trim()
+        .to_string()
+}
+
+fn plain_text_to_vtt(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// WebVTT cues share SRT's block layout, so the header, `NOTE`/`STYLE` blocks
+/// and cue identifiers fall out of the tolerant SRT parser; only the inline
+/// markup needs stripping.
+pub fn parse_vtt(content: &str) -> Vec<SubtitleCue> {
+    parse_srt(content)
+        .into_iter()
+        .enumerate()
+        .map(|(position, cue)| SubtitleCue {
+            index: position as u32 + 1,
+            text: vtt_text_to_plain(&cue.text),
+            ..cue
+        })
+        .collect()
+}
+
+fn format_vtt_timestamp(seconds: f64) -> String {
+    crate::srt::format_timestamp(seconds).replace(',', ".")
+}
+
+pub fn serialize_vtt(cues: &[SubtitleCue]) -> String {
+    let body = cues
+        .iter()
+        .map(|cue| {
+            format!(
+                "{} --> {}\n{}\n",
+                format_vtt_timestamp(cue.start_time),
+                format_vtt_timestamp(cue.end_time),
+                plain_text_to_vtt(&cue.text)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!("WEBVTT\n\n{body}")
+}
+
+fn ass_text_to_plain(text: &str) -> String {
+    strip_markup(text, '{', '}')
+        .replace("\\N", "\n")
+        .replace("\\n", "\n")
+        .replace("\\h", " ")
+        .trim()
+        .to_string()
+}
+
+/// Reads `Dialogue:` lines from the `[Events]` section using its `Format:`
+/// line to locate the start, end and text columns.
+pub fn parse_ass(content: &str) -> Vec<SubtitleCue> {
+    let normalized = content
+        .trim_start_matches('\u{feff}')
+        .replace("\r\n", "\n")
+        .replace('\r', "\n");
+
+    let mut in_events = false;
+    let mut fields: Vec<String> = Vec::new();
+    let mut cues = Vec::new();
+    for line in normalized.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_events = line.eq_ignore_ascii_case("[events]");
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        if !in_events {
+            continue;
+        }
+
+        if key.trim().eq_ignore_ascii_case("format") {
+            fields = value.split(',').map(|field| field.trim().to_ascii_lowercase()).collect();
+            continue;
+        }
+        if !key.trim().eq_ignore_ascii_case("dialogue") || fields.is_empty() {
+            continue;
+        }
+
+        let values = value.splitn(fields.len(), ',').collect::<Vec<_>>();
+        if values.len() != fields.len() {
+            continue;
+        }
+        let column = |name: &str| {
+            fields
+                .iter()
+                .position(|field| field == name)
+                .map(|position| values[position])
+        };
+        let (Some(start), Some(end), Some(text)) = (column("start"), column("end"), column("text")) else {
+            continue;
+        };
+        let Some((start_time, end_time)) = crate::srt::parse_time_range(start, end) else {
+            continue;
+        };
+
+        cues.push(SubtitleCue {
+            index: cues.len() as u32 + 1,
+            start_time,
+            end_time,
+            text: ass_text_to_plain(text),
+        });
+    }
+
+    cues
+}
+
+fn format_ass_timestamp(seconds: f64) -> String {
+    let total_centis = (seconds.max(0.0) * 100.0).round() as u64;
+    let hours = total_centis / 360_000;
+    let minutes = (total_centis % 360_000) / 6000;
+    let whole_seconds = (total_centis % 6000) / 100;
+    let centis = total_centis % 100;
+    format!("{hours}:{minutes:02}:{whole_seconds:02}.{centis:02}")
+}
+
+pub fn serialize_ass(cues: &[SubtitleCue]) -> String {
+    let mut content = String::from(
+        "[Script Info]\nScriptType: v4.00+\n\n\
+         [V4+ Styles]\n\
+         Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, OutlineColour, BackColour, Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, Angle, BorderStyle, Outline, Shadow, Alignment, MarginL, MarginR, MarginV, Encoding\n\
+         Style: Default,Arial,20,&H00FFFFFF,&H000000FF,&H00000000,&H00000000,0,0,0,0,100,100,0,0,1,2,0,2,10,10,10,1\n\n\
+         [Events]\n\
+         Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text\n",
+    );
+    for cue in cues {
+        content.push_str(&format!(
+            "Dialogue: 0,{},{},Default,,0,0,0,,{}\n",
+            format_ass_timestamp(cue.start_time),
+            format_ass_timestamp(cue.end_time),
+            cue.text.replace('\n', "\\N")
+        ));
+    }
+    content
+}
+
+pub fn parse_subtitles(content: &str, format: SubtitleFormat) -> Vec<SubtitleCue> {
+    match format {
+        SubtitleFormat::Srt => parse_srt(content),
+        SubtitleFormat::Vtt => parse_vtt(content),
+        SubtitleFormat::Ass => parse_ass(content),
+    }
+}
+
+pub fn serialize_subtitles(cues: &[SubtitleCue], format: SubtitleFormat) -> String {
+    match format {
+        SubtitleFormat::Srt => serialize_srt(cues),
+        SubtitleFormat::Vtt => serialize_vtt(cues),
+        SubtitleFormat::Ass => serialize_ass(cues),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::srt::{parse_srt, serialize_srt, SubtitleCue};
+
+    use super::{parse_ass, parse_vtt, serialize_ass, serialize_vtt, SubtitleFormat};
+
+    fn cue(index: u32, start_time: f64, end_time: f64, text: &str) -> SubtitleCue {
+        SubtitleCue {
+            index,
+            start_time,
+            end_time,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn should_parse_vtt_with_header_notes_and_markup() {
+        let content = "WEBVTT - downloaded\n\nNOTE fetched from the channel\n\nintro\n00:01.000 --> 00:02.500 line:0\n<v Host>Hello</v> there\n\n01:00:00.000 --> 01:00:01.000\n<i>Bye</i>\n";
+
+        assert_eq!(
+            parse_vtt(content),
+            vec![cue(1, 1.0, 2.5, "Hello there"), cue(2, 3600.0, 3601.0, "Bye")]
+        );
+    }
+
+    #[test]
+    fn should_split_vtt_cues_on_whitespace_only_lines() {
+        let content = "WEBVTT\n \n00:01.000 --> 00:02.000\nFirst\n\t \n00:03.000 --> 00:04.000\nSecond\n";
+
+        assert_eq!(parse_vtt(content), vec![cue(1, 1.0, 2.0, "First"), cue(2, 3.0, 4.0, "Second")]);
+    }
+
+    #[test]
+    fn should_keep_literal_angle_brackets_and_decode_entities_in_vtt() {
+        let content = "WEBVTT\n\n00:01.000 --> 00:02.000\n<c.yellow>1 < 2</c> &amp; 3 &gt; 2 <00:01.500>done\n";
+
+        assert_eq!(parse_vtt(content), vec![cue(1, 1.0, 2.0, "1 < 2 & 3 > 2 done")]);
+    }
+
+    #[test]
+    fn should_round_trip_special_characters_through_vtt() {
+        let cues = vec![cue(1, 1.0, 2.0, "a <b> & c")];
+
+        assert_eq!(parse_vtt(&serialize_vtt(&cues)), cues);
+    }
+
+    #[test]
+    fn should_parse_ass_dialogue_lines() {
+        let content = "[Script Info]\nTitle: Episode\n\n[Events]\nFormat: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text\nComment: 0,0:00:00.00,0:00:01.00,Default,,0,0,0,,ignored\nDialogue: 0,0:00:01.50,0:00:03.00,Default,,0,0,0,,{\\i1}Hello{\\i0}, there\\Nfriend\nDialogue: 0,1:00:00.00,1:00:01.25,Default,,0,0,0,,Bye\n";
+
+        assert_eq!(
+            parse_ass(content),
+            vec![cue(1, 1.5, 3.0, "Hello, there\nfriend"), cue(2, 3600.0, 3601.25, "Bye")]
+        );
+    }
+
+    #[test]
+    fn should_convert_between_srt_vtt_and_ass() {
+        let cues = vec![cue(1, 1.5, 3.0, "Hello\nthere"), cue(2, 62.25, 63.0, "Bye")];
+
+        let vtt = serialize_vtt(&cues);
+        let ass = serialize_ass(&parse_vtt(&vtt));
+
+        assert!(vtt.starts_with("WEBVTT\n\n00:00:01.500 --> 00:00:03.000"));
+        assert!(ass.contains("Dialogue: 0,0:01:02.25,0:01:03.00,Default,,0,0,0,,Bye"));
+        assert_eq!(parse_srt(&serialize_srt(&parse_ass(&ass))), cues);
+    }
+
+    #[test]
+    fn should_detect_subtitle_formats_from_extensions() {
+        assert_eq!(SubtitleFormat::from_path(Path::new("a/ep.VTT")), Some(SubtitleFormat::Vtt));
+        assert_eq!(SubtitleFormat::from_path(Path::new("ep.ssa")), Some(SubtitleFormat::Ass));
+        assert_eq!(SubtitleFormat::from_extension(".srt"), Some(SubtitleFormat::Srt));
+        assert_eq!(SubtitleFormat::from_path(Path::new("ep.analysis.json")), None);
+    }
+}
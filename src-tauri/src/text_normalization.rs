@@ -0,0 +1,179 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::types::TextNormalization;
+
+static LETTER: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\p{L}$").expect("letter class should compile"));
+static LETTER_OR_NUMBER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[\p{L}\p{N}]$").expect("letter or number class should compile"));
+
+/// Python's `str.isalpha()`, which unlike `char::is_alphabetic` leaves out
+/// combining marks such as harakat.
+pub fn is_python_alphabetic(character: char) -> bool {
+    LETTER.is_match(character.encode_utf8(&mut [0; 4]))
+}
+
+/// Python's `str.isalnum()`, and so what its `\w` matches besides `_`.
+pub fn is_python_alphanumeric(character: char) -> bool {
+    LETTER_OR_NUMBER.is_match(character.encode_utf8(&mut [0; 4]))
+}
+
+fn is_diacritic(character: char) -> bool {
+    matches!(
+        character,
+        // Tanween, harakat, shadda, sukun and other Arabic combining marks.
+        '\u{064B}'..='\u{065F}'
+            // Superscript alef.
+            | '\u{0670}'
+            // Quranic annotation marks.
+            | '\u{06D6}'..='\u{06ED}'
+            // Tatweel.
+            | '\u{0640}'
+            // Combining diacritical marks of decomposed Latin text.
+            | '\u{0300}'..='\u{036F}'
+    )
+}
+
+fn unify_arabic_letter(character: char) -> char {
+    match character {
+        // Alef with hamza above or below, alef with madda, alef wasla.
+        '\u{0623}' | '\u{0625}' | '\u{0622}' | '\u{0671}' => '\u{0627}',
+        // Alef maqsura, Farsi/Urdu yeh, yeh with hamza above.
+        '\u{0649}' | '\u{06CC}' | '\u{0626}' => '\u{064A}',
+        // Taa marbuta, Urdu taa marbuta goal, Urdu heh goal.
+        '\u{0629}' | '\u{06C3}' | '\u{06C1}' => '\u{0647}',
+        // Keheh.
+        '\u{06A9}' => '\u{0643}',
+        other => other,
+    }
+}
+
+fn fold_digit(character: char) -> char {
+    let zero = match character {
+        '\u{0660}'..='\u{0669}' => 0x0660,
+        '\u{06F0}'..='\u{06F9}' => 0x06F0,
+        _ => return character,
+    };
+    char::from_digit(u32::from(character) - zero, 10).unwrap_or(character)
+}
+
+fn fold_leetspeak(character: char) -> char {
+    match character {
+        '0' => 'o',
+        '1' => 'i',
+        '3' => 'e',
+        '4' | '@' => 'a',
+        '5' | '$' => 's',
+        '7' => 't',
+        other => other,
+    }
+}
+
+/// Normalizes `text` character by character, returning for each byte of the
+/// result the index of the character in `text` it came from, so matches in
+/// the normalized text can be located in the original.
+pub fn normalize_with_origins(text: &str, options: &TextNormalization) -> (String, Vec<usize>) {
+    let characters = text.chars().collect::<Vec<_>>();
+    let mut normalized = String::with_capacity(text.len());
+    let mut origins = Vec::with_capacity(text.len());
+
+    let mut word_start = 0;
+    while word_start < characters.len() {
+        let word_end = characters[word_start..]
+            .iter()
+            .position(|character| character.is_whitespace())
+            .map_or(characters.len(), |length| word_start + length.max(1));
+        let word = &characters[word_start..word_end];
+        let fold_leet_in_word = options.fold_leetspeak && word.iter().any(|character| is_python_alphabetic(*character));
+
+        for (offset, &character) in word.iter().enumerate() {
+            if options.strip_diacritics && is_diacritic(character) {
+                continue;
+            }
+            let mut character = character;
+            if options.fold_digits {
+                character = fold_digit(character);
+            }
+            if options.unify_arabic_letters {
+                character = unify_arabic_letter(character);
+            }
+            if fold_leet_in_word {
+                character = fold_leetspeak(character);
+            }
+            if options.fold_case {
+                normalized.extend(character.to_lowercase());
+            } else {
+                normalized.push(character);
+            }
+            origins.resize(normalized.len(), word_start + offset);
+        }
+        word_start = word_end;
+    }
+
+    (normalized, origins)
+}
+
+pub fn normalize_text(text: &str, options: &TextNormalization) -> String {
+    normalize_with_origins(text, options).0
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::TextNormalization;
+
+    use super::{normalize_text, normalize_with_origins};
+
+    fn all_enabled() -> TextNormalization {
+        TextNormalization {
+            fold_case: true,
+            strip_diacritics: true,
+            unify_arabic_letters: true,
+            fold_digits: true,
+            fold_leetspeak: true,
+        }
+    }
+
+    #[test]
+    fn should_not_fold_leetspeak_in_words_whose_only_letters_are_marks() {
+        let options = TextNormalization {
+            fold_leetspeak: true,
+            ..TextNormalization::default()
+        };
+
+        assert_eq!(normalize_text("3\u{0650}", &options), "3\u{0650}");
+        assert_eq!(normalize_text("3\u{0628}", &options), "e\u{0628}");
+    }
+
+    #[test]
+    fn should_strip_tashkeel_and_unify_arabic_letters() {
+        // "إِلَٰهٌ" and "مدرسة" normalize to "اله" and "مدرسه".
+        let normalized = normalize_text("\u{0625}\u{0650}\u{0644}\u{064E}\u{0670}\u{0647}\u{064C} \u{0645}\u{062F}\u{0631}\u{0633}\u{0629}", &all_enabled());
+
+        assert_eq!(normalized, "\u{0627}\u{0644}\u{0647} \u{0645}\u{062F}\u{0631}\u{0633}\u{0647}");
+    }
+
+    #[test]
+    fn should_fold_digits_and_leetspeak_only_inside_words() {
+        let options = all_enabled();
+
+        assert_eq!(normalize_text("St00pid 2024 \u{0663}\u{0660}", &options), "stoopid 2024 30");
+        assert_eq!(normalize_text("a$$ h3ll", &options), "ass hell");
+    }
+
+    #[test]
+    fn should_only_lowercase_by_default() {
+        let text = "St00pid \u{0625}\u{0650}\u{0644}\u{0647}";
+
+        assert_eq!(normalize_text(text, &TextNormalization::default()), "st00pid \u{0625}\u{0650}\u{0644}\u{0647}");
+    }
+
+    #[test]
+    fn should_map_normalized_bytes_back_to_characters() {
+        let (normalized, origins) = normalize_with_origins("\u{0628}\u{064E}\u{0627}\u{0628} X", &all_enabled());
+
+        assert_eq!(normalized, "\u{0628}\u{0627}\u{0628} x");
+        let character_origins = normalized.char_indices().map(|(offset, _)| origins[offset]).collect::<Vec<_>>();
+        assert_eq!(character_origins, vec![0, 2, 3, 4, 5]);
+    }
+}
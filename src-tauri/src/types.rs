@@ -1,5 +1,9 @@
 use serde::{Deserialize, Serialize};
 
+use crate::analysis::{AnalysisDocument, FlagPriority, MergeMode, ReviewDecision};
+use crate::profiles::ProfileRef;
+use crate::rule_packs::RuleMergeStrategy;
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StartBatchRequest {
@@ -27,10 +31,13 @@ pub struct ListVideosRequest {
 pub struct VideoListItem {
     pub file_name: String,
     pub path: String,
+    /// The video's subtitle sidecar: `.srt`, else `.vtt`, `.ass` or `.ssa`.
     pub srt_path: Option<String>,
     pub analysis_path: Option<String>,
     pub has_srt: bool,
     pub has_analysis: bool,
+    /// Per-engine results such as `episode.analysis.gemini.json`.
+    pub engine_analysis_paths: Vec<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -50,6 +57,30 @@ pub struct StartFlagBatchRequest {
     pub allowed_extensions: Option<Vec<String>>,
     pub engine: Option<String>,
     pub analysis_strategy: Option<String>,
+    /// A moderation profile id; the default profile when omitted.
+    pub profile: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveModerationProfileRequest {
+    pub id: String,
+    pub settings: ModerationSettings,
+}
+
+/// Starts from the default moderation settings when `settings` is omitted.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateModerationProfileRequest {
+    pub name: String,
+    pub settings: Option<ModerationSettings>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CloneModerationProfileRequest {
+    pub source_id: String,
+    pub name: String,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -58,6 +89,100 @@ pub struct ListSrtFilesRequest {
     pub input_dir: String,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeAnalysesRequest {
+    /// Per-engine sidecars of one video, e.g. `episode.analysis.gemini.json`.
+    pub analysis_paths: Vec<String>,
+    pub mode: MergeMode,
+    /// Also saves the merged analysis here, e.g. the video's `.analysis.json`
+    /// so cut planning uses it.
+    pub output_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffAnalysesRequest {
+    pub analysis_paths: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WriteAnalysisFileRequest {
+    pub path: String,
+    pub document: AnalysisDocument,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewFlaggedItemRequest {
+    pub analysis_path: String,
+    /// Position in the sidecar's `flagged` list.
+    pub index: usize,
+    /// `None` clears an earlier review.
+    pub decision: Option<ReviewDecision>,
+    pub start_time: Option<f64>,
+    pub end_time: Option<f64>,
+    pub priority: Option<FlagPriority>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConvertSubtitleRequest {
+    pub path: String,
+    /// `srt`, `vtt` or `ass`; the converted file is written next to the source.
+    pub target_format: String,
+    #[serde(default)]
+    pub overwrite: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConvertSubtitleResponse {
+    pub output_path: String,
+    pub cue_count: usize,
+}
+
+/// Exports the default profile when `profile` is omitted.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportModerationRulesRequest {
+    pub profile: Option<String>,
+    /// A `.json` file; existing files are only replaced with `overwrite`.
+    pub output_path: String,
+    #[serde(default)]
+    pub overwrite: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportModerationRulesResponse {
+    pub output_path: String,
+    pub rule_count: usize,
+    pub profanity_word_count: usize,
+}
+
+/// Imports into the default profile when `profile` is omitted. With `dry_run`
+/// the report is computed but nothing is saved.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportModerationRulesRequest {
+    pub profile: Option<String>,
+    pub path: String,
+    pub strategy: RuleMergeStrategy,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Exactly one of `text` and `subtitle_path` is expected.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestModerationRulesRequest {
+    pub settings: ModerationSettings,
+    pub text: Option<String>,
+    pub subtitle_path: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct SrtListItem {
@@ -78,10 +203,147 @@ pub struct CutRange {
 #[serde(rename_all = "camelCase")]
 pub struct StartCutJobRequest {
     pub video_path: String,
+    /// Kept segments for `video_cleaned_default`; silenced segments for
+    /// `mute` and `bleep`, which keep the whole timeline.
     pub ranges: Vec<CutRange>,
     pub output_mode: String,
 }
 
+/// One step of a pipeline run. Every file goes through the stages in the
+/// order of `PipelineStageKind`, each stage starting from the previous output.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "stage", rename_all = "snake_case", rename_all_fields = "camelCase")]
+pub enum PipelineStage {
+    RemoveMusic {
+        #[serde(default = "default_compute_mode")]
+        compute_mode: String,
+    },
+    Transcribe {
+        #[serde(default = "default_yap_mode")]
+        yap_mode: String,
+    },
+    /// Overrides the saved moderation settings like `StartFlagBatchRequest`,
+    /// starting from the moderation profile `profile` when given.
+    Flag {
+        engine: Option<String>,
+        analysis_strategy: Option<String>,
+        profile: Option<String>,
+    },
+    /// Removes the flagged segments of the `.analysis.json` sidecar.
+    Cut {
+        #[serde(default = "default_cut_output_mode")]
+        output_mode: String,
+        #[serde(flatten)]
+        plan: CutPlanOptions,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum PipelineStageKind {
+    RemoveMusic,
+    Transcribe,
+    Flag,
+    Cut,
+}
+
+impl PipelineStage {
+    pub fn kind(&self) -> PipelineStageKind {
+        match self {
+            PipelineStage::RemoveMusic { .. } => PipelineStageKind::RemoveMusic,
+            PipelineStage::Transcribe { .. } => PipelineStageKind::Transcribe,
+            PipelineStage::Flag { .. } => PipelineStageKind::Flag,
+            PipelineStage::Cut { .. } => PipelineStageKind::Cut,
+        }
+    }
+}
+
+fn default_compute_mode() -> String {
+    "auto".to_string()
+}
+
+fn default_yap_mode() -> String {
+    "auto".to_string()
+}
+
+fn default_cut_output_mode() -> String {
+    "video_cleaned_default".to_string()
+}
+
+/// Which flagged segments of an `.analysis.json` sidecar get cut, and how much
+/// time around each one goes with it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CutPlanOptions {
+    /// `high`, `medium` or `low`; segments of this priority or higher are cut.
+    pub min_priority: String,
+    /// Only cut segments in these categories; omitted means every category.
+    pub categories: Option<Vec<String>>,
+    pub padding_before_seconds: f64,
+    pub padding_after_seconds: f64,
+}
+
+impl Default for CutPlanOptions {
+    fn default() -> Self {
+        Self {
+            min_priority: "high".to_string(),
+            categories: None,
+            padding_before_seconds: 0.0,
+            padding_after_seconds: 0.0,
+        }
+    }
+}
+
+/// One video of a cut batch with the ranges the app planned for it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PlannedCutJob {
+    pub video_path: String,
+    /// Ranges for the cut job in the batch's output mode, as in [`CutPlanResponse`].
+    pub ranges: Vec<CutRange>,
+    pub removed_ranges: Vec<CutRange>,
+    /// Why the video could not be planned; the worker fails its job with it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartCutBatchRequest {
+    pub input_dir: String,
+    pub allowed_extensions: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub plan: CutPlanOptions,
+    #[serde(default = "default_cut_output_mode")]
+    pub output_mode: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanCutsRequest {
+    pub video_path: String,
+    /// Defaults to the video's sibling `.analysis.json`.
+    pub analysis_path: Option<String>,
+    /// Probed from the video when omitted.
+    pub duration_seconds: Option<f64>,
+    #[serde(flatten)]
+    pub plan: CutPlanOptions,
+    /// Start a cut job with the planned ranges instead of only returning them.
+    #[serde(default)]
+    pub start_cut_job: bool,
+    #[serde(default = "default_cut_output_mode")]
+    pub output_mode: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartPipelineRequest {
+    pub input_dir: Option<String>,
+    pub input_paths: Option<Vec<String>>,
+    pub allowed_extensions: Option<Vec<String>>,
+    pub stages: Vec<PipelineStage>,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CancelTaskRequest {
@@ -89,6 +351,49 @@ pub struct CancelTaskRequest {
     pub mode: String,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchControlRequest {
+    pub batch_id: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskControlRequest {
+    pub task_id: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetryJobsRequest {
+    /// Batch or task id of the run to retry from.
+    pub id: String,
+    /// Jobs to retry; omitted means every failed, cancelled or interrupted job.
+    pub job_ids: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListTasksRequest {
+    pub kind: Option<AnalyticsTaskKind>,
+    pub status: Option<TaskStatus>,
+    pub since_epoch_seconds: Option<u64>,
+    pub until_epoch_seconds: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskHistoryItem {
+    /// The batch id for Remove Music runs, the task id otherwise.
+    pub id: String,
+    pub kind: AnalyticsTaskKind,
+    pub status: TaskStatus,
+    pub job_count: usize,
+    pub summary: Option<TaskSummary>,
+    pub created_at_epoch_seconds: u64,
+    pub parent_task_id: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CutJobStartedResponse {
@@ -104,6 +409,7 @@ pub enum JobStatus {
     Completed,
     Failed,
     Cancelled,
+    Interrupted,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -124,6 +430,9 @@ pub struct BatchSummary {
     pub ok: usize,
     pub failed: usize,
     pub cancelled: usize,
+    /// Jobs the worker died before finishing, which a resume runs again.
+    #[serde(default)]
+    pub interrupted: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
@@ -132,6 +441,9 @@ pub struct TaskSummary {
     pub ok: usize,
     pub failed: usize,
     pub cancelled: usize,
+    /// Jobs the worker died before finishing, which a resume runs again.
+    #[serde(default)]
+    pub interrupted: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -139,8 +451,17 @@ pub struct TaskSummary {
 pub enum BatchStatus {
     Queued,
     Running,
+    Paused,
     Completed,
     Cancelled,
+    Interrupted,
+}
+
+impl BatchStatus {
+    /// Whether the worker still owns the batch (it has not reached `batch_done`).
+    pub fn is_active(&self) -> bool {
+        matches!(self, BatchStatus::Queued | BatchStatus::Running | BatchStatus::Paused)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -149,6 +470,7 @@ pub enum TaskKind {
     Transcription,
     Flag,
     Cut,
+    Pipeline,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -159,6 +481,7 @@ pub enum TaskJobStatus {
     Completed,
     Failed,
     Cancelled,
+    Interrupted,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -166,8 +489,17 @@ pub enum TaskJobStatus {
 pub enum TaskStatus {
     Queued,
     Running,
+    Paused,
     Completed,
     Cancelled,
+    Interrupted,
+}
+
+impl TaskStatus {
+    /// Whether the worker still owns the task (it has not reached `task_done`).
+    pub fn is_active(&self) -> bool {
+        matches!(self, TaskStatus::Queued | TaskStatus::Running | TaskStatus::Paused)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -182,6 +514,9 @@ pub struct TaskJobRecord {
     pub error: Option<String>,
     pub logs: Vec<String>,
     pub artifacts: Option<serde_json::Value>,
+    /// Stage the job last entered; only set for pipeline jobs.
+    #[serde(default)]
+    pub stage: Option<PipelineStageKind>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -192,6 +527,13 @@ pub struct TaskState {
     pub status: TaskStatus,
     pub jobs: Vec<TaskJobRecord>,
     pub summary: Option<TaskSummary>,
+    #[serde(default)]
+    pub created_at_epoch_seconds: u64,
+    #[serde(default)]
+    pub parameters: Option<RunParameters>,
+    /// Set on retry runs to the batch/task whose failed jobs they re-run.
+    #[serde(default)]
+    pub parent_task_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -201,6 +543,53 @@ pub struct BatchState {
     pub status: BatchStatus,
     pub jobs: Vec<JobRecord>,
     pub summary: Option<BatchSummary>,
+    #[serde(default)]
+    pub created_at_epoch_seconds: u64,
+    #[serde(default)]
+    pub parameters: Option<RunParameters>,
+    /// Set on retry runs to the batch/task whose failed jobs they re-run.
+    #[serde(default)]
+    pub parent_task_id: Option<String>,
+}
+
+/// What a batch or task was started with, kept so an interrupted run can be
+/// resumed without asking for its inputs again.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case", rename_all_fields = "camelCase")]
+pub enum RunParameters {
+    RemoveMusic {
+        output_dir: String,
+        compute_mode: String,
+    },
+    Transcription {
+        yap_mode: String,
+    },
+    /// API keys are blanked before the settings are stored; resuming reads
+    /// them from the saved moderation settings instead.
+    Flag {
+        settings: ModerationSettings,
+        /// The moderation profile the settings came from.
+        #[serde(default)]
+        profile: Option<ProfileRef>,
+    },
+    Cut {
+        video_path: String,
+        ranges: Vec<CutRange>,
+        output_mode: String,
+    },
+    /// Ranges are planned per video from its `.analysis.json` when it runs.
+    CutBatch {
+        plan: CutPlanOptions,
+        output_mode: String,
+    },
+    /// `settings` has its API keys blanked like `Flag`.
+    Pipeline {
+        stages: Vec<PipelineStage>,
+        settings: ModerationSettings,
+        /// The moderation profile the flag stage's settings came from.
+        #[serde(default)]
+        profile: Option<ProfileRef>,
+    },
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -211,6 +600,31 @@ pub struct BatchStartedResponse {
     pub input_paths: Vec<String>,
 }
 
+/// Exactly one of `batch_id` (retrying a Remove Music batch) and `task_id` is set.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetryStartedResponse {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub batch_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<String>,
+    /// The batch or task the retried jobs came from.
+    pub parent_task_id: String,
+    pub file_count: usize,
+    pub input_paths: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CutPlanResponse {
+    /// Ranges for `start_cut_job` in the requested output mode: the kept
+    /// segments when cutting, the flagged ones when muting or bleeping.
+    pub ranges: Vec<CutRange>,
+    pub removed_ranges: Vec<CutRange>,
+    /// Set when the cut job was started.
+    pub task_id: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CancelAck {
@@ -225,6 +639,20 @@ pub struct TaskCancelAck {
     pub accepted: bool,
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchControlAck {
+    pub batch_id: String,
+    pub accepted: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskControlAck {
+    pub task_id: String,
+    pub accepted: bool,
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum WorkerStatusKind {
@@ -241,6 +669,7 @@ pub enum AnalyticsTaskKind {
     Transcription,
     Flag,
     Cut,
+    Pipeline,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -265,6 +694,7 @@ pub struct AnalyticsTotals {
     pub total_transcription_jobs: usize,
     pub total_flag_jobs: usize,
     pub total_cut_jobs: usize,
+    pub total_pipeline_jobs: usize,
     pub total_flagged_items: usize,
     pub total_files_with_flags: usize,
     pub success_count: usize,
@@ -315,11 +745,32 @@ pub enum BatchEvent {
         job_id: String,
         error: String,
     },
+    JobCancelled {
+        #[serde(rename = "batchId")]
+        batch_id: String,
+        #[serde(rename = "jobId")]
+        job_id: String,
+    },
+    JobInterrupted {
+        #[serde(rename = "batchId")]
+        batch_id: String,
+        #[serde(rename = "jobId")]
+        job_id: String,
+        error: String,
+    },
     BatchDone {
         #[serde(rename = "batchId")]
         batch_id: String,
         summary: BatchSummary,
     },
+    BatchPaused {
+        #[serde(rename = "batchId")]
+        batch_id: String,
+    },
+    BatchResumed {
+        #[serde(rename = "batchId")]
+        batch_id: String,
+    },
     JobLog {
         #[serde(rename = "batchId")]
         batch_id: String,
@@ -366,6 +817,21 @@ impl BatchEvent {
         }
     }
 
+    pub fn job_cancelled(batch_id: impl Into<String>, job_id: impl Into<String>) -> Self {
+        Self::JobCancelled {
+            batch_id: batch_id.into(),
+            job_id: job_id.into(),
+        }
+    }
+
+    pub fn job_interrupted(batch_id: impl Into<String>, job_id: impl Into<String>, error: impl Into<String>) -> Self {
+        Self::JobInterrupted {
+            batch_id: batch_id.into(),
+            job_id: job_id.into(),
+            error: error.into(),
+        }
+    }
+
     pub fn batch_done(batch_id: impl Into<String>, summary: BatchSummary) -> Self {
         Self::BatchDone {
             batch_id: batch_id.into(),
@@ -373,6 +839,18 @@ impl BatchEvent {
         }
     }
 
+    pub fn batch_paused(batch_id: impl Into<String>) -> Self {
+        Self::BatchPaused {
+            batch_id: batch_id.into(),
+        }
+    }
+
+    pub fn batch_resumed(batch_id: impl Into<String>) -> Self {
+        Self::BatchResumed {
+            batch_id: batch_id.into(),
+        }
+    }
+
     pub fn job_log(
         batch_id: impl Into<String>,
         job_id: impl Into<String>,
@@ -421,6 +899,32 @@ pub enum TaskEvent {
         job_id: String,
         error: String,
     },
+    JobCancelled {
+        #[serde(rename = "taskId")]
+        task_id: String,
+        #[serde(rename = "taskKind")]
+        task_kind: TaskKind,
+        #[serde(rename = "jobId")]
+        job_id: String,
+    },
+    JobInterrupted {
+        #[serde(rename = "taskId")]
+        task_id: String,
+        #[serde(rename = "taskKind")]
+        task_kind: TaskKind,
+        #[serde(rename = "jobId")]
+        job_id: String,
+        error: String,
+    },
+    JobStage {
+        #[serde(rename = "taskId")]
+        task_id: String,
+        #[serde(rename = "taskKind")]
+        task_kind: TaskKind,
+        #[serde(rename = "jobId")]
+        job_id: String,
+        stage: PipelineStageKind,
+    },
     TaskDone {
         #[serde(rename = "taskId")]
         task_id: String,
@@ -428,6 +932,18 @@ pub enum TaskEvent {
         task_kind: TaskKind,
         summary: TaskSummary,
     },
+    TaskPaused {
+        #[serde(rename = "taskId")]
+        task_id: String,
+        #[serde(rename = "taskKind")]
+        task_kind: TaskKind,
+    },
+    TaskResumed {
+        #[serde(rename = "taskId")]
+        task_id: String,
+        #[serde(rename = "taskKind")]
+        task_kind: TaskKind,
+    },
     JobLog {
         #[serde(rename = "taskId")]
         task_id: String,
@@ -496,6 +1012,42 @@ impl TaskEvent {
         }
     }
 
+    pub fn job_cancelled(task_id: impl Into<String>, task_kind: TaskKind, job_id: impl Into<String>) -> Self {
+        Self::JobCancelled {
+            task_id: task_id.into(),
+            task_kind,
+            job_id: job_id.into(),
+        }
+    }
+
+    pub fn job_interrupted(
+        task_id: impl Into<String>,
+        task_kind: TaskKind,
+        job_id: impl Into<String>,
+        error: impl Into<String>,
+    ) -> Self {
+        Self::JobInterrupted {
+            task_id: task_id.into(),
+            task_kind,
+            job_id: job_id.into(),
+            error: error.into(),
+        }
+    }
+
+    pub fn job_stage(
+        task_id: impl Into<String>,
+        task_kind: TaskKind,
+        job_id: impl Into<String>,
+        stage: PipelineStageKind,
+    ) -> Self {
+        Self::JobStage {
+            task_id: task_id.into(),
+            task_kind,
+            job_id: job_id.into(),
+            stage,
+        }
+    }
+
     pub fn task_done(task_id: impl Into<String>, task_kind: TaskKind, summary: TaskSummary) -> Self {
         Self::TaskDone {
             task_id: task_id.into(),
@@ -504,6 +1056,20 @@ impl TaskEvent {
         }
     }
 
+    pub fn task_paused(task_id: impl Into<String>, task_kind: TaskKind) -> Self {
+        Self::TaskPaused {
+            task_id: task_id.into(),
+            task_kind,
+        }
+    }
+
+    pub fn task_resumed(task_id: impl Into<String>, task_kind: TaskKind) -> Self {
+        Self::TaskResumed {
+            task_id: task_id.into(),
+            task_kind,
+        }
+    }
+
     pub fn job_log(
         task_id: impl Into<String>,
         task_kind: TaskKind,
@@ -521,17 +1087,74 @@ impl TaskEvent {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PatternMatchType {
+    /// Anywhere in the text, even inside a longer word.
+    #[default]
+    Substring,
+    WholeWord,
+    /// Whole words in order, separated by any run of spaces or punctuation.
+    Phrase,
+    /// A case-insensitive regular expression.
+    Regex,
+    /// Words starting with the pattern, so "kill" also matches "killed".
+    Stem,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", from = "RawRulePattern")]
+pub struct RulePattern {
+    pub value: String,
+    pub match_type: PatternMatchType,
+}
+
+impl RulePattern {
+    pub fn substring(value: &str) -> Self {
+        Self {
+            value: value.to_string(),
+            match_type: PatternMatchType::Substring,
+        }
+    }
+}
+
+/// Patterns were plain substring strings before match types existed; both
+/// shapes are accepted so older settings files and stored runs still load.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawRulePattern {
+    Legacy(String),
+    #[serde(rename_all = "camelCase")]
+    Typed {
+        value: String,
+        #[serde(default)]
+        match_type: PatternMatchType,
+    },
+}
+
+impl From<RawRulePattern> for RulePattern {
+    fn from(raw: RawRulePattern) -> Self {
+        match raw {
+            RawRulePattern::Legacy(value) => Self::substring(&value),
+            RawRulePattern::Typed { value, match_type } => Self { value, match_type },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct ModerationRule {
     pub rule_id: String,
     pub category: String,
     pub priority: String,
     pub reason: String,
-    pub patterns: Vec<String>,
+    pub patterns: Vec<RulePattern>,
+    /// Text matching any of these is never flagged by the rule.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclusions: Vec<RulePattern>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct ModerationSettings {
     #[serde(default = "default_moderation_engine")]
@@ -546,6 +1169,58 @@ pub struct ModerationSettings {
     pub priority_guidelines: String,
     pub profanity_words: Vec<String>,
     pub rules: Vec<ModerationRule>,
+    #[serde(default)]
+    pub normalization: TextNormalization,
+}
+
+/// How the blacklist engine normalizes subtitle text, rule patterns and
+/// profanity words before matching them. Regex patterns are only affected by
+/// `fold_case`, which makes them case-insensitive.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TextNormalization {
+    pub fold_case: bool,
+    /// Removes Arabic tashkeel, Quranic marks, tatweel and combining accents.
+    pub strip_diacritics: bool,
+    /// Alef forms to bare alef, alef maqsura and Farsi/Urdu yeh to yaa, taa
+    /// marbuta and Urdu heh to haa, and keheh to kaf.
+    pub unify_arabic_letters: bool,
+    /// Arabic-Indic and Extended Arabic-Indic digits to ASCII digits.
+    pub fold_digits: bool,
+    /// Look-alike digits and symbols to letters ("st00pid" reads as
+    /// "stoopid"), only in words that also contain a letter.
+    pub fold_leetspeak: bool,
+}
+
+/// Settings saved before normalization existed only lowercased text.
+impl Default for TextNormalization {
+    fn default() -> Self {
+        Self {
+            fold_case: true,
+            strip_diacritics: false,
+            unify_arabic_letters: false,
+            fold_digits: false,
+            fold_leetspeak: false,
+        }
+    }
+}
+
+#[cfg(test)]
+impl ModerationSettings {
+    /// Blacklist settings without API keys, criteria or guidelines.
+    pub fn for_tests(rules: Vec<ModerationRule>, profanity_words: &[&str]) -> Self {
+        Self {
+            engine: default_moderation_engine(),
+            analysis_strategy: default_analysis_strategy(),
+            google_api_key: String::new(),
+            amazon_nova_api_key: String::new(),
+            content_criteria: String::new(),
+            priority_guidelines: String::new(),
+            profanity_words: profanity_words.iter().map(|word| word.to_string()).collect(),
+            rules,
+            normalization: TextNormalization::default(),
+        }
+    }
 }
 
 fn default_moderation_engine() -> String {
@@ -1,22 +1,126 @@
-use std::{env, path::PathBuf, process::Stdio};
+use std::{
+    env,
+    future::Future,
+    path::{Path, PathBuf},
+    pin::Pin,
+    process::Stdio,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
 
 use tauri::{AppHandle, Emitter};
 use tokio::{
     io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
     process::Command,
-    sync::mpsc,
+    sync::{mpsc, oneshot},
 };
 
 use crate::{
+    analysis::carry_over_reviews_from,
     analytics,
-    protocol::{parse_worker_event, to_frontend_batch_event, to_frontend_task_event, WorkerCommand},
-    runtime::ensure_runtime_ready,
+    cut_planning::plan_cut_job,
+    protocol::{
+        ensure_compatible_worker, parse_worker_event, to_frontend_batch_event,
+        to_frontend_task_event, WorkerCommand, WorkerEvent,
+    },
+    runtime::{ensure_runtime_ready, resolve_ffmpeg_executable},
     state::AppState,
-    types::{BatchEvent, TaskEvent, WorkerStatusKind},
+    types::{
+        BatchEvent, PipelineStage, PlannedCutJob, RunParameters, TaskEvent, WorkerStatusKind,
+    },
 };
 
 const BATCH_EVENT_NAME: &str = "batch-event";
 const TASK_EVENT_NAME: &str = "task-event";
+const WORKER_RESTART_BASE_DELAY: Duration = Duration::from_secs(1);
+const WORKER_RESTART_MAX_DELAY: Duration = Duration::from_secs(30);
+const MAX_WORKER_RESTART_ATTEMPTS: u32 = 5;
+const WORKER_STABLE_UPTIME: Duration = Duration::from_secs(60);
+const WORKER_PING_INTERVAL: Duration = Duration::from_secs(15);
+const DEFAULT_WORKER_HANG_TIMEOUT: Duration = Duration::from_secs(300);
+const WORKER_HANG_TIMEOUT_ENV: &str = "AIYAAL_WORKER_HANG_TIMEOUT_SECS";
+const WORKER_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(60);
+
+struct WorkerHello {
+    protocol_version: u32,
+    worker_version: String,
+    capabilities: Vec<String>,
+}
+
+fn worker_restart_delay(attempt: u32) -> Duration {
+    let exponent = attempt.saturating_sub(1).min(16);
+    WORKER_RESTART_BASE_DELAY
+        .saturating_mul(1 << exponent)
+        .min(WORKER_RESTART_MAX_DELAY)
+}
+
+fn worker_hang_timeout() -> Duration {
+    env::var(WORKER_HANG_TIMEOUT_ENV)
+        .ok()
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+        .map(|timeout| timeout.max(WORKER_PING_INTERVAL * 2))
+        .unwrap_or(DEFAULT_WORKER_HANG_TIMEOUT)
+}
+
+/// Tracks when the worker last spoke so a silent stall can be told apart from
+/// a long job that is still reporting progress.
+struct WorkerHeartbeat {
+    last_event_at: Instant,
+    last_job_event_at: Instant,
+    hang_reported: bool,
+}
+
+impl WorkerHeartbeat {
+    fn new(now: Instant) -> Self {
+        Self {
+            last_event_at: now,
+            last_job_event_at: now,
+            hang_reported: false,
+        }
+    }
+
+    fn record(&mut self, event: &WorkerEvent, now: Instant) {
+        self.last_event_at = now;
+        if event.is_job_activity() {
+            self.last_job_event_at = now;
+        }
+    }
+
+    /// Returns how long the worker has been silent the first time the silence
+    /// exceeds `timeout`. While a job is running only job events count, since
+    /// the worker keeps answering pings even when demucs or yap has stalled.
+    fn check(&mut self, now: Instant, timeout: Duration, has_running_job: bool) -> Option<Duration> {
+        if !has_running_job {
+            self.last_job_event_at = now;
+        }
+        let last_activity = if has_running_job {
+            self.last_job_event_at
+        } else {
+            self.last_event_at
+        };
+        let silence = now.saturating_duration_since(last_activity);
+
+        if silence <= timeout {
+            self.hang_reported = false;
+            return None;
+        }
+        if self.hang_reported {
+            return None;
+        }
+        self.hang_reported = true;
+        Some(silence)
+    }
+}
+
+fn emit_worker_status(app: &AppHandle, status: WorkerStatusKind, message: impl Into<String>) {
+    let message = message.into();
+    let _ = app.emit(
+        BATCH_EVENT_NAME,
+        BatchEvent::worker_status(status.clone(), message.clone()),
+    );
+    let _ = app.emit(TASK_EVENT_NAME, TaskEvent::worker_status(status, message));
+}
 
 fn is_worker_stderr_error(line: &str) -> bool {
     let normalized = line.trim().to_ascii_lowercase();
@@ -26,6 +130,7 @@ fn is_worker_stderr_error(line: &str) -> bool {
 }
 
 pub async fn ensure_worker_sender(app: AppHandle, state: AppState) -> Result<crate::state::WorkerSender, String> {
+    let _start_guard = state.worker_start_lock.lock().await;
     if let Some(sender) = state.worker_sender().await {
         if !sender.is_closed() {
             return Ok(sender);
@@ -47,14 +152,15 @@ pub async fn ensure_worker_sender(app: AppHandle, state: AppState) -> Result<cra
     let worker_sender = spawn_worker_process(app.clone(), state.clone(), runtime).await?;
     state.set_worker_sender(worker_sender.clone()).await;
 
+    let ready_message = format!("Worker {} ready.", worker_sender.worker_version());
     app.emit(
         BATCH_EVENT_NAME,
-        BatchEvent::worker_status(WorkerStatusKind::Ready, "Worker ready."),
+        BatchEvent::worker_status(WorkerStatusKind::Ready, ready_message.clone()),
     )
     .map_err(|error| format!("Failed to emit worker ready event: {error}"))?;
     app.emit(
         TASK_EVENT_NAME,
-        TaskEvent::worker_status(WorkerStatusKind::Ready, "Worker ready."),
+        TaskEvent::worker_status(WorkerStatusKind::Ready, ready_message),
     )
     .map_err(|error| format!("Failed to emit task ready event: {error}"))?;
 
@@ -125,6 +231,8 @@ async fn spawn_worker_process(
         .ok_or_else(|| "Failed to access worker stderr".to_string())?;
 
     let (tx, mut rx) = mpsc::unbounded_channel::<WorkerCommand>();
+    let (hello_tx, hello_rx) = oneshot::channel::<WorkerHello>();
+    let heartbeat = Arc::new(Mutex::new(WorkerHeartbeat::new(Instant::now())));
 
     let app_for_stdin = app.clone();
     tauri::async_runtime::spawn(async move {
@@ -169,8 +277,10 @@ async fn spawn_worker_process(
 
     let app_for_stdout = app.clone();
     let state_for_stdout = state.clone();
-    tauri::async_runtime::spawn(async move {
+    let heartbeat_for_stdout = Arc::clone(&heartbeat);
+    let stdout_task = tauri::async_runtime::spawn(async move {
         let mut reader = BufReader::new(stdout).lines();
+        let mut hello_tx = Some(hello_tx);
 
         while let Ok(Some(line)) = reader.next_line().await {
             eprintln!("worker stdout: {line}");
@@ -190,36 +300,24 @@ async fn spawn_worker_process(
                 }
             };
 
-            state_for_stdout.apply_worker_event(&parsed_event).await;
-            match &parsed_event {
-                crate::protocol::WorkerEvent::BatchDone { batch_id, .. } => {
-                    if let Some(batch) = state_for_stdout.get_batch(batch_id).await {
-                        let started_at = state_for_stdout.take_batch_started_at(batch_id).await;
-                        if let Err(error) =
-                            analytics::record_batch_completion(&app_for_stdout, &batch, started_at)
-                        {
-                            eprintln!("analytics batch record error: {error}");
-                        }
-                    }
-                }
-                crate::protocol::WorkerEvent::TaskDone { task_id, .. } => {
-                    if let Some(task) = state_for_stdout.get_task(task_id).await {
-                        let started_at = state_for_stdout.take_task_started_at(task_id).await;
-                        if let Err(error) =
-                            analytics::record_task_completion(&app_for_stdout, &task, started_at)
-                        {
-                            eprintln!("analytics task record error: {error}");
-                        }
-                    }
-                }
-                _ => {}
+            if let Ok(mut heartbeat) = heartbeat_for_stdout.lock() {
+                heartbeat.record(&parsed_event, Instant::now());
             }
-            if let Some(frontend_event) = to_frontend_batch_event(&parsed_event) {
-                let _ = app_for_stdout.emit(BATCH_EVENT_NAME, frontend_event);
-            }
-            if let Some(task_event) = to_frontend_task_event(&parsed_event) {
-                let _ = app_for_stdout.emit(TASK_EVENT_NAME, task_event);
+            if let WorkerEvent::Hello {
+                protocol_version,
+                worker_version,
+                capabilities,
+            } = &parsed_event
+            {
+                if let Some(hello_tx) = hello_tx.take() {
+                    let _ = hello_tx.send(WorkerHello {
+                        protocol_version: *protocol_version,
+                        worker_version: worker_version.clone(),
+                        capabilities: capabilities.clone(),
+                    });
+                }
             }
+            handle_worker_event(&app_for_stdout, &state_for_stdout, &parsed_event).await;
         }
     });
 
@@ -242,10 +340,88 @@ async fn spawn_worker_process(
         }
     });
 
+    let hello = match tokio::time::timeout(WORKER_HANDSHAKE_TIMEOUT, hello_rx).await {
+        Ok(Ok(hello)) => hello,
+        Ok(Err(_)) => {
+            let _ = child.kill().await;
+            return Err("Worker exited before completing the startup handshake.".to_string());
+        }
+        Err(_) => {
+            let _ = child.kill().await;
+            return Err(format!(
+                "Worker did not complete the startup handshake within {}s.",
+                WORKER_HANDSHAKE_TIMEOUT.as_secs()
+            ));
+        }
+    };
+    if let Err(error) = ensure_compatible_worker(hello.protocol_version, &hello.worker_version) {
+        let _ = child.kill().await;
+        return Err(error);
+    }
+    let worker_sender = crate::state::WorkerSender::new(tx, hello.worker_version, hello.capabilities);
+    let sends_pings = worker_sender.supports("ping");
+
+    let app_for_heartbeat = app.clone();
+    let state_for_heartbeat = state.clone();
+    let sender_for_heartbeat = worker_sender.downgrade();
+    let heartbeat_task = tauri::async_runtime::spawn(async move {
+        let hang_timeout = worker_hang_timeout();
+        let mut interval = tokio::time::interval(WORKER_PING_INTERVAL);
+        let mut ping_count: u64 = 0;
+
+        loop {
+            interval.tick().await;
+            let Some(sender) = sender_for_heartbeat.upgrade() else {
+                break;
+            };
+            if sends_pings {
+                ping_count += 1;
+                if sender
+                    .send(WorkerCommand::Ping {
+                        ping_id: format!("ping-{ping_count}"),
+                    })
+                    .is_err()
+                {
+                    break;
+                }
+            }
+            drop(sender);
+
+            let running_jobs = state_for_heartbeat.running_job_labels().await;
+            let silence = match heartbeat.lock() {
+                Ok(mut heartbeat) => heartbeat.check(Instant::now(), hang_timeout, !running_jobs.is_empty()),
+                Err(_) => None,
+            };
+            let Some(silence) = silence else {
+                continue;
+            };
+
+            let message = if running_jobs.is_empty() {
+                format!(
+                    "Worker appears hung: no response to heartbeat for {}s.",
+                    silence.as_secs()
+                )
+            } else {
+                format!(
+                    "Worker appears hung: no progress for {}s while running {}.",
+                    silence.as_secs(),
+                    running_jobs.join(", ")
+                )
+            };
+            eprintln!("{message}");
+            emit_worker_status(&app_for_heartbeat, WorkerStatusKind::Error, message);
+        }
+    });
+
     let app_for_wait = app.clone();
     let state_for_wait = state.clone();
+    let spawned_at = Instant::now();
     tauri::async_runtime::spawn(async move {
         let status = child.wait().await;
+        heartbeat_task.abort();
+        // Drain whatever the worker printed before exiting so its own final
+        // events are applied before we synthesize any for orphaned work.
+        let _ = stdout_task.await;
         state_for_wait.clear_worker_sender().await;
 
         let has_active_tasks = state_for_wait
@@ -253,13 +429,13 @@ async fn spawn_worker_process(
             .lock()
             .await
             .values()
-            .any(|task| matches!(task.status, crate::types::TaskStatus::Queued | crate::types::TaskStatus::Running));
+            .any(|task| task.status.is_active());
         let has_active_batches = state_for_wait
             .batches
             .lock()
             .await
             .values()
-            .any(|batch| matches!(batch.status, crate::types::BatchStatus::Queued | crate::types::BatchStatus::Running));
+            .any(|batch| batch.status.is_active());
         let has_active_work = has_active_tasks || has_active_batches;
 
         let (message, is_error) = match status {
@@ -275,33 +451,224 @@ async fn spawn_worker_process(
         };
         eprintln!("{message}");
 
-        let batch_status = if is_error {
+        if has_active_work {
+            for event in state_for_wait.orphaned_work_events(&message).await {
+                handle_worker_event(&app_for_wait, &state_for_wait, &event).await;
+            }
+        }
+
+        let worker_status = if is_error {
             WorkerStatusKind::Error
         } else {
             WorkerStatusKind::Stopped
         };
-        let task_status = if is_error {
-            WorkerStatusKind::Error
-        } else {
-            WorkerStatusKind::Stopped
+        emit_worker_status(&app_for_wait, worker_status, message);
+
+        if is_error {
+            if spawned_at.elapsed() >= WORKER_STABLE_UPTIME {
+                state_for_wait.reset_worker_restart_attempts().await;
+            }
+            schedule_worker_restart(app_for_wait, state_for_wait);
+        }
+    });
+
+    Ok(worker_sender)
+}
+
+pub async fn handle_worker_event(app: &AppHandle, state: &AppState, event: &WorkerEvent) {
+    if let WorkerEvent::CutPlanNeeded {
+        task_id,
+        video_path,
+        analysis_path,
+    } = event
+    {
+        return provide_cut_plan(app, state, task_id, video_path, analysis_path);
+    }
+    if let WorkerEvent::AnalysisReplaced {
+        task_id,
+        task_kind,
+        job_id,
+        analysis_path,
+        engine_analysis_path,
+        previous_analysis_path,
+    } = event
+    {
+        let paths = [Path::new(analysis_path), Path::new(engine_analysis_path)];
+        let (message, stream) = match carry_over_reviews_from(Path::new(previous_analysis_path), &paths) {
+            Ok(0) => return,
+            Ok(carried) => (
+                format!("Kept {carried} reviewer decision(s) from the previous analysis."),
+                "stdout",
+            ),
+            Err(error) => (
+                format!("Failed keeping reviewer decisions from the previous analysis: {error}"),
+                "stderr",
+            ),
+        };
+        let log = WorkerEvent::JobLog {
+            batch_id: None,
+            task_id: Some(task_id.clone()),
+            task_kind: Some(task_kind.clone()),
+            job_id: job_id.clone(),
+            message,
+            stream: Some(stream.to_string()),
         };
+        state.apply_worker_event(&log).await;
+        return emit_frontend_events(app, &log);
+    }
+    state.apply_worker_event(event).await;
+    // The stored summary of a resumed run also counts the jobs it completed
+    // before it was interrupted, so the frontend gets that one.
+    let resolved_event = match event {
+        WorkerEvent::BatchDone { batch_id, summary } => {
+            let Some(batch) = state.get_batch(batch_id).await else {
+                return emit_frontend_events(app, event);
+            };
+            let started_at = state.take_batch_started_at(batch_id).await;
+            if let Err(error) = analytics::record_batch_completion(app, &batch, started_at) {
+                eprintln!("analytics batch record error: {error}");
+            }
+            WorkerEvent::BatchDone {
+                batch_id: batch_id.clone(),
+                summary: batch.summary.unwrap_or_else(|| summary.clone()),
+            }
+        }
+        WorkerEvent::TaskDone {
+            task_id,
+            task_kind,
+            summary,
+        } => {
+            let Some(task) = state.get_task(task_id).await else {
+                return emit_frontend_events(app, event);
+            };
+            let started_at = state.take_task_started_at(task_id).await;
+            if let Err(error) = analytics::record_task_completion(app, &task, started_at) {
+                eprintln!("analytics task record error: {error}");
+            }
+            WorkerEvent::TaskDone {
+                task_id: task_id.clone(),
+                task_kind: task_kind.clone(),
+                summary: task.summary.unwrap_or_else(|| summary.clone()),
+            }
+        }
+        _ => return emit_frontend_events(app, event),
+    };
+    emit_frontend_events(app, &resolved_event);
+}
 
-        let _ = app_for_wait.emit(
-            BATCH_EVENT_NAME,
-            BatchEvent::worker_status(batch_status, message.clone()),
-        );
-        let _ = app_for_wait.emit(
-            TASK_EVENT_NAME,
-            TaskEvent::worker_status(task_status, message.clone()),
-        );
+/// Plans a pipeline's cut stage off the event loop, since probing the video
+/// runs ffmpeg, and sends the plan to the waiting worker.
+fn provide_cut_plan(
+    app: &AppHandle,
+    state: &AppState,
+    task_id: &str,
+    video_path: &str,
+    analysis_path: &str,
+) {
+    let app = app.clone();
+    let state = state.clone();
+    let task_id = task_id.to_string();
+    let video_path = video_path.to_string();
+    let analysis_path = PathBuf::from(analysis_path);
+    tauri::async_runtime::spawn(async move {
+        let job = plan_pipeline_cut(&app, &state, &task_id, &video_path, &analysis_path).await;
+        let Some(worker_sender) = state.worker_sender().await else {
+            return;
+        };
+        if let Err(error) = worker_sender.send(WorkerCommand::ProvideCutPlan { task_id, job }) {
+            eprintln!("worker cut plan error: {error}");
+        }
     });
+}
+
+async fn plan_pipeline_cut(
+    app: &AppHandle,
+    state: &AppState,
+    task_id: &str,
+    video_path: &str,
+    analysis_path: &Path,
+) -> PlannedCutJob {
+    let cut_stage = match state.get_task(task_id).await.and_then(|task| task.parameters) {
+        Some(RunParameters::Pipeline { stages, .. }) => stages.into_iter().find_map(|stage| match stage {
+            PipelineStage::Cut { output_mode, plan } => Some((output_mode, plan)),
+            _ => None,
+        }),
+        _ => None,
+    };
+    let planned = cut_stage
+        .ok_or_else(|| format!("Task {task_id} has no cut stage."))
+        .and_then(|stage| Ok((resolve_ffmpeg_executable(app)?, stage)));
+    match planned {
+        Ok((ffmpeg_executable, (output_mode, plan))) => {
+            plan_cut_job(&ffmpeg_executable, video_path, analysis_path, &plan, &output_mode).await
+        }
+        Err(error) => PlannedCutJob {
+            video_path: video_path.to_string(),
+            ranges: Vec::new(),
+            removed_ranges: Vec::new(),
+            error: Some(error),
+        },
+    }
+}
+
+fn emit_frontend_events(app: &AppHandle, event: &WorkerEvent) {
+    if let Some(frontend_event) = to_frontend_batch_event(event) {
+        let _ = app.emit(BATCH_EVENT_NAME, frontend_event);
+    }
+    if let Some(task_event) = to_frontend_task_event(event) {
+        let _ = app.emit(TASK_EVENT_NAME, task_event);
+    }
+}
+
+/// Restarts a crashed worker after an exponential backoff. The future is boxed
+/// because it re-enters `ensure_worker_sender`, which spawns the task that
+/// schedules it.
+fn schedule_worker_restart(app: AppHandle, state: AppState) {
+    let restart: Pin<Box<dyn Future<Output = ()> + Send>> = Box::pin(async move {
+        let attempt = state.next_worker_restart_attempt().await;
+        if attempt > MAX_WORKER_RESTART_ATTEMPTS {
+            emit_worker_status(
+                &app,
+                WorkerStatusKind::Error,
+                format!(
+                    "Worker crashed {MAX_WORKER_RESTART_ATTEMPTS} times in a row. It will start again with the next job."
+                ),
+            );
+            state.reset_worker_restart_attempts().await;
+            return;
+        }
 
-    Ok(tx)
+        let delay = worker_restart_delay(attempt);
+        emit_worker_status(
+            &app,
+            WorkerStatusKind::Starting,
+            format!(
+                "Restarting worker in {}s (attempt {attempt}/{MAX_WORKER_RESTART_ATTEMPTS})...",
+                delay.as_secs()
+            ),
+        );
+        tokio::time::sleep(delay).await;
+
+        if let Err(error) = ensure_worker_sender(app.clone(), state.clone()).await {
+            eprintln!("worker restart error: {error}");
+            emit_worker_status(
+                &app,
+                WorkerStatusKind::Error,
+                format!("Failed restarting worker: {error}"),
+            );
+            schedule_worker_restart(app, state);
+        }
+    });
+    tauri::async_runtime::spawn(restart);
 }
 
 #[cfg(test)]
 mod tests {
-    use super::is_worker_stderr_error;
+    use std::time::{Duration, Instant};
+
+    use crate::protocol::WorkerEvent;
+
+    use super::{is_worker_stderr_error, worker_restart_delay, WorkerHeartbeat};
 
     #[test]
     fn should_treat_tracebacks_as_worker_errors() {
@@ -314,4 +681,65 @@ mod tests {
         assert!(!is_worker_stderr_error("Using cache found in /Users/test/.cache"));
         assert!(!is_worker_stderr_error("UserWarning: This path is deprecated."));
     }
+
+    #[test]
+    fn should_back_off_exponentially_between_worker_restarts() {
+        assert_eq!(worker_restart_delay(1), Duration::from_secs(1));
+        assert_eq!(worker_restart_delay(2), Duration::from_secs(2));
+        assert_eq!(worker_restart_delay(4), Duration::from_secs(8));
+        assert_eq!(worker_restart_delay(10), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn should_report_a_hang_once_when_the_worker_stops_answering() {
+        let started = Instant::now();
+        let timeout = Duration::from_secs(60);
+        let mut heartbeat = WorkerHeartbeat::new(started);
+
+        assert_eq!(heartbeat.check(started + Duration::from_secs(30), timeout, false), None);
+        assert_eq!(
+            heartbeat.check(started + Duration::from_secs(61), timeout, false),
+            Some(Duration::from_secs(61))
+        );
+        assert_eq!(heartbeat.check(started + Duration::from_secs(90), timeout, false), None);
+
+        heartbeat.record(
+            &WorkerEvent::Pong {
+                ping_id: "ping-1".to_string(),
+            },
+            started + Duration::from_secs(95),
+        );
+        assert_eq!(heartbeat.check(started + Duration::from_secs(100), timeout, false), None);
+    }
+
+    #[test]
+    fn should_flag_a_stalled_job_even_while_pongs_arrive() {
+        let started = Instant::now();
+        let timeout = Duration::from_secs(60);
+        let mut heartbeat = WorkerHeartbeat::new(started);
+        heartbeat.record(
+            &WorkerEvent::JobProgress {
+                batch_id: Some("batch-1".to_string()),
+                task_id: None,
+                task_kind: None,
+                job_id: "job-a".to_string(),
+                progress_pct: 10.0,
+            },
+            started,
+        );
+
+        for seconds in [15, 30, 45, 60, 75] {
+            heartbeat.record(
+                &WorkerEvent::Pong {
+                    ping_id: format!("ping-{seconds}"),
+                },
+                started + Duration::from_secs(seconds),
+            );
+        }
+
+        assert_eq!(
+            heartbeat.check(started + Duration::from_secs(75), timeout, true),
+            Some(Duration::from_secs(75))
+        );
+    }
 }
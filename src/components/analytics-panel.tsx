@@ -1,4 +1,12 @@
-import { Activity, AudioLines, Clock3, Scissors, ShieldAlert, WandSparkles } from "lucide-react";
+import {
+  Activity,
+  AudioLines,
+  Clock3,
+  Scissors,
+  ShieldAlert,
+  WandSparkles,
+  Workflow,
+} from "lucide-react";
 import { useEffect, useState } from "react";
 
 import { AnalyticsChartCard } from "@/components/layout/analytics-chart-card";
@@ -15,6 +23,7 @@ import {
 const taskKindIcon = {
   cut: Scissors,
   flag: ShieldAlert,
+  pipeline: Workflow,
   remove_music: WandSparkles,
   transcription: AudioLines,
 };
@@ -39,6 +48,7 @@ const emptySnapshot: AnalyticsSnapshot = {
     totalFlaggedItems: 0,
     totalFlagJobs: 0,
     totalMediaProcessed: 0,
+    totalPipelineJobs: 0,
     totalRemoveMusicJobs: 0,
     totalTranscriptionJobs: 0,
   },
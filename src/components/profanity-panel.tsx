@@ -73,7 +73,7 @@ const toTaskActivity = (
   workerStatus: MediaController["state"]["workerStatus"],
 ) => {
   const isTaskStarting = taskStatus === "queued" || workerStatus === "starting";
-  const isTaskRunning = taskStatus === "running";
+  const isTaskRunning = taskStatus === "running" || taskStatus === "paused";
 
   return {
     buttonLabel: isTaskStarting
@@ -106,6 +106,10 @@ const toTaskStatusVariant = (status: TaskState["status"]) => {
     return "cancelled" as const;
   }
 
+  if (status === "paused" || status === "interrupted") {
+    return status;
+  }
+
   return "running" as const;
 };
 
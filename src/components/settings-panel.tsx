@@ -5,7 +5,10 @@ import { Button } from "@/components/ui/button";
 import { Card, CardContent, CardHeader, CardTitle } from "@/components/ui/card";
 import { Input } from "@/components/ui/input";
 import { getModerationSettings, saveModerationSettings } from "@/features/media/transport";
-import type { ModerationSettings } from "@/features/media/types";
+import type { ModerationSettings, RulePattern } from "@/features/media/types";
+
+const substrings = (values: string[]): RulePattern[] =>
+  values.map((value) => ({ matchType: "substring", value }));
 
 const defaultSettings: ModerationSettings = {
   amazonNovaApiKey: "",
@@ -20,21 +23,21 @@ const defaultSettings: ModerationSettings = {
   rules: [
     {
       category: "aqeedah",
-      patterns: ["christmas", "xmas", "easter"],
+      patterns: substrings(["christmas", "xmas", "easter"]),
       priority: "high",
       reason: "Promotes non-Islamic religious celebration.",
       ruleId: "aqeedah_christmas",
     },
     {
       category: "magic",
-      patterns: ["spell", "sorcery", "witchcraft"],
+      patterns: substrings(["spell", "sorcery", "witchcraft"]),
       priority: "high",
       reason: "References magic or sorcery.",
       ruleId: "magic_sorcery",
     },
     {
       category: "language",
-      patterns: ["stupid", "idiot", "dumb"],
+      patterns: substrings(["stupid", "idiot", "dumb"]),
       priority: "medium",
       reason: "Contains offensive language.",
       ruleId: "offensive_language",
@@ -7,9 +7,9 @@ import { Badge } from "@/components/ui/badge";
 import { Button } from "@/components/ui/button";
 import { Card, CardContent, CardHeader, CardTitle } from "@/components/ui/card";
 import { DrawerClose } from "@/components/ui/drawer";
-import { findSubtitleAtTime, formatTime, parseSrt } from "@/features/editor/subtitles";
+import { findSubtitleAtTime, formatTime } from "@/features/editor/subtitles";
 import { getLatestTask, getTaskOutputPath } from "@/features/media/selectors";
-import { readTextFile } from "@/features/media/transport";
+import { parseSrtFile, readTextFile } from "@/features/media/transport";
 import type { AnalysisSidecar, CutRange, SubtitleEntry, TaskState } from "@/features/media/types";
 import type { useMediaController } from "@/features/media/useMediaController";
 import { parseAnalysisSidecar } from "@/features/moderation/results";
@@ -55,8 +55,21 @@ const toCutRanges = (ranges: LocalRange[]): CutRange[] => {
   }));
 };
 
-const toSrtSidecarPath = (path: string) => path.replace(/\.[^.]+$/, ".srt");
-const toAnalysisSidecarPath = (path: string) => path.replace(/\.[^.]+$/, ".analysis.json");
+const SUBTITLE_SIDECAR_EXTENSIONS = [".srt", ".vtt", ".ass", ".ssa"];
+
+const toSidecarPath = (path: string, extension: string) => path.replace(/\.[^.]+$/, extension);
+const toAnalysisSidecarPath = (path: string) => toSidecarPath(path, ".analysis.json");
+
+const loadSubtitleSidecar = async (videoPath: string) => {
+  for (const extension of SUBTITLE_SIDECAR_EXTENSIONS) {
+    try {
+      return await parseSrtFile(toSidecarPath(videoPath, extension));
+    } catch {
+      // Fall through to the next subtitle format.
+    }
+  }
+  throw new Error("No subtitle sidecar found.");
+};
 
 const resetLoadedSidecars = (
   setAnalysisSidecar: (value: AnalysisSidecar | null) => void,
@@ -70,19 +83,14 @@ const resetLoadedSidecars = (
 
 const applyLoadedSidecars = (
   analysisResult: PromiseSettledResult<string>,
-  subtitleResult: PromiseSettledResult<string>,
+  subtitleResult: PromiseSettledResult<SubtitleEntry[]>,
   setAnalysisSidecar: (value: AnalysisSidecar | null) => void,
   setHasSubtitleSidecar: (value: boolean) => void,
   setSubtitles: (value: SubtitleEntry[]) => void,
 ) => {
   if (subtitleResult.status === "fulfilled") {
-    try {
-      setSubtitles(parseSrt(subtitleResult.value));
-      setHasSubtitleSidecar(true);
-    } catch {
-      setSubtitles([]);
-      setHasSubtitleSidecar(false);
-    }
+    setSubtitles(subtitleResult.value);
+    setHasSubtitleSidecar(true);
   } else {
     setSubtitles([]);
     setHasSubtitleSidecar(false);
@@ -221,7 +229,7 @@ const CurrentSubtitleCard = ({
       </div>
       {!hasSubtitleSidecar ? (
         <p className="mt-2 text-[#7f524a] text-sm">
-          No `.srt` sidecar was found for this video yet.
+          No `.srt`, `.vtt` or `.ass` sidecar was found for this video yet.
         </p>
       ) : subtitle ? (
         <div className="mt-2 rounded-[18px] border border-[#ead3c4] bg-white px-3 py-2.5">
@@ -258,6 +266,10 @@ const toTaskStatusVariant = (status: TaskState["status"]) => {
     return "cancelled" as const;
   }
 
+  if (status === "paused" || status === "interrupted") {
+    return status;
+  }
+
   if (status === "queued") {
     return "queued" as const;
   }
@@ -395,7 +407,7 @@ const SimpleCutEditorPanel = ({ controller }: SimpleCutEditorPanelProps) => {
 
       try {
         const [subtitleContent, analysisContent] = await Promise.allSettled([
-          readTextFile(toSrtSidecarPath(videoPath)),
+          loadSubtitleSidecar(videoPath),
           readTextFile(toAnalysisSidecarPath(videoPath)),
         ]);
 
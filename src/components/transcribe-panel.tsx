@@ -33,7 +33,7 @@ const toTaskActivity = (
   workerStatus: MediaController["state"]["workerStatus"],
 ) => {
   const isTaskStarting = taskStatus === "queued" || workerStatus === "starting";
-  const isTaskRunning = taskStatus === "running";
+  const isTaskRunning = taskStatus === "running" || taskStatus === "paused";
 
   return {
     buttonLabel: isTaskStarting
@@ -68,6 +68,10 @@ const toTaskStatusVariant = (status: MediaController["state"]["tasksById"][strin
     return "cancelled" as const;
   }
 
+  if (status === "paused" || status === "interrupted") {
+    return status;
+  }
+
   if (status === "queued") {
     return "queued" as const;
   }
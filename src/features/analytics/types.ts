@@ -1,4 +1,4 @@
-export type AnalyticsTaskKind = "remove_music" | "transcription" | "flag" | "cut";
+export type AnalyticsTaskKind = "remove_music" | "transcription" | "flag" | "cut" | "pipeline";
 
 export type AnalyticsTotals = {
   totalMediaProcessed: number;
@@ -6,6 +6,7 @@ export type AnalyticsTotals = {
   totalTranscriptionJobs: number;
   totalFlagJobs: number;
   totalCutJobs: number;
+  totalPipelineJobs: number;
   totalFlaggedItems: number;
   totalFilesWithFlags: number;
   successCount: number;
@@ -22,6 +22,7 @@ const snapshot: AnalyticsSnapshot = {
     totalFlaggedItems: 7,
     totalFlagJobs: 2,
     totalMediaProcessed: 12,
+    totalPipelineJobs: 0,
     totalRemoveMusicJobs: 5,
     totalTranscriptionJobs: 4,
   },
@@ -44,4 +45,15 @@ describe("analytics utils", () => {
     expect(breakdown[0]?.label).toBe("Remove Music");
     expect(breakdown[1]?.jobs).toBe(3);
   });
+
+  it("should count and label pipeline runs", () => {
+    const withPipelines: AnalyticsSnapshot = {
+      ...snapshot,
+      breakdown: [...snapshot.breakdown, { jobs: 2, label: "", taskKind: "pipeline" }],
+      totals: { ...snapshot.totals, totalPipelineJobs: 2 },
+    };
+
+    expect(toAnalyticsMetricCards(withPipelines)[1]?.value).toBe("14");
+    expect(toTaskBreakdown(withPipelines)[2]?.label).toBe("Pipelines");
+  });
 });
@@ -9,6 +9,7 @@ import type {
 const taskKindLabel: Record<AnalyticsTaskKindBreakdown["taskKind"], string> = {
   cut: "Cut Exports",
   flag: "Detection Runs",
+  pipeline: "Pipelines",
   remove_music: "Remove Music",
   transcription: "Transcriptions",
 };
@@ -33,6 +34,7 @@ export const createEmptyAnalyticsTotals = (): AnalyticsTotals => ({
   totalFlaggedItems: 0,
   totalFlagJobs: 0,
   totalMediaProcessed: 0,
+  totalPipelineJobs: 0,
   totalRemoveMusicJobs: 0,
   totalTranscriptionJobs: 0,
 });
@@ -43,7 +45,8 @@ export const toAnalyticsMetricCards = (snapshot: AnalyticsSnapshot): AnalyticsMe
     totals.totalRemoveMusicJobs +
     totals.totalTranscriptionJobs +
     totals.totalFlagJobs +
-    totals.totalCutJobs;
+    totals.totalCutJobs +
+    totals.totalPipelineJobs;
 
   return [
     {
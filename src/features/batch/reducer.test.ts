@@ -79,6 +79,7 @@ describe("batch reducer", () => {
         summary: {
           cancelled: 1,
           failed: 0,
+          interrupted: 0,
           ok: 1,
         },
         type: "batch_done",
@@ -90,6 +91,108 @@ describe("batch reducer", () => {
     expect(state.batchesById["batch-1"]?.jobs[0]?.status).toBe("cancelled");
   });
 
+  it("should keep hard-cancelled jobs cancelled when the batch finishes", () => {
+    const seed = batchReducer(createInitialBatchUiState(), {
+      payload: createBatch(),
+      type: "start_batch_success",
+    });
+    const running = batchReducer(seed, {
+      payload: { batchId: "batch-1", jobId: "job-a", progressPct: 40, type: "job_progress" },
+      type: "apply_event",
+    });
+    const cancelled = batchReducer(running, {
+      payload: { batchId: "batch-1", jobId: "job-a", type: "job_cancelled" },
+      type: "apply_event",
+    });
+
+    const state = batchReducer(cancelled, {
+      payload: {
+        batchId: "batch-1",
+        summary: {
+          cancelled: 2,
+          failed: 0,
+          interrupted: 0,
+          ok: 0,
+        },
+        type: "batch_done",
+      },
+      type: "apply_event",
+    });
+
+    expect(state.batchesById["batch-1"]?.status).toBe("cancelled");
+    expect(state.batchesById["batch-1"]?.jobs[0]?.status).toBe("cancelled");
+    expect(state.batchesById["batch-1"]?.jobs[0]?.error).toBeUndefined();
+  });
+
+  it("should leave a batch interrupted when the worker dies mid-run", () => {
+    const seed = batchReducer(createInitialBatchUiState(), {
+      payload: createBatch(),
+      type: "start_batch_success",
+    });
+
+    const state = batchReducer(seed, {
+      payload: {
+        batchId: "batch-1",
+        summary: {
+          cancelled: 0,
+          failed: 0,
+          interrupted: 1,
+          ok: 1,
+        },
+        type: "batch_done",
+      },
+      type: "apply_event",
+    });
+
+    expect(state.batchesById["batch-1"]?.status).toBe("interrupted");
+    expect(state.batchesById["batch-1"]?.jobs[0]?.status).toBe("interrupted");
+  });
+
+  it("should mark the running job interrupted when the worker dies", () => {
+    const seed = batchReducer(createInitialBatchUiState(), {
+      payload: createBatch(),
+      type: "start_batch_success",
+    });
+    const running = batchReducer(seed, {
+      payload: { batchId: "batch-1", jobId: "job-a", progressPct: 40, type: "job_progress" },
+      type: "apply_event",
+    });
+
+    const state = batchReducer(running, {
+      payload: {
+        batchId: "batch-1",
+        error: "Worker process exited unexpectedly",
+        jobId: "job-a",
+        type: "job_interrupted",
+      },
+      type: "apply_event",
+    });
+
+    expect(state.batchesById["batch-1"]?.jobs[0]?.status).toBe("interrupted");
+    expect(state.batchesById["batch-1"]?.jobs[0]?.error).toBe(
+      "Worker process exited unexpectedly",
+    );
+  });
+
+  it("should mark a batch paused and running again on resume", () => {
+    const seed = batchReducer(createInitialBatchUiState(), {
+      payload: createBatch(),
+      type: "start_batch_success",
+    });
+
+    const paused = batchReducer(seed, {
+      payload: { batchId: "batch-1", type: "batch_paused" },
+      type: "apply_event",
+    });
+    const resumed = batchReducer(paused, {
+      payload: { batchId: "batch-1", type: "batch_resumed" },
+      type: "apply_event",
+    });
+
+    expect(paused.batchesById["batch-1"]?.status).toBe("paused");
+    expect(resumed.batchesById["batch-1"]?.status).toBe("running");
+  });
+
   it("should track worker status events", () => {
     const state = batchReducer(createInitialBatchUiState(), {
       payload: {
@@ -53,6 +53,21 @@ const applyBatchEvent = (batch: BatchState, event: BatchEvent): BatchState => {
     }));
   }
 
+  if (event.type === "job_interrupted") {
+    return updateJob(batch, event.jobId, (job) => ({
+      ...job,
+      error: event.error,
+      status: "interrupted",
+    }));
+  }
+
+  if (event.type === "job_cancelled") {
+    return updateJob(batch, event.jobId, (job) => ({
+      ...job,
+      status: "cancelled",
+    }));
+  }
+
   if (event.type === "batch_done") {
     return {
       ...batch,
@@ -60,7 +75,7 @@ const applyBatchEvent = (batch: BatchState, event: BatchEvent): BatchState => {
         job.status === "queued"
           ? {
               ...job,
-              status: "cancelled",
+              status: event.summary.interrupted > 0 ? "interrupted" : "cancelled",
             }
           : job.status === "running"
             ? {
@@ -70,11 +85,30 @@ const applyBatchEvent = (batch: BatchState, event: BatchEvent): BatchState => {
               }
             : job,
       ),
-      status: event.summary.cancelled > 0 ? "cancelled" : "completed",
+      status:
+        event.summary.interrupted > 0
+          ? "interrupted"
+          : event.summary.cancelled > 0
+            ? "cancelled"
+            : "completed",
       summary: event.summary,
     };
   }
 
+  if (event.type === "batch_paused") {
+    return {
+      ...batch,
+      status: "paused",
+    };
+  }
+
+  if (event.type === "batch_resumed") {
+    return {
+      ...batch,
+      status: "running",
+    };
+  }
+
   if (event.type === "job_log") {
     return updateJob(batch, event.jobId, (job) => ({
       ...job,
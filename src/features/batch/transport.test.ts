@@ -1,11 +1,15 @@
 import { describe, expect, it } from "bun:test";
 import {
+  buildBatchControlInvokeArgs,
   buildCancelBatchInvokeArgs,
   buildGetBatchStateInvokeArgs,
   buildStartBatchInvokeArgs,
   cancelBatch,
   getBatchState,
   openFolderPicker,
+  pauseBatch,
+  restartInterruptedBatch,
+  resumeBatch,
   startBatch,
   subscribeToBatchEvents,
 } from "@/features/batch/transport";
@@ -29,6 +33,9 @@ describe("batch transport", () => {
     expect(buildCancelBatchInvokeArgs(cancelRequest)).toEqual({
       request: cancelRequest,
     });
+    expect(buildBatchControlInvokeArgs({ batchId: "batch-1" })).toEqual({
+      request: { batchId: "batch-1" },
+    });
     expect(buildGetBatchStateInvokeArgs("batch-1")).toEqual({
       batchId: "batch-1",
     });
@@ -57,12 +64,18 @@ describe("batch transport", () => {
       },
       invokeMock,
     );
+    await pauseBatch({ batchId: "batch-1" }, invokeMock);
+    await resumeBatch({ batchId: "batch-1" }, invokeMock);
+    await restartInterruptedBatch({ batchId: "batch-1" }, invokeMock);
     await getBatchState("batch-1", invokeMock);
 
     expect(calls.map((call) => call.command)).toEqual([
       "open_folder_picker",
       "start_batch",
       "cancel_batch",
+      "pause_batch",
+      "resume_batch",
+      "restart_interrupted_batch",
       "get_batch_state",
     ]);
   });
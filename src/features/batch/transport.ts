@@ -1,5 +1,7 @@
 import { BATCH_EVENT_NAME } from "@/features/batch/constants";
 import type {
+  BatchControlAck,
+  BatchControlRequest,
   BatchEvent,
   BatchStartedResponse,
   BatchState,
@@ -20,6 +22,10 @@ export const buildCancelBatchInvokeArgs = (request: CancelBatchRequest) => ({
   request,
 });
 
+export const buildBatchControlInvokeArgs = (request: BatchControlRequest) => ({
+  request,
+});
+
 export const buildGetBatchStateInvokeArgs = (batchId: string) => ({
   batchId,
 });
@@ -33,6 +39,18 @@ export const startBatch = (request: StartBatchRequest, invokeFn: InvokeFn = invo
 export const cancelBatch = (request: CancelBatchRequest, invokeFn: InvokeFn = invoke) =>
   invokeFn<CancelAck>("cancel_batch", buildCancelBatchInvokeArgs(request));
 
+export const pauseBatch = (request: BatchControlRequest, invokeFn: InvokeFn = invoke) =>
+  invokeFn<BatchControlAck>("pause_batch", buildBatchControlInvokeArgs(request));
+
+// Only for paused batches; interrupted ones go through `restartInterruptedBatch`.
+export const resumeBatch = (request: BatchControlRequest, invokeFn: InvokeFn = invoke) =>
+  invokeFn<BatchControlAck>("resume_batch", buildBatchControlInvokeArgs(request));
+
+export const restartInterruptedBatch = (
+  request: BatchControlRequest,
+  invokeFn: InvokeFn = invoke,
+) => invokeFn<BatchControlAck>("restart_interrupted_batch", buildBatchControlInvokeArgs(request));
+
 export const getBatchState = (batchId: string, invokeFn: InvokeFn = invoke) =>
   invokeFn<BatchState | null>("get_batch_state", buildGetBatchStateInvokeArgs(batchId));
 
@@ -11,7 +11,7 @@ export type CancelBatchRequest = {
   mode: "stop_after_current";
 };
 
-export type JobStatus = "queued" | "running" | "completed" | "failed" | "cancelled";
+export type JobStatus = "queued" | "running" | "completed" | "failed" | "cancelled" | "interrupted";
 
 export type JobRecord = {
   jobId: string;
@@ -28,9 +28,10 @@ export type BatchSummary = {
   ok: number;
   failed: number;
   cancelled: number;
+  interrupted: number;
 };
 
-export type BatchStatus = "queued" | "running" | "completed" | "cancelled";
+export type BatchStatus = "queued" | "running" | "paused" | "completed" | "cancelled" | "interrupted";
 
 export type BatchState = {
   batchId: string;
@@ -50,6 +51,15 @@ export type CancelAck = {
   accepted: boolean;
 };
 
+export type BatchControlRequest = {
+  batchId: string;
+};
+
+export type BatchControlAck = {
+  batchId: string;
+  accepted: boolean;
+};
+
 export type BatchEvent =
   | {
       type: "job_progress";
@@ -69,11 +79,30 @@ export type BatchEvent =
       jobId: string;
       error: string;
     }
+  | {
+      type: "job_interrupted";
+      batchId: string;
+      jobId: string;
+      error: string;
+    }
+  | {
+      type: "job_cancelled";
+      batchId: string;
+      jobId: string;
+    }
   | {
       type: "batch_done";
       batchId: string;
       summary: BatchSummary;
     }
+  | {
+      type: "batch_paused";
+      batchId: string;
+    }
+  | {
+      type: "batch_resumed";
+      batchId: string;
+    }
   | {
       type: "job_log";
       batchId: string;
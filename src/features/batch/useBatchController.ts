@@ -10,6 +10,8 @@ import {
 import {
   cancelBatch,
   openFolderPicker,
+  restartInterruptedBatch,
+  resumeBatch,
   startBatch,
   subscribeToBatchEvents,
 } from "@/features/batch/transport";
@@ -115,6 +117,40 @@ export const useBatchController = () => {
     }
   };
 
+  // Resuming continues a paused batch; only an interrupted batch is restarted,
+  // so each action is a no-op for the other status.
+  const resume = async () => {
+    const batch = selectActiveBatch(state);
+    if (batch?.status !== "paused") {
+      return;
+    }
+
+    try {
+      await resumeBatch({ batchId: batch.batchId });
+    } catch (error: unknown) {
+      dispatch({
+        payload: error instanceof Error ? error.message : "Unable to resume batch.",
+        type: "start_batch_error",
+      });
+    }
+  };
+
+  const restartInterrupted = async () => {
+    const batch = selectActiveBatch(state);
+    if (batch?.status !== "interrupted") {
+      return;
+    }
+
+    try {
+      await restartInterruptedBatch({ batchId: batch.batchId });
+    } catch (error: unknown) {
+      dispatch({
+        payload: error instanceof Error ? error.message : "Unable to restart batch.",
+        type: "start_batch_error",
+      });
+    }
+  };
+
   const openOutput = async (path: string) => {
     await openPath(path);
   };
@@ -132,6 +168,8 @@ export const useBatchController = () => {
     jobs: activeBatch ? selectSortedJobs(activeBatch.jobs) : [],
     openOutput,
     progressPct: selectBatchProgress(state),
+    restartInterrupted,
+    resume,
     setSelectedInputDir,
     start,
     state,
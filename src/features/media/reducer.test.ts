@@ -47,6 +47,121 @@ describe("mediaReducer", () => {
     expect(completed.tasksById["task-1"]?.jobs[0]?.artifacts?.summary).toBe("Transcript written.");
   });
 
+  it("should keep hard-cancelled jobs cancelled when the task finishes", () => {
+    const started = mediaReducer(createInitialMediaUiState(), {
+      payload: {
+        inputPaths: ["/tmp/clip.mp4"],
+        taskId: "task-1",
+        taskKind: "transcription",
+      },
+      type: "task_started",
+    });
+    const running = mediaReducer(started, {
+      payload: {
+        jobId: "tmp-clip-mp4",
+        progressPct: 27,
+        taskId: "task-1",
+        taskKind: "transcription",
+        type: "job_progress",
+      },
+      type: "apply_task_event",
+    });
+    const cancelled = mediaReducer(running, {
+      payload: {
+        jobId: "tmp-clip-mp4",
+        taskId: "task-1",
+        taskKind: "transcription",
+        type: "job_cancelled",
+      },
+      type: "apply_task_event",
+    });
+
+    const done = mediaReducer(cancelled, {
+      payload: {
+        summary: { cancelled: 1, failed: 0, interrupted: 0, ok: 0 },
+        taskId: "task-1",
+        taskKind: "transcription",
+        type: "task_done",
+      },
+      type: "apply_task_event",
+    });
+
+    expect(done.tasksById["task-1"]?.status).toBe("cancelled");
+    expect(done.tasksById["task-1"]?.jobs[0]?.status).toBe("cancelled");
+  });
+
+  it("should track pipeline stages until the task finishes", () => {
+    const started = mediaReducer(createInitialMediaUiState(), {
+      payload: {
+        inputPaths: ["/tmp/clip.mp4"],
+        taskId: "task-1",
+        taskKind: "pipeline",
+      },
+      type: "task_started",
+    });
+
+    const transcribing = mediaReducer(started, {
+      payload: {
+        jobId: "tmp-clip-mp4",
+        stage: "transcribe",
+        taskId: "task-1",
+        taskKind: "pipeline",
+        type: "job_stage",
+      },
+      type: "apply_task_event",
+    });
+    const completed = mediaReducer(transcribing, {
+      payload: {
+        jobId: "tmp-clip-mp4",
+        outputPath: "/tmp/clip-cleaned.mp4",
+        taskId: "task-1",
+        taskKind: "pipeline",
+        type: "job_done",
+      },
+      type: "apply_task_event",
+    });
+    const done = mediaReducer(completed, {
+      payload: {
+        summary: { cancelled: 0, failed: 0, interrupted: 0, ok: 1 },
+        taskId: "task-1",
+        taskKind: "pipeline",
+        type: "task_done",
+      },
+      type: "apply_task_event",
+    });
+
+    expect(transcribing.tasksById["task-1"]?.status).toBe("running");
+    expect(transcribing.tasksById["task-1"]?.jobs[0]?.stage).toBe("transcribe");
+    expect(transcribing.tasksById["task-1"]?.jobs[0]?.status).toBe("running");
+    expect(done.tasksById["task-1"]?.taskKind).toBe("pipeline");
+    expect(done.tasksById["task-1"]?.status).toBe("completed");
+    expect(done.tasksById["task-1"]?.jobs[0]?.status).toBe("completed");
+    expect(done.tasksById["task-1"]?.jobs[0]?.outputPath).toBe("/tmp/clip-cleaned.mp4");
+  });
+
+  it("should mark a task paused and running again on resume", () => {
+    const started = mediaReducer(createInitialMediaUiState(), {
+      payload: {
+        inputPaths: ["/tmp/clip.srt"],
+        taskId: "task-1",
+        taskKind: "flag",
+      },
+      type: "task_started",
+    });
+
+    const paused = mediaReducer(started, {
+      payload: { taskId: "task-1", taskKind: "flag", type: "task_paused" },
+      type: "apply_task_event",
+    });
+    const resumed = mediaReducer(paused, {
+      payload: { taskId: "task-1", taskKind: "flag", type: "task_resumed" },
+      type: "apply_task_event",
+    });
+
+    expect(paused.tasksById["task-1"]?.status).toBe("paused");
+    expect(resumed.tasksById["task-1"]?.status).toBe("running");
+  });
+
   it("should keep remove-music and editor task states isolated", () => {
     const initial = {
       ...createInitialMediaUiState(),
@@ -99,6 +99,22 @@ const applyTaskEvent = (task: TaskState, event: TaskEvent): TaskState => {
     };
   }
 
+  if (event.type === "job_stage") {
+    return {
+      ...task,
+      jobs: task.jobs.map((job) =>
+        job.jobId === event.jobId
+          ? {
+              ...job,
+              stage: event.stage,
+              status: "running",
+            }
+          : job,
+      ),
+      status: "running",
+    };
+  }
+
   if (event.type === "job_done") {
     const artifacts: TaskJobArtifacts | undefined = toJobArtifacts(event.artifacts);
     return {
@@ -133,6 +149,49 @@ const applyTaskEvent = (task: TaskState, event: TaskEvent): TaskState => {
     };
   }
 
+  if (event.type === "job_interrupted") {
+    return {
+      ...task,
+      jobs: task.jobs.map((job) =>
+        job.jobId === event.jobId
+          ? {
+              ...job,
+              error: event.error,
+              status: "interrupted",
+            }
+          : job,
+      ),
+    };
+  }
+
+  if (event.type === "job_cancelled") {
+    return {
+      ...task,
+      jobs: task.jobs.map((job) =>
+        job.jobId === event.jobId
+          ? {
+              ...job,
+              status: "cancelled",
+            }
+          : job,
+      ),
+    };
+  }
+
+  if (event.type === "task_paused") {
+    return {
+      ...task,
+      status: "paused",
+    };
+  }
+
+  if (event.type === "task_resumed") {
+    return {
+      ...task,
+      status: "running",
+    };
+  }
+
   if (event.type === "job_log") {
     return {
       ...task,
@@ -155,7 +214,7 @@ const applyTaskEvent = (task: TaskState, event: TaskEvent): TaskState => {
         job.status === "queued"
           ? {
               ...job,
-              status: "cancelled",
+              status: event.summary.interrupted > 0 ? "interrupted" : "cancelled",
             }
           : job.status === "running"
             ? {
@@ -165,7 +224,12 @@ const applyTaskEvent = (task: TaskState, event: TaskEvent): TaskState => {
               }
             : job,
       ),
-      status: event.summary.cancelled > 0 ? "cancelled" : "completed",
+      status:
+        event.summary.interrupted > 0
+          ? "interrupted"
+          : event.summary.cancelled > 0
+            ? "cancelled"
+            : "completed",
       summary: event.summary,
     };
   }
@@ -7,7 +7,10 @@ import type {
   StartCutJobRequest,
   StartFlagBatchRequest,
   StartTranscriptionBatchRequest,
+  SubtitleEntry,
   TaskCancelAck,
+  TaskControlAck,
+  TaskControlRequest,
   TaskEvent,
   TaskStartedResponse,
   TaskState,
@@ -56,6 +59,22 @@ export const cancelTask = (request: CancelTaskRequest, invokeFn: InvokeFn = invo
     request,
   });
 
+export const pauseTask = (request: TaskControlRequest, invokeFn: InvokeFn = invoke) =>
+  invokeFn<TaskControlAck>("pause_task", {
+    request,
+  });
+
+// Only for paused tasks; interrupted ones go through `restartInterruptedTask`.
+export const resumeTask = (request: TaskControlRequest, invokeFn: InvokeFn = invoke) =>
+  invokeFn<TaskControlAck>("resume_task", {
+    request,
+  });
+
+export const restartInterruptedTask = (request: TaskControlRequest, invokeFn: InvokeFn = invoke) =>
+  invokeFn<TaskControlAck>("restart_interrupted_task", {
+    request,
+  });
+
 export const getTaskState = (taskId: string, invokeFn: InvokeFn = invoke) =>
   invokeFn<TaskState | null>("get_task_state", {
     taskId,
@@ -74,6 +93,11 @@ export const readTextFile = (path: string, invokeFn: InvokeFn = invoke) =>
     path,
   });
 
+export const parseSrtFile = (path: string, invokeFn: InvokeFn = invoke) =>
+  invokeFn<SubtitleEntry[]>("parse_srt_file", {
+    path,
+  });
+
 export const subscribeToTaskEvents = async (
   onEvent: (event: TaskEvent) => void,
   listenFn: ListenFn = listen,
@@ -1,12 +1,14 @@
-export type TaskKind = "transcription" | "flag" | "cut";
+export type TaskKind = "transcription" | "flag" | "cut" | "pipeline";
+export type PipelineStageKind = "remove_music" | "transcribe" | "flag" | "cut";
 
-export type TaskJobStatus = "queued" | "running" | "completed" | "failed" | "cancelled";
-export type TaskStatus = "queued" | "running" | "completed" | "cancelled";
+export type TaskJobStatus = "queued" | "running" | "completed" | "failed" | "cancelled" | "interrupted";
+export type TaskStatus = "queued" | "running" | "paused" | "completed" | "cancelled" | "interrupted";
 
 export type TaskSummary = {
   ok: number;
   failed: number;
   cancelled: number;
+  interrupted: number;
 };
 
 export type TaskJobRecord = {
@@ -19,6 +21,7 @@ export type TaskJobRecord = {
   error?: string;
   logs: string[];
   artifacts?: TaskJobArtifacts;
+  stage?: PipelineStageKind;
 };
 
 export type TaskState = {
@@ -77,6 +80,15 @@ export type TaskCancelAck = {
   accepted: boolean;
 };
 
+export type TaskControlRequest = {
+  taskId: string;
+};
+
+export type TaskControlAck = {
+  taskId: string;
+  accepted: boolean;
+};
+
 export type VideoListItem = {
   fileName: string;
   path: string;
@@ -103,12 +115,28 @@ export type FlaggedSegment = {
   ruleId: string;
 };
 
+export type PatternMatchType = "substring" | "whole_word" | "phrase" | "regex" | "stem";
+
+export type RulePattern = {
+  value: string;
+  matchType: PatternMatchType;
+};
+
 export type ModerationRule = {
   ruleId: string;
   category: string;
   priority: "high" | "medium" | "low";
   reason: string;
-  patterns: string[];
+  patterns: RulePattern[];
+  exclusions?: RulePattern[];
+};
+
+export type TextNormalization = {
+  foldCase: boolean;
+  stripDiacritics: boolean;
+  unifyArabicLetters: boolean;
+  foldDigits: boolean;
+  foldLeetspeak: boolean;
 };
 
 export type ModerationEngine = "blacklist" | "gemini" | "nova_pro";
@@ -123,6 +151,7 @@ export type ModerationSettings = {
   priorityGuidelines: string;
   profanityWords: string[];
   rules: ModerationRule[];
+  normalization?: TextNormalization;
 };
 
 export type AnalysisSidecar = {
@@ -161,12 +190,42 @@ export type TaskEvent =
       jobId: string;
       error: string;
     }
+  | {
+      type: "job_interrupted";
+      taskId: string;
+      taskKind: TaskKind;
+      jobId: string;
+      error: string;
+    }
+  | {
+      type: "job_cancelled";
+      taskId: string;
+      taskKind: TaskKind;
+      jobId: string;
+    }
+  | {
+      type: "job_stage";
+      taskId: string;
+      taskKind: TaskKind;
+      jobId: string;
+      stage: PipelineStageKind;
+    }
   | {
       type: "task_done";
       taskId: string;
       taskKind: TaskKind;
       summary: TaskSummary;
     }
+  | {
+      type: "task_paused";
+      taskId: string;
+      taskKind: TaskKind;
+    }
+  | {
+      type: "task_resumed";
+      taskId: string;
+      taskKind: TaskKind;
+    }
   | {
       type: "job_log";
       taskId: string;
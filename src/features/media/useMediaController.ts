@@ -7,6 +7,8 @@ import {
   cancelTask,
   getModerationSettings,
   listVideos,
+  restartInterruptedTask,
+  resumeTask,
   saveModerationSettings,
   startCutJob,
   startFlagBatch,
@@ -205,6 +207,36 @@ export const useMediaController = () => {
     }
   };
 
+  // Resuming continues a paused task; only an interrupted task is restarted,
+  // so each action is a no-op for the other status.
+  const resumeTaskById = async (taskId: string | null) => {
+    if (!taskId || state.tasksById[taskId]?.status !== "paused") {
+      return;
+    }
+    try {
+      await resumeTask({ taskId });
+    } catch (error: unknown) {
+      dispatch({
+        payload: error instanceof Error ? error.message : "Failed resuming task.",
+        type: "task_start_error",
+      });
+    }
+  };
+
+  const restartInterruptedTaskById = async (taskId: string | null) => {
+    if (!taskId || state.tasksById[taskId]?.status !== "interrupted") {
+      return;
+    }
+    try {
+      await restartInterruptedTask({ taskId });
+    } catch (error: unknown) {
+      dispatch({
+        payload: error instanceof Error ? error.message : "Failed restarting task.",
+        type: "task_start_error",
+      });
+    }
+  };
+
   const cancelActiveTask = async () => {
     await cancelTaskById(state.activeTaskId);
   };
@@ -231,6 +263,8 @@ export const useMediaController = () => {
       }),
     loadSettings,
     loadVideos,
+    restartInterruptedTaskById,
+    resumeTaskById,
     saveSettings,
     selectVideo,
     setSelectedInputDir,
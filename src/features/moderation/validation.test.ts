@@ -15,7 +15,7 @@ describe("isValidModerationSettings", () => {
       rules: [
         {
           category: "aqeedah",
-          patterns: ["christmas"],
+          patterns: [{ matchType: "substring", value: "christmas" }],
           priority: "high",
           reason: "issue",
           ruleId: "aqeedah_1",
@@ -28,5 +28,64 @@ describe("isValidModerationSettings", () => {
     ).toBe(false);
     expect(isValidModerationSettings({ ...valid, engine: "bad" })).toBe(false);
     expect(isValidModerationSettings({ ...valid, analysisStrategy: "bad" })).toBe(false);
+    expect(
+      isValidModerationSettings({
+        ...valid,
+        rules: [{ ...valid.rules[0], patterns: ["christmas"] }],
+      }),
+    ).toBe(false);
+  });
+
+  it("should accept settings as the backend returns them after a JSON round trip", () => {
+    const returned = {
+      amazonNovaApiKey: "",
+      analysisStrategy: "fast",
+      contentCriteria: "criteria",
+      engine: "blacklist",
+      googleApiKey: "",
+      normalization: {
+        foldCase: true,
+        foldDigits: false,
+        foldLeetspeak: false,
+        stripDiacritics: true,
+        unifyArabicLetters: true,
+      },
+      priorityGuidelines: "guidelines",
+      profanityWords: [],
+      rules: [
+        {
+          category: "magic",
+          exclusions: [{ matchType: "phrase", value: "spelling bee" }],
+          patterns: [
+            { matchType: "whole_word", value: "spell" },
+            { matchType: "stem", value: "summon" },
+            { matchType: "regex", value: "\\bwitch(es|craft)?\\b" },
+          ],
+          priority: "high",
+          reason: "References magic or sorcery.",
+          ruleId: "magic_sorcery",
+        },
+        {
+          category: "aqeedah",
+          patterns: [{ matchType: "substring", value: "christmas" }],
+          priority: "high",
+          reason: "Promotes non-Islamic religious celebration.",
+          ruleId: "aqeedah_christmas",
+        },
+      ],
+    };
+
+    const roundTripped = JSON.parse(JSON.stringify(returned)) as unknown;
+
+    expect(isValidModerationSettings(roundTripped)).toBe(true);
+    expect(
+      isValidModerationSettings({
+        ...returned,
+        rules: [{ ...returned.rules[0], exclusions: [{ matchType: "fuzzy", value: "bee" }] }],
+      }),
+    ).toBe(false);
+    expect(
+      isValidModerationSettings({ ...returned, normalization: { foldCase: "yes" } }),
+    ).toBe(false);
   });
 });
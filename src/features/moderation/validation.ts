@@ -3,6 +3,9 @@ import type {
   ModerationEngine,
   ModerationRule,
   ModerationSettings,
+  PatternMatchType,
+  RulePattern,
+  TextNormalization,
 } from "@/features/media/types";
 import { analysisStrategyValues, moderationEngineValues } from "@/features/moderation/engines";
 
@@ -15,6 +18,30 @@ const isEngine = (value: string): value is ModerationEngine =>
 const isAnalysisStrategy = (value: string): value is AnalysisStrategy =>
   analysisStrategyValues.includes(value as AnalysisStrategy);
 
+const patternMatchTypeValues: PatternMatchType[] = [
+  "substring",
+  "whole_word",
+  "phrase",
+  "regex",
+  "stem",
+];
+
+const isPatternMatchType = (value: string): value is PatternMatchType =>
+  patternMatchTypeValues.includes(value as PatternMatchType);
+
+const isRulePattern = (value: unknown): value is RulePattern => {
+  if (!value || typeof value !== "object") {
+    return false;
+  }
+
+  const candidate = value as Partial<RulePattern>;
+  return (
+    typeof candidate.value === "string" &&
+    typeof candidate.matchType === "string" &&
+    isPatternMatchType(candidate.matchType)
+  );
+};
+
 const isRule = (value: unknown): value is ModerationRule => {
   if (!value || typeof value !== "object") {
     return false;
@@ -26,12 +53,31 @@ const isRule = (value: unknown): value is ModerationRule => {
     typeof candidate.category === "string" &&
     typeof candidate.reason === "string" &&
     Array.isArray(candidate.patterns) &&
-    candidate.patterns.every((pattern) => typeof pattern === "string") &&
+    candidate.patterns.every(isRulePattern) &&
+    (candidate.exclusions === undefined ||
+      (Array.isArray(candidate.exclusions) && candidate.exclusions.every(isRulePattern))) &&
     typeof candidate.priority === "string" &&
     isPriority(candidate.priority)
   );
 };
 
+const normalizationKeys: (keyof TextNormalization)[] = [
+  "foldCase",
+  "stripDiacritics",
+  "unifyArabicLetters",
+  "foldDigits",
+  "foldLeetspeak",
+];
+
+const isNormalization = (value: unknown): value is TextNormalization => {
+  if (!value || typeof value !== "object") {
+    return false;
+  }
+
+  const candidate = value as Partial<TextNormalization>;
+  return normalizationKeys.every((key) => typeof candidate[key] === "boolean");
+};
+
 export const isValidModerationSettings = (value: unknown): value is ModerationSettings => {
   if (!value || typeof value !== "object") {
     return false;
@@ -50,6 +96,7 @@ export const isValidModerationSettings = (value: unknown): value is ModerationSe
     Array.isArray(candidate.profanityWords) &&
     candidate.profanityWords.every((word) => typeof word === "string") &&
     Array.isArray(candidate.rules) &&
-    candidate.rules.every(isRule)
+    candidate.rules.every(isRule) &&
+    (candidate.normalization === undefined || isNormalization(candidate.normalization))
   );
 };
@@ -5,7 +5,14 @@ type BrandPalette = {
   light: string;
 };
 
-type BadgeVariant = "queued" | "running" | "completed" | "failed" | "cancelled";
+type BadgeVariant =
+  | "queued"
+  | "running"
+  | "paused"
+  | "completed"
+  | "failed"
+  | "cancelled"
+  | "interrupted";
 
 const brandPalette: BrandPalette = {
   dark: "#88322d",
@@ -18,6 +25,8 @@ const statusClassByVariant: Record<BadgeVariant, string> = {
   cancelled: "bg-[#d1968f]/35 text-[#88322d]",
   completed: "bg-emerald-100 text-emerald-800",
   failed: "bg-rose-100 text-rose-800",
+  interrupted: "bg-amber-100 text-amber-800",
+  paused: "bg-[#f1d1b1]/70 text-[#88322d]",
   queued: "bg-[#f1d1b1]/45 text-[#88322d]",
   running: "bg-[#d1968f]/45 text-[#88322d]",
 };